@@ -0,0 +1,55 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::fmt::Write;
+use std::hint::black_box;
+
+/// A large document, where every element has several attributes and some of them contain more than one url
+fn large_xml() -> String {
+    let mut xml = String::from(r#"<?xml version="1.0"?><catalog xmlns="https://ns.bench.com">"#);
+    for i in 0..5_000 {
+        let _ = write!(
+            xml,
+            r#"<item id="item-{i}" class="entry" href="https://{i}.bench.com/page" data-mirrors="https://a.bench.com/{i} https://b.bench.com/{i} https://c.bench.com/{i}" title="Item {i}" lang="en">See https://text.bench.com/{i}</item>"#
+        );
+    }
+    xml.push_str("</catalog>");
+    xml
+}
+
+/// A generated document with few elements, that each have hundreds of attributes
+fn wide_xml(attributes: usize) -> String {
+    let mut xml = String::from("<config>");
+    for i in 0..100 {
+        xml.push_str("<entry");
+        for j in 0..attributes {
+            let _ = write!(
+                xml,
+                r#" option-{j}="value {j} https://{i}-{j}.bench.com/page""#
+            );
+        }
+        xml.push_str("/>");
+    }
+    xml.push_str("</config>");
+    xml
+}
+
+fn bench_xml_scraping(c: &mut Criterion) {
+    let xml = large_xml();
+    let mut group = c.benchmark_group("xml");
+    group.throughput(Throughput::Bytes(xml.len() as u64));
+    group.bench_function("scrape", |b| {
+        b.iter(|| link_scraper::formats::xml::scrape(black_box(xml.as_bytes())).unwrap())
+    });
+    group.finish();
+
+    // With the feature `rayon`, the attributes of these elements are scraped in parallel
+    let wide = wide_xml(500);
+    let mut group = c.benchmark_group("xml_wide_elements");
+    group.throughput(Throughput::Bytes(wide.len() as u64));
+    group.bench_function("scrape", |b| {
+        b.iter(|| link_scraper::formats::xml::scrape(black_box(wide.as_bytes())).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_xml_scraping);
+criterion_main!(benches);
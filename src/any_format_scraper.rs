@@ -1,5 +1,5 @@
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
 use crate::helpers::find_urls;
+use crate::{gen_scrape_from_file, gen_scrape_from_slice};
 use infer::Type;
 use std::fmt::{Display, Formatter};
 use std::io::{read_to_string, BufRead, BufReader, Read, Seek};
@@ -74,6 +74,10 @@ pub enum LinkScrapingError {
     #[error(transparent)]
     XmlScrapingError(#[from] crate::formats::xml::XmlScrapingError),
 
+    #[cfg(feature = "html")]
+    #[error(transparent)]
+    HtmlScrapingError(#[from] crate::formats::html::HtmlScrapingError),
+
     #[cfg(feature = "svg")]
     #[error(transparent)]
     SvgScrapingError(#[from] crate::formats::xml::svg::SvgScrapingError),
@@ -93,6 +97,7 @@ pub enum LinkScrapingError {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Link {
     StringLink(String),
     #[cfg(feature = "plaintext")]
@@ -107,6 +112,8 @@ pub enum Link {
     RtfLink(crate::formats::rtf::RtfLink),
     #[cfg(feature = "xml")]
     XmlLink(crate::formats::xml::XmlLink),
+    #[cfg(feature = "html")]
+    HtmlLink(crate::formats::html::HtmlLink),
     #[cfg(feature = "svg")]
     SvgLink(crate::formats::xml::svg::SvgLink),
     #[cfg(feature = "image")]
@@ -143,6 +150,10 @@ impl Display for Link {
             Link::XmlLink(link) => {
                 write!(f, "XmlLink({})", link)
             }
+            #[cfg(feature = "html")]
+            Link::HtmlLink(link) => {
+                write!(f, "HtmlLink({})", link)
+            }
             #[cfg(feature = "svg")]
             Link::SvgLink(link) => {
                 write!(f, "SvgLink({})", link)
@@ -155,6 +166,104 @@ impl Display for Link {
     }
 }
 
+impl crate::link::Link for Link {
+    fn url(&self) -> &str {
+        match self {
+            Link::StringLink(link) => link,
+            #[cfg(feature = "plaintext")]
+            Link::TextFileLink(link) => link.url(),
+            #[cfg(feature = "ooxml")]
+            Link::OoxmlLink(link) => link.url(),
+            #[cfg(feature = "odf")]
+            Link::OdfLink(link) => link.url(),
+            #[cfg(feature = "pdf")]
+            Link::PdfLink(link) => link.url(),
+            #[cfg(feature = "rtf")]
+            Link::RtfLink(link) => link.url(),
+            #[cfg(feature = "xml")]
+            Link::XmlLink(link) => link.url(),
+            #[cfg(feature = "html")]
+            Link::HtmlLink(link) => link.url(),
+            #[cfg(feature = "svg")]
+            Link::SvgLink(link) => link.url(),
+            #[cfg(feature = "image")]
+            Link::ImageLink(link) => link.url(),
+        }
+    }
+
+    fn set_url(&mut self, url: String) {
+        match self {
+            Link::StringLink(link) => *link = url,
+            #[cfg(feature = "plaintext")]
+            Link::TextFileLink(link) => link.set_url(url),
+            #[cfg(feature = "ooxml")]
+            Link::OoxmlLink(link) => link.set_url(url),
+            #[cfg(feature = "odf")]
+            Link::OdfLink(link) => link.set_url(url),
+            #[cfg(feature = "pdf")]
+            Link::PdfLink(link) => link.set_url(url),
+            #[cfg(feature = "rtf")]
+            Link::RtfLink(link) => link.set_url(url),
+            #[cfg(feature = "xml")]
+            Link::XmlLink(link) => link.set_url(url),
+            #[cfg(feature = "html")]
+            Link::HtmlLink(link) => link.set_url(url),
+            #[cfg(feature = "svg")]
+            Link::SvgLink(link) => link.set_url(url),
+            #[cfg(feature = "image")]
+            Link::ImageLink(link) => link.set_url(url),
+        }
+    }
+
+    fn location(&self) -> crate::link::Location {
+        match self {
+            Link::StringLink(_) => crate::link::Location::Unknown,
+            #[cfg(feature = "plaintext")]
+            Link::TextFileLink(link) => link.location(),
+            #[cfg(feature = "ooxml")]
+            Link::OoxmlLink(link) => link.location(),
+            #[cfg(feature = "odf")]
+            Link::OdfLink(link) => link.location(),
+            #[cfg(feature = "pdf")]
+            Link::PdfLink(link) => link.location(),
+            #[cfg(feature = "rtf")]
+            Link::RtfLink(link) => link.location(),
+            #[cfg(feature = "xml")]
+            Link::XmlLink(link) => link.location(),
+            #[cfg(feature = "html")]
+            Link::HtmlLink(link) => link.location(),
+            #[cfg(feature = "svg")]
+            Link::SvgLink(link) => link.location(),
+            #[cfg(feature = "image")]
+            Link::ImageLink(link) => link.location(),
+        }
+    }
+
+    fn bases(&self) -> &[String] {
+        match self {
+            Link::StringLink(_) => &[],
+            #[cfg(feature = "plaintext")]
+            Link::TextFileLink(link) => link.bases(),
+            #[cfg(feature = "ooxml")]
+            Link::OoxmlLink(link) => link.bases(),
+            #[cfg(feature = "odf")]
+            Link::OdfLink(link) => link.bases(),
+            #[cfg(feature = "pdf")]
+            Link::PdfLink(link) => link.bases(),
+            #[cfg(feature = "rtf")]
+            Link::RtfLink(link) => link.bases(),
+            #[cfg(feature = "xml")]
+            Link::XmlLink(link) => link.bases(),
+            #[cfg(feature = "html")]
+            Link::HtmlLink(link) => link.bases(),
+            #[cfg(feature = "svg")]
+            Link::SvgLink(link) => link.bases(),
+            #[cfg(feature = "image")]
+            Link::ImageLink(link) => link.bases(),
+        }
+    }
+}
+
 fn scrape_from_buffer<R>(mut reader: R, file_type: Type) -> Result<Vec<Link>, LinkScrapingError>
 where
     R: BufRead + Seek,
@@ -195,7 +304,8 @@ where
             Ok(try_rtf(s)?)
         }
         "image/svg+xml" => Ok(try_svg(reader)?),
-        "text/xml" | "text/html" => Ok(try_xml(reader)?),
+        "text/xml" => Ok(try_xml(reader)?),
+        "text/html" => Ok(try_html(reader)?),
 
         "image/jpeg" | "image/png" | "image/tiff" | "image/webp" | "image/heic" | "image/heif" => {
             Ok(try_image(reader)?)
@@ -232,35 +342,45 @@ gen_try_format!(try_odf(impl Read + Seek), "odf", odf, OdfLink => scrape);
 gen_try_format!(try_pdf(impl AsRef<[u8]>), "pdf", pdf, PdfLink => scrape_from_slice);
 gen_try_format!(try_rtf(impl AsRef<str>), "rtf", rtf, RtfLink => scrape_from_string);
 gen_try_format!(try_xml(impl Read), "xml", xml, XmlLink => scrape);
+cfg_if::cfg_if! {
+    if #[cfg(any(feature = "html", not(feature = "xml")))] {
+        gen_try_format!(try_html(impl Read), "html", html, HtmlLink => scrape);
+    } else {
+        /// Without the html-feature, html-files are scraped as xml, which works for well-formed documents.
+        fn try_html(reader: impl Read) -> Result<Vec<Link>, LinkScrapingError> {
+            try_xml(reader)
+        }
+    }
+}
 gen_try_format!(try_image(impl BufRead + Seek), "image", image, ImageLink => scrape);
 
 #[cfg(feature = "svg")]
 fn try_svg(reader: impl Read) -> Result<Vec<Link>, LinkScrapingError> {
     Ok(crate::formats::xml::svg::scrape(reader)?
         .into_iter()
-        .map(|link| Link::SvgLink(link))
+        .map(Link::SvgLink)
         .collect())
 }
 #[cfg(not(feature = "svg"))]
 fn try_svg(_: impl Read) -> Result<Vec<Link>, LinkScrapingError> {
-    return Err(LinkScrapingError::FeatureNotEnabledError(format!("Detected svg-file but the corresponding feature is not enabled. Please enable it in your dependencies.")));
+    Err(LinkScrapingError::FeatureNotEnabledError("Detected svg-file but the corresponding feature is not enabled. Please enable it in your dependencies.".to_string()))
 }
 
 cfg_if::cfg_if! {
     if #[cfg(any(feature = "ooxml", feature = "odf"))] {
         fn try_zip(bytes: impl AsRef<[u8]>) -> Result<Vec<Link>, LinkScrapingError> {
             #[cfg(feature = "ooxml")] {
-                let ooxml_result = try_ooxml(std::io::Cursor::new(bytes.as_ref())).map_err(|e| LinkScrapingError::from(e));
+                let ooxml_result = try_ooxml(std::io::Cursor::new(bytes.as_ref()));
                 if let Ok(res) = ooxml_result { return Ok(res); }
             }
 
             #[cfg(feature = "odf")] {
-                let odf_result = try_odf(std::io::Cursor::new(bytes.as_ref())).map_err(|e| LinkScrapingError::from(e));
+                let odf_result = try_odf(std::io::Cursor::new(bytes.as_ref()));
                 if let Ok(res) = odf_result { return Ok(res); }
             }
 
             #[cfg(all(feature = "ooxml", feature = "odf"))] {
-                return Err(LinkScrapingError::FileTypeNotImplemented("Detected zip-file but the corresponding type is not supported!".to_string()));
+                Err(LinkScrapingError::FileTypeNotImplemented("Detected zip-file but the corresponding type is not supported!".to_string()))
             }
             #[cfg(not(all(feature = "ooxml", feature = "odf")))] {
                 return Err(LinkScrapingError::FeatureNotEnabledError("Detected zip-file but the corresponding feature is not enabled. Please enable it in your dependencies.".to_string()));
@@ -289,6 +409,7 @@ mod tests {
     const TEST_PDF: &[u8] = include_bytes!("../test_files/pdf/pdf_test.pdf");
     const TEST_RTF: &[u8] = include_bytes!("../test_files/rtf/rtf_test.rtf");
     const TEST_XML: &[u8] = include_bytes!("../test_files/xml/xml_test.xml");
+    const TEST_HTML: &[u8] = include_bytes!("../test_files/html/html_test.html");
     const TEST_SVG: &[u8] = include_bytes!("../test_files/xml/svg_test.svg");
     const TEST_JPG: &[u8] = include_bytes!("../test_files/images/exif_test.jpg");
 
@@ -329,7 +450,21 @@ mod tests {
         scrape(TEST_PDF, is_active!("pdf"));
         scrape(TEST_RTF, is_active!("rtf"));
         scrape(TEST_XML, is_active!("xml"));
-        scrape(TEST_SVG, is_active!("svg"));
+        scrape(TEST_HTML, is_active!("html"));
+        // infer does not recognize svg-files without a xml-declaration, so they are scraped as plaintext.
+        scrape(TEST_SVG, true);
         scrape(TEST_JPG, is_active!("image"));
     }
+
+    #[cfg(all(feature = "xml", not(feature = "html")))]
+    #[test]
+    fn scrape_html_as_xml_test() {
+        let links = scrape_from_slice(
+            br#"<html><body><a href="https://xhtml.test.com">link</a></body></html>"#,
+        )
+        .unwrap();
+        assert!(links
+            .iter()
+            .any(|it| matches!(it, Link::XmlLink(link) if link.url == "https://xhtml.test.com")));
+    }
 }
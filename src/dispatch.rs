@@ -0,0 +1,1191 @@
+use crate::link::{Link, Location};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+#[cfg(feature = "rayon")]
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Scrapes links from the file at `path`, choosing the format by its content and file-extension.
+///
+/// If [`detect_format`] recognizes the first bytes of the file unambiguously, that format is used,
+/// regardless of the extension. Otherwise the format is chosen by the file-extension,
+/// or by the most likely of the detected candidates if the extension is unknown or missing.
+pub fn scrape_any(path: &Path) -> Result<Vec<Box<dyn Link>>, ScrapeError> {
+    let mut head = Vec::with_capacity(SNIFF_LENGTH);
+    File::open(path)?
+        .take(SNIFF_LENGTH as u64)
+        .read_to_end(&mut head)?;
+
+    scrape_file_as(path, choose_format(path, &head))
+}
+
+/// Like [`scrape_any`], but keeps the format-specific links inside a [`ScrapedLink`] instead of boxing them.
+pub fn scrape_any_typed(path: &Path) -> Result<Vec<ScrapedLink>, ScrapeError> {
+    let mut head = Vec::with_capacity(SNIFF_LENGTH);
+    File::open(path)?
+        .take(SNIFF_LENGTH as u64)
+        .read_to_end(&mut head)?;
+
+    scrape_file_as_typed(path, choose_format(path, &head))
+}
+
+/// A path with the result of scraping the file at it, as returned by [`scrape_paths`]
+#[cfg(feature = "rayon")]
+pub type ScrapedPath = (PathBuf, Result<Vec<Box<dyn Link>>, ScrapeError>);
+
+/// Scrapes the files at `paths` in parallel with [`scrape_any`], using the global thread-pool of rayon.
+///
+/// The results are returned in the order of `paths`.
+#[cfg(feature = "rayon")]
+pub fn scrape_paths(paths: impl IntoIterator<Item = PathBuf>) -> Vec<ScrapedPath> {
+    use rayon::prelude::*;
+
+    paths
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|path| {
+            let result = scrape_any(&path);
+            (path, result)
+        })
+        .collect()
+}
+
+/// Like [`scrape_paths`], but runs on its own thread-pool configured by `options`.
+/// # Example
+/// ```
+/// # #[cfg(feature = "plaintext")] {
+/// use link_scraper::dispatch::{scrape_paths_with_options, ScrapePathsOptions};
+/// let paths = vec!["test_files/plaintext/plaintext_test.txt".into()];
+/// let results = scrape_paths_with_options(paths, &ScrapePathsOptions { threads: Some(2) }).unwrap();
+/// assert_eq!(results[0].1.as_ref().unwrap()[0].url(), "https://plaintext.test.com");
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn scrape_paths_with_options(
+    paths: impl IntoIterator<Item = PathBuf>,
+    options: &ScrapePathsOptions,
+) -> Result<Vec<ScrapedPath>, rayon::ThreadPoolBuildError> {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(threads) = options.threads {
+        builder = builder.num_threads(threads);
+    }
+    let pool = builder.build()?;
+    let paths: Vec<PathBuf> = paths.into_iter().collect();
+    Ok(pool.install(|| scrape_paths(paths)))
+}
+
+/// How [`scrape_paths_with_options`] scrapes the files
+#[cfg(feature = "rayon")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScrapePathsOptions {
+    /// The number of threads scraping files at once. `None` uses one thread per cpu-core.
+    pub threads: Option<usize>,
+}
+
+/// The format [`scrape_any`] uses for the file at `path` starting with `head`
+fn choose_format(path: &Path, head: &[u8]) -> Format {
+    match detect_format(head) {
+        DetectedFormat::Certain(format) => format,
+        detected => Format::from_path(path).unwrap_or(detected.candidates()[0]),
+    }
+}
+
+/// The number of bytes [`scrape_any`] passes to [`detect_format`]
+pub const SNIFF_LENGTH: usize = 8192;
+
+/// Guesses the format of a file by its first bytes.
+///
+/// Binary formats are recognized by their signature (see [`infer`]).
+/// Markup is recognized by its doctype or root-element, after skipping the xml-declaration,
+/// processing-instructions and comments. Only `<html>` and `<svg>` root-elements are certain,
+/// all other markup is ambiguous, e.g. an html-fragment with an inline `<svg>`.
+/// Everything else is reported as [`DetectedFormat::Unknown`].
+/// # Example
+/// ```
+/// use link_scraper::dispatch::{detect_format, DetectedFormat, Format};
+/// assert_eq!(detect_format(b"%PDF-1.7"), DetectedFormat::Certain(Format::Pdf));
+/// assert_eq!(detect_format(b"<?xml version='1.0'?><svg/>"), DetectedFormat::Certain(Format::Svg));
+/// assert_eq!(detect_format(b"just text").candidates(), vec![Format::PlainText]);
+/// ```
+pub fn detect_format(bytes: &[u8]) -> DetectedFormat {
+    if let Some(kind) = infer::get(bytes) {
+        if kind.mime_type() == "application/zip" {
+            return detect_zip_format(bytes);
+        }
+        if let Some(format) = binary_format(kind.mime_type()) {
+            return DetectedFormat::Certain(format);
+        }
+    }
+
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let bytes = trim_start(bytes);
+    if bytes.starts_with(b"<") {
+        return detect_markup_format(bytes);
+    }
+    if bytes.starts_with(b"{") || bytes.starts_with(b"[") {
+        return DetectedFormat::Ambiguous(vec![Format::Json, Format::PlainText]);
+    }
+
+    DetectedFormat::Unknown
+}
+
+fn binary_format(mime_type: &str) -> Option<Format> {
+    match mime_type {
+        "application/pdf" => Some(Format::Pdf),
+        "application/rtf" => Some(Format::Rtf),
+        ooxml if ooxml.starts_with("application/vnd.openxmlformats-officedocument.") => {
+            Some(Format::Ooxml)
+        }
+        odf if odf.starts_with("application/vnd.oasis.opendocument.") => Some(Format::Odf),
+        "image/jpeg" | "image/png" | "image/tiff" | "image/webp" | "image/heic" | "image/heif" => {
+            Some(Format::Image)
+        }
+        _ => None,
+    }
+}
+
+/// infer only recognizes archives, that start with the files specific to their format
+fn detect_zip_format(bytes: &[u8]) -> DetectedFormat {
+    if find(bytes, b"mimetypeapplication/vnd.oasis.opendocument").is_some() {
+        DetectedFormat::Certain(Format::Odf)
+    } else if find(bytes, b"[Content_Types].xml").is_some() {
+        DetectedFormat::Certain(Format::Ooxml)
+    } else {
+        DetectedFormat::Ambiguous(vec![Format::Ooxml, Format::Odf])
+    }
+}
+
+fn detect_markup_format(bytes: &[u8]) -> DetectedFormat {
+    let lowercase = bytes.to_ascii_lowercase();
+    let has_xml_declaration = lowercase.starts_with(b"<?xml");
+
+    let mut rest = &lowercase[..];
+    let root = loop {
+        rest = trim_start(rest);
+        let skip_to = if rest.starts_with(b"<?") {
+            b"?>".as_slice()
+        } else if rest.starts_with(b"<!--") {
+            b"-->"
+        } else if let Some(doctype) = rest.strip_prefix(b"<!doctype") {
+            match trim_start(doctype) {
+                html if html.starts_with(b"html") => return DetectedFormat::Certain(Format::Html),
+                svg if svg.starts_with(b"svg") => return DetectedFormat::Certain(Format::Svg),
+                // An internal subset may contain '>'
+                _ if find(rest, b"[").is_some_and(|it| Some(it) < find(rest, b">")) => {
+                    b"]>".as_slice()
+                }
+                _ => b">".as_slice(),
+            }
+        } else if let Some(element) = rest.strip_prefix(b"<") {
+            let name_length = element
+                .iter()
+                .position(|b| b.is_ascii_whitespace() || matches!(b, b'>' | b'/'))
+                .unwrap_or(element.len());
+            break Some(&element[..name_length]);
+        } else {
+            break None;
+        };
+
+        match find(rest, skip_to) {
+            Some(index) => rest = &rest[index + skip_to.len()..],
+            // The prolog is longer than the sniffed bytes
+            None => break None,
+        }
+    };
+
+    // Ignore the prefix of namespaced root-elements (e.g. `<svg:svg>`)
+    let local_name = root.map(|name| match name.iter().rposition(|b| *b == b':') {
+        Some(colon) => &name[colon + 1..],
+        None => name,
+    });
+    match local_name {
+        Some(b"html") => DetectedFormat::Certain(Format::Html),
+        Some(b"svg") => DetectedFormat::Certain(Format::Svg),
+        Some(_) if has_xml_declaration => DetectedFormat::Ambiguous(vec![Format::Xml]),
+        None if has_xml_declaration => DetectedFormat::Ambiguous(vec![Format::Xml, Format::Svg]),
+        _ => DetectedFormat::Ambiguous(vec![Format::Xml, Format::Html, Format::PlainText]),
+    }
+}
+
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    &bytes[bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len())..]
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// The result of [`detect_format`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetectedFormat {
+    /// The content has the signature of exactly one format
+    Certain(Format),
+    /// The content may belong to any of the formats, which are ordered from most to least likely
+    Ambiguous(Vec<Format>),
+    /// The content has no known signature, so it is probably plaintext
+    Unknown,
+}
+
+impl DetectedFormat {
+    /// All formats the content may belong to, ordered from most to least likely.
+    /// [`DetectedFormat::Unknown`] falls back to [`Format::PlainText`].
+    pub fn candidates(&self) -> Vec<Format> {
+        match self {
+            DetectedFormat::Certain(format) => vec![*format],
+            DetectedFormat::Ambiguous(formats) => formats.clone(),
+            DetectedFormat::Unknown => vec![Format::PlainText],
+        }
+    }
+}
+
+/// Scrapes links from the file at `path` with the scraper of `format`.
+pub fn scrape_file_as(path: &Path, format: Format) -> Result<Vec<Box<dyn Link>>, ScrapeError> {
+    Ok(scrape_file_as_typed(path, format)?
+        .into_iter()
+        .map(|link| Box::new(link) as Box<dyn Link>)
+        .collect())
+}
+
+/// Like [`scrape_file_as`], but keeps the format-specific links inside a [`ScrapedLink`] instead of boxing them.
+pub fn scrape_file_as_typed(path: &Path, format: Format) -> Result<Vec<ScrapedLink>, ScrapeError> {
+    macro_rules! scrape_with {
+        ($feature:literal, $module:ident $(::$submodule:ident)*) => {{
+            #[cfg(feature = $feature)]
+            {
+                Ok(crate::formats::$module$(::$submodule)*::scrape_from_file(path)?
+                    .into_iter()
+                    .map(ScrapedLink::from)
+                    .collect())
+            }
+            #[cfg(not(feature = $feature))]
+            {
+                let _ = path;
+                Err(ScrapeError::FeatureNotEnabledError(format))
+            }
+        }};
+    }
+
+    match format {
+        Format::PlainText => scrape_with!("plaintext", plaintext),
+        Format::Html => scrape_with!("html", html),
+        Format::Markdown => scrape_with!("markdown", markdown),
+        Format::Json => scrape_with!("json", json),
+        Format::Xml => scrape_with!("xml", xml),
+        Format::Svg => scrape_with!("svg", xml::svg),
+        Format::XLink => scrape_with!("xlink", xml::xlink),
+        Format::Ooxml => scrape_with!("ooxml", ooxml),
+        Format::Odf => scrape_with!("odf", odf),
+        Format::Pdf => scrape_with!("pdf", pdf),
+        Format::Rtf => scrape_with!("rtf", rtf),
+        Format::Image => scrape_with!("image", image),
+        Format::Csv => scrape_with!("csv", csv),
+        Format::Tsv => {
+            #[cfg(feature = "csv")]
+            {
+                use crate::formats::csv::{scrape_with_options, CsvScrapeOptions};
+                Ok(
+                    scrape_with_options(File::open(path)?, &CsvScrapeOptions::tsv())?
+                        .into_iter()
+                        .map(ScrapedLink::from)
+                        .collect(),
+                )
+            }
+            #[cfg(not(feature = "csv"))]
+            {
+                let _ = path;
+                Err(ScrapeError::FeatureNotEnabledError(format))
+            }
+        }
+        Format::Yaml => scrape_with!("yaml", yaml),
+        Format::Toml => scrape_with!("toml", toml),
+        Format::Ical => scrape_with!("ical", ical),
+        Format::Ini => scrape_with!("ini", ini),
+        Format::Shortcut => scrape_with!("shortcut", shortcut),
+        Format::Css => scrape_with!("css", css),
+        Format::Subtitle => scrape_with!("subtitle", subtitle),
+        Format::Feed => scrape_with!("feed", feed),
+        Format::Geo => scrape_with!("geo", geo),
+        Format::Opml => scrape_with!("opml", opml),
+        Format::Plist => scrape_with!("plist", plist),
+        Format::Email => scrape_with!("email", email),
+        Format::Epub => scrape_with!("epub", epub),
+        Format::Warc => scrape_with!("warc", warc),
+        // The language is given, so it is not chosen by the extension like in `source_code::scrape_from_file`
+        #[cfg(feature = "source_code")]
+        Format::SourceCode(language) => Ok(crate::formats::source_code::scrape(
+            File::open(path)?,
+            language,
+        )?
+        .into_iter()
+        .map(ScrapedLink::from)
+        .collect()),
+    }
+}
+
+/// Scrapes links from `reader` with the scraper of `format`,
+/// for callers that already know the format (e.g. from a MIME-type) and hold no file.
+///
+/// The content is read completely before it is scraped, as some formats (like [`Format::Ooxml`]) have to seek in it.
+/// # Example
+/// ```
+/// # #[cfg(feature = "xml")]
+/// # {
+/// use link_scraper::dispatch::{scrape_reader, Format};
+/// use link_scraper::link::Link;
+/// let links = scrape_reader(&b"<a href='https://example.com'/>"[..], Format::Xml).unwrap();
+/// assert_eq!(links[0].url(), "https://example.com");
+/// # }
+/// ```
+pub fn scrape_reader<R>(mut reader: R, format: Format) -> Result<Vec<ScrapedLink>, ScrapeError>
+where
+    R: Read,
+{
+    let mut buffer = vec![];
+    reader.read_to_end(&mut buffer)?;
+
+    macro_rules! scrape_with {
+        ($feature:literal, $module:ident $(::$submodule:ident)*) => {{
+            #[cfg(feature = $feature)]
+            {
+                Ok(crate::formats::$module$(::$submodule)*::scrape_from_slice(&buffer)?
+                    .into_iter()
+                    .map(ScrapedLink::from)
+                    .collect())
+            }
+            #[cfg(not(feature = $feature))]
+            {
+                Err(ScrapeError::FeatureNotEnabledError(format))
+            }
+        }};
+    }
+
+    match format {
+        Format::PlainText => scrape_with!("plaintext", plaintext),
+        Format::Html => scrape_with!("html", html),
+        Format::Markdown => scrape_with!("markdown", markdown),
+        Format::Json => scrape_with!("json", json),
+        Format::Xml => scrape_with!("xml", xml),
+        Format::Svg => scrape_with!("svg", xml::svg),
+        Format::XLink => scrape_with!("xlink", xml::xlink),
+        Format::Ooxml => scrape_with!("ooxml", ooxml),
+        Format::Odf => scrape_with!("odf", odf),
+        Format::Pdf => scrape_with!("pdf", pdf),
+        Format::Rtf => scrape_with!("rtf", rtf),
+        Format::Image => scrape_with!("image", image),
+        Format::Csv => scrape_with!("csv", csv),
+        Format::Tsv => {
+            #[cfg(feature = "csv")]
+            {
+                use crate::formats::csv::{scrape_with_options, CsvScrapeOptions};
+                Ok(scrape_with_options(&buffer[..], &CsvScrapeOptions::tsv())?
+                    .into_iter()
+                    .map(ScrapedLink::from)
+                    .collect())
+            }
+            #[cfg(not(feature = "csv"))]
+            {
+                Err(ScrapeError::FeatureNotEnabledError(format))
+            }
+        }
+        Format::Yaml => scrape_with!("yaml", yaml),
+        Format::Toml => scrape_with!("toml", toml),
+        Format::Ical => scrape_with!("ical", ical),
+        Format::Ini => scrape_with!("ini", ini),
+        Format::Shortcut => scrape_with!("shortcut", shortcut),
+        Format::Css => scrape_with!("css", css),
+        Format::Subtitle => scrape_with!("subtitle", subtitle),
+        Format::Feed => scrape_with!("feed", feed),
+        Format::Geo => scrape_with!("geo", geo),
+        Format::Opml => scrape_with!("opml", opml),
+        Format::Plist => scrape_with!("plist", plist),
+        Format::Email => scrape_with!("email", email),
+        Format::Epub => scrape_with!("epub", epub),
+        Format::Warc => scrape_with!("warc", warc),
+        #[cfg(feature = "source_code")]
+        Format::SourceCode(language) => Ok(crate::formats::source_code::scrape_from_slice(
+            &buffer, language,
+        )?
+        .into_iter()
+        .map(ScrapedLink::from)
+        .collect()),
+    }
+}
+
+/// The formats, that can be scraped by [`scrape_file_as`] and [`scrape_reader`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    PlainText,
+    Html,
+    Markdown,
+    Json,
+    Xml,
+    Svg,
+    /// Xml-documents with xlink-elements. Never detected, as they cannot be told apart from other xml.
+    XLink,
+    /// .docx, .pptx, .xlsx
+    Ooxml,
+    /// .odt, .ods, .odp
+    Odf,
+    Pdf,
+    Rtf,
+    /// Images with exif-data
+    Image,
+    Csv,
+    /// Tab-separated files, scraped by the scraper of [`Format::Csv`]
+    Tsv,
+    Yaml,
+    Toml,
+    /// .ics
+    Ical,
+    Ini,
+    /// .url, .desktop
+    Shortcut,
+    Css,
+    /// .srt, .vtt
+    Subtitle,
+    /// RSS- and Atom-feeds
+    Feed,
+    /// .gpx, .kml
+    Geo,
+    Opml,
+    /// Apple property lists, in XML- and binary encoding
+    Plist,
+    /// .eml
+    Email,
+    Epub,
+    /// Web archives, whose responses are scraped by their `Content-Type`
+    Warc,
+    /// Source files written in the given language.
+    /// Only exists with the source_code-feature, as the language is defined by it.
+    #[cfg(feature = "source_code")]
+    SourceCode(crate::formats::source_code::Language),
+}
+
+impl Format {
+    /// Guesses the format by the extension (case-insensitive) of `path`.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        Self::from_extension(path.extension()?.to_str()?)
+    }
+
+    /// Guesses the format by a file-extension (case-insensitive) without the leading dot.
+    pub fn from_extension(extension: &str) -> Option<Format> {
+        let format = match extension.to_ascii_lowercase().as_str() {
+            "txt" | "text" | "log" => Format::PlainText,
+            "csv" => Format::Csv,
+            "tsv" => Format::Tsv,
+            "css" => Format::Css,
+            "html" | "htm" | "xhtml" => Format::Html,
+            "md" | "markdown" => Format::Markdown,
+            "json" => Format::Json,
+            "xml" | "xsl" | "xslt" | "xsd" => Format::Xml,
+            "svg" => Format::Svg,
+            "docx" | "docm" | "dotx" | "xlsx" | "xlsm" | "xltx" | "pptx" | "pptm" | "ppsx"
+            | "potx" => Format::Ooxml,
+            "odt" | "ods" | "odp" | "odg" | "ott" | "ots" | "otp" => Format::Odf,
+            "pdf" => Format::Pdf,
+            "rtf" => Format::Rtf,
+            "jpg" | "jpeg" | "png" | "tif" | "tiff" | "webp" | "heic" | "heif" => Format::Image,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// Guesses the format by a MIME-type (case-insensitive), like the value of a `Content-Type`-header.
+    /// Parameters like `; charset=utf-8` are ignored, unknown `text/*`-types are treated as plaintext.
+    /// # Example
+    /// ```
+    /// use link_scraper::dispatch::Format;
+    /// assert_eq!(Format::from_mime_type("text/html; charset=UTF-8"), Some(Format::Html));
+    /// assert_eq!(Format::from_mime_type("application/rss+xml"), Some(Format::Xml));
+    /// assert_eq!(Format::from_mime_type("application/octet-stream"), None);
+    /// ```
+    pub fn from_mime_type(mime_type: &str) -> Option<Format> {
+        let essence = mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let format = match essence.as_str() {
+            "text/html" | "application/xhtml+xml" => Format::Html,
+            "image/svg+xml" => Format::Svg,
+            "text/markdown" => Format::Markdown,
+            "application/json" => Format::Json,
+            json if json.ends_with("+json") => Format::Json,
+            "application/xml" | "text/xml" => Format::Xml,
+            xml if xml.ends_with("+xml") => Format::Xml,
+            "text/rtf" => Format::Rtf,
+            "text/csv" => Format::Csv,
+            "text/tab-separated-values" => Format::Tsv,
+            "text/css" => Format::Css,
+            binary if binary_format(binary).is_some() => return binary_format(binary),
+            text if text.starts_with("text/") => Format::PlainText,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// The name of the feature, that enables the scraper of this format
+    pub fn feature(&self) -> &'static str {
+        match self {
+            Format::PlainText => "plaintext",
+            Format::Html => "html",
+            Format::Markdown => "markdown",
+            Format::Json => "json",
+            Format::Xml => "xml",
+            Format::Svg => "svg",
+            Format::XLink => "xlink",
+            Format::Ooxml => "ooxml",
+            Format::Odf => "odf",
+            Format::Pdf => "pdf",
+            Format::Rtf => "rtf",
+            Format::Image => "image",
+            Format::Csv | Format::Tsv => "csv",
+            Format::Yaml => "yaml",
+            Format::Toml => "toml",
+            Format::Ical => "ical",
+            Format::Ini => "ini",
+            Format::Shortcut => "shortcut",
+            Format::Css => "css",
+            Format::Subtitle => "subtitle",
+            Format::Feed => "feed",
+            Format::Geo => "geo",
+            Format::Opml => "opml",
+            Format::Plist => "plist",
+            Format::Email => "email",
+            Format::Epub => "epub",
+            Format::Warc => "warc",
+            #[cfg(feature = "source_code")]
+            Format::SourceCode(_) => "source_code",
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.feature())
+    }
+}
+
+/// Defines [`ScrapedLink`] with one variant per link-type, and the `From`-impls of the link-types
+macro_rules! scraped_link {
+    ($($(#[$attr:meta])* $variant:ident($feature:literal, $link:ty)),* $(,)?) => {
+        /// A link of any format, that keeps its format-specific data.
+        ///
+        /// Use this instead of `Box<dyn Link>` to collect the links of several formats,
+        /// if you need to match on their type later on. The variants only exist with the features of their formats.
+        #[derive(Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[non_exhaustive]
+        pub enum ScrapedLink {
+            $(
+                $(#[$attr])*
+                #[cfg(feature = $feature)]
+                $variant($link),
+            )*
+        }
+
+        $(
+            #[cfg(feature = $feature)]
+            impl From<$link> for ScrapedLink {
+                fn from(link: $link) -> Self {
+                    ScrapedLink::$variant(link)
+                }
+            }
+        )*
+
+        impl ScrapedLink {
+            /// The link as trait-object, to access whatever the variant contains
+            pub fn as_link(&self) -> &dyn Link {
+                // Dereferenced, so the match is still valid without any variants
+                match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        ScrapedLink::$variant(ref link) => link,
+                    )*
+                }
+            }
+
+            fn as_link_mut(&mut self) -> &mut dyn Link {
+                match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        ScrapedLink::$variant(ref mut link) => link,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+scraped_link! {
+    Text("plaintext", crate::formats::plaintext::TextFileLink),
+    Html("html", crate::formats::html::HtmlLink),
+    Markdown("markdown", crate::formats::markdown::MarkdownLink),
+    Json("json", crate::formats::json::JsonLink),
+    Csv("csv", crate::formats::csv::CsvLink),
+    Yaml("yaml", crate::formats::yaml::YamlLink),
+    Toml("toml", crate::formats::toml::TomlLink),
+    Plist("plist", crate::formats::plist::PlistLink),
+    Email("email", crate::formats::email::EmailLink),
+    Ical("ical", crate::formats::ical::IcalLink),
+    Shortcut("shortcut", crate::formats::shortcut::ShortcutLink),
+    Ini("ini", crate::formats::ini::IniLink),
+    Css("css", crate::formats::css::CssLink),
+    Subtitle("subtitle", crate::formats::subtitle::SubtitleLink),
+    /// The xml-based links carrying an xml-kind are boxed, because they are much larger than the links of the other formats
+    Xml("xml", Box<crate::formats::xml::XmlLink>),
+    Svg("svg", Box<crate::formats::xml::svg::SvgLink>),
+    XLink("xlink", crate::formats::xml::xlink::XLinkLink),
+    Feed("feed", crate::formats::feed::FeedLink),
+    Geo("geo", crate::formats::geo::GeoLink),
+    Opml("opml", crate::formats::opml::OpmlLink),
+    /// A link of any ooxml-file, as found by [`crate::formats::ooxml::scrape`]
+    Ooxml("ooxml", crate::formats::ooxml::OoxmlLink),
+    Docx("ooxml", crate::formats::ooxml::docx::DocxLink),
+    Xlsx("ooxml", crate::formats::ooxml::xlsx::XlsxLink),
+    Odf("odf", crate::formats::odf::OdfLink),
+    Epub("epub", Box<crate::formats::epub::EpubLink>),
+    Pdf("pdf", crate::formats::pdf::PdfLink),
+    Rtf("rtf", crate::formats::rtf::RtfLink),
+    SourceCode("source_code", crate::formats::source_code::SourceCodeLink),
+    Image("image", crate::formats::image::ImageLink),
+    /// Boxed, because a [`crate::formats::warc::WarcLink`] contains the link of the response's format
+    Warc("warc", Box<crate::formats::warc::WarcLink>),
+}
+
+/// Implements `From` for the link-types, that are boxed inside of [`ScrapedLink`]
+macro_rules! from_unboxed_link {
+    ($($variant:ident($feature:literal, $link:ty)),* $(,)?) => {
+        $(
+            #[cfg(feature = $feature)]
+            impl From<$link> for ScrapedLink {
+                fn from(link: $link) -> Self {
+                    ScrapedLink::$variant(Box::new(link))
+                }
+            }
+        )*
+    };
+}
+
+from_unboxed_link! {
+    Xml("xml", crate::formats::xml::XmlLink),
+    Svg("svg", crate::formats::xml::svg::SvgLink),
+    Epub("epub", crate::formats::epub::EpubLink),
+    Warc("warc", crate::formats::warc::WarcLink),
+}
+
+impl Link for ScrapedLink {
+    fn url(&self) -> &str {
+        self.as_link().url()
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.as_link_mut().set_url(url)
+    }
+
+    fn location(&self) -> Location {
+        self.as_link().location()
+    }
+
+    fn bases(&self) -> &[String] {
+        self.as_link().bases()
+    }
+}
+
+impl Display for ScrapedLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ScrapeError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    #[cfg(feature = "plaintext")]
+    #[error(transparent)]
+    TextFileScrapingError(#[from] crate::formats::plaintext::TextFileScrapingError),
+
+    #[cfg(feature = "html")]
+    #[error(transparent)]
+    HtmlScrapingError(#[from] crate::formats::html::HtmlScrapingError),
+
+    #[cfg(feature = "markdown")]
+    #[error(transparent)]
+    MarkdownScrapingError(#[from] crate::formats::markdown::MarkdownScrapingError),
+
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    JsonScrapingError(#[from] crate::formats::json::JsonScrapingError),
+
+    #[cfg(feature = "xml")]
+    #[error(transparent)]
+    XmlScrapingError(#[from] crate::formats::xml::XmlScrapingError),
+
+    #[cfg(feature = "svg")]
+    #[error(transparent)]
+    SvgScrapingError(#[from] crate::formats::xml::svg::SvgScrapingError),
+
+    #[cfg(feature = "xlink")]
+    #[error(transparent)]
+    XLinkFormatError(#[from] crate::formats::xml::xlink::XLinkFormatError),
+
+    #[cfg(feature = "ooxml")]
+    #[error(transparent)]
+    OoxmlScrapingError(#[from] crate::formats::ooxml::OoxmlScrapingError),
+
+    #[cfg(feature = "odf")]
+    #[error(transparent)]
+    OdfScrapingError(#[from] crate::formats::odf::OdfScrapingError),
+
+    #[cfg(feature = "pdf")]
+    #[error(transparent)]
+    PdfScrapingError(#[from] crate::formats::pdf::PdfScrapingError),
+
+    #[cfg(feature = "rtf")]
+    #[error(transparent)]
+    RtfScrapingError(#[from] crate::formats::rtf::RtfScrapingError),
+
+    #[cfg(feature = "image")]
+    #[error(transparent)]
+    ImageScrapingError(#[from] crate::formats::image::ImageScrapingError),
+
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    CsvScrapingError(#[from] crate::formats::csv::CsvScrapingError),
+
+    #[cfg(feature = "yaml")]
+    #[error(transparent)]
+    YamlScrapingError(#[from] crate::formats::yaml::YamlScrapingError),
+
+    #[cfg(feature = "toml")]
+    #[error(transparent)]
+    TomlScrapingError(#[from] crate::formats::toml::TomlScrapingError),
+
+    #[cfg(feature = "ical")]
+    #[error(transparent)]
+    IcalScrapingError(#[from] crate::formats::ical::IcalScrapingError),
+
+    #[cfg(feature = "ini")]
+    #[error(transparent)]
+    IniScrapingError(#[from] crate::formats::ini::IniScrapingError),
+
+    #[cfg(feature = "shortcut")]
+    #[error(transparent)]
+    ShortcutScrapingError(#[from] crate::formats::shortcut::ShortcutScrapingError),
+
+    #[cfg(feature = "css")]
+    #[error(transparent)]
+    CssScrapingError(#[from] crate::formats::css::CssScrapingError),
+
+    #[cfg(feature = "subtitle")]
+    #[error(transparent)]
+    SubtitleScrapingError(#[from] crate::formats::subtitle::SubtitleScrapingError),
+
+    #[cfg(feature = "feed")]
+    #[error(transparent)]
+    FeedScrapingError(#[from] crate::formats::feed::FeedScrapingError),
+
+    #[cfg(feature = "geo")]
+    #[error(transparent)]
+    GeoScrapingError(#[from] crate::formats::geo::GeoScrapingError),
+
+    #[cfg(feature = "opml")]
+    #[error(transparent)]
+    OpmlScrapingError(#[from] crate::formats::opml::OpmlScrapingError),
+
+    #[cfg(feature = "plist")]
+    #[error(transparent)]
+    PlistScrapingError(#[from] crate::formats::plist::PlistScrapingError),
+
+    #[cfg(feature = "email")]
+    #[error(transparent)]
+    EmailScrapingError(#[from] crate::formats::email::EmailScrapingError),
+
+    #[cfg(feature = "epub")]
+    #[error(transparent)]
+    EpubScrapingError(#[from] crate::formats::epub::EpubScrapingError),
+
+    #[cfg(feature = "source_code")]
+    #[error(transparent)]
+    SourceCodeScrapingError(#[from] crate::formats::source_code::SourceCodeScrapingError),
+
+    /// Boxed, because a [`crate::formats::warc::WarcScrapingError`] may contain the [`ScrapeError`] of a response
+    #[cfg(feature = "warc")]
+    #[error(transparent)]
+    WarcScrapingError(Box<crate::formats::warc::WarcScrapingError>),
+
+    #[error("Detected {0}-file but the corresponding feature is not enabled. Please enable it in your dependencies.")]
+    FeatureNotEnabledError(Format),
+}
+
+#[cfg(feature = "warc")]
+impl From<crate::formats::warc::WarcScrapingError> for ScrapeError {
+    fn from(error: crate::formats::warc::WarcScrapingError) -> Self {
+        ScrapeError::WarcScrapingError(Box::new(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_from_path_test() {
+        assert_eq!(
+            Format::from_path(Path::new("test_files/xml/svg_test.SVG")),
+            Some(Format::Svg)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("export.CSV")),
+            Some(Format::Csv)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("export.tsv")),
+            Some(Format::Tsv)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("test_files/css/css_test.css")),
+            Some(Format::Css)
+        );
+        assert_eq!(Format::from_path(Path::new("archive.tar.gz")), None);
+        assert_eq!(Format::from_path(Path::new("README")), None);
+    }
+
+    #[test]
+    fn format_from_mime_type_test() {
+        assert_eq!(Format::from_mime_type("TEXT/HTML"), Some(Format::Html));
+        assert_eq!(Format::from_mime_type("image/svg+xml"), Some(Format::Svg));
+        assert_eq!(
+            Format::from_mime_type("application/atom+xml;charset=utf-8"),
+            Some(Format::Xml)
+        );
+        assert_eq!(
+            Format::from_mime_type("application/ld+json"),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::from_mime_type("application/pdf"), Some(Format::Pdf));
+        assert_eq!(Format::from_mime_type("image/png"), Some(Format::Image));
+        assert_eq!(Format::from_mime_type("text/css"), Some(Format::Css));
+        assert_eq!(
+            Format::from_mime_type("text/x-unknown"),
+            Some(Format::PlainText)
+        );
+        assert_eq!(
+            Format::from_mime_type("text/csv; header=present"),
+            Some(Format::Csv)
+        );
+        assert_eq!(
+            Format::from_mime_type("text/tab-separated-values"),
+            Some(Format::Tsv)
+        );
+        assert_eq!(Format::from_mime_type("font/woff2"), None);
+        assert_eq!(Format::from_mime_type(""), None);
+    }
+
+    #[test]
+    fn choose_format_test() {
+        fn choose(path: &str) -> Format {
+            choose_format(Path::new(path), &std::fs::read(path).unwrap())
+        }
+
+        assert_eq!(choose("test_files/xml/svg_test.svg"), Format::Svg);
+        assert_eq!(choose("test_files/xml/xml_test.xml"), Format::Xml);
+        assert_eq!(
+            choose("test_files/xml/svg_mislabeled_test.txt"),
+            Format::Svg
+        );
+        assert_eq!(
+            choose("test_files/html/html_inline_svg_test.html"),
+            Format::Html
+        );
+        assert_eq!(
+            choose("test_files/plaintext/plaintext_test.txt"),
+            Format::PlainText
+        );
+        assert_eq!(
+            choose("test_files/markdown/markdown_test.md"),
+            Format::Markdown
+        );
+        assert_eq!(choose("test_files/json/json_test.json"), Format::Json);
+        // The content is ambiguous, so the extension decides
+        assert_eq!(
+            choose_format(Path::new("fragment.html"), b"<div><svg/></div>"),
+            Format::Html
+        );
+        assert_eq!(choose_format(Path::new("fragment"), b"<div/>"), Format::Xml);
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn scrape_any_svg_test() {
+        let links = scrape_any(Path::new("test_files/xml/svg_test.svg")).unwrap();
+        assert!(!links.is_empty());
+        assert!(links
+            .iter()
+            .all(|it| matches!(it.location(), crate::link::Location::TextPosition(_))));
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn scrape_any_xml_test() {
+        let links = scrape_any(Path::new("test_files/xml/xml_test.xml")).unwrap();
+        assert!(links
+            .iter()
+            .any(|it| it.url() == "https://attribute.test.com"));
+        assert!(links
+            .iter()
+            .all(|it| matches!(it.location(), crate::link::Location::TextPosition(_))));
+    }
+
+    #[cfg(all(feature = "xml", feature = "plaintext"))]
+    #[test]
+    fn scrape_reader_test() {
+        let content = br#"<root xmlns="https://ns.test.com"><a href="https://a.test.com">See https://b.test.com</a></root>"#;
+        let as_xml = scrape_reader(&content[..], Format::Xml).unwrap();
+        let as_text = scrape_reader(&content[..], Format::PlainText).unwrap();
+        // The xml-scraper also reports the namespaces predefined by xml
+        assert_eq!(as_xml.len(), 5);
+        assert_eq!(as_text.len(), 3);
+        assert!(as_xml.iter().all(|it| matches!(it, ScrapedLink::Xml(_))));
+        assert!(as_text.iter().all(|it| matches!(it, ScrapedLink::Text(_))));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn scrape_reader_csv_test() {
+        let links =
+            scrape_reader(&b"name,url\ntest,https://csv.test.com\n"[..], Format::Csv).unwrap();
+        assert_eq!(links.len(), 1);
+        assert!(matches!(&links[0], ScrapedLink::Csv(link) if link.row == 1 && link.column == 1));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn scrape_reader_tsv_test() {
+        let content = b"name\turl, with comma\ntest\thttps://tsv.test.com\n";
+        let links = scrape_reader(&content[..], Format::Tsv).unwrap();
+        assert_eq!(links.len(), 1);
+        assert!(matches!(&links[0], ScrapedLink::Csv(link) if link.row == 1 && link.column == 1));
+        // Split at the comma instead of the tab
+        let links = scrape_reader(&content[..], Format::Csv).unwrap();
+        assert!(matches!(&links[0], ScrapedLink::Csv(link) if link.column == 0));
+    }
+
+    #[cfg(feature = "source_code")]
+    #[test]
+    fn scrape_reader_source_code_test() {
+        use crate::formats::source_code::Language;
+        let content = b"# https://comment.test.com\nx = \"https://string.test.com\"\n";
+        let links = scrape_reader(&content[..], Format::SourceCode(Language::Python)).unwrap();
+        assert_eq!(links.len(), 2);
+        assert!(links
+            .iter()
+            .all(|it| matches!(it, ScrapedLink::SourceCode(_))));
+        // The same content is no comment in rust
+        let links = scrape_reader(&content[..], Format::SourceCode(Language::Rust)).unwrap();
+        assert_eq!(links.len(), 1);
+    }
+
+    #[cfg(feature = "warc")]
+    #[test]
+    fn scrape_reader_warc_test() {
+        let http = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<a href=\"https://warc.test.com\">a</a>";
+        let warc = format!(
+            "WARC/1.1\r\nWARC-Type: response\r\nContent-Length: {}\r\n\r\n{http}\r\n\r\n",
+            http.len()
+        );
+        let links = scrape_reader(warc.as_bytes(), Format::Warc).unwrap();
+        assert_eq!(links.len(), 1);
+        assert!(
+            matches!(&links[0], ScrapedLink::Warc(link) if matches!(link.inner, ScrapedLink::Html(_)))
+        );
+        assert!(matches!(
+            scrape_reader(&b"no record"[..], Format::Warc),
+            Err(ScrapeError::WarcScrapingError(_))
+        ));
+    }
+
+    #[cfg(not(feature = "yaml"))]
+    #[test]
+    fn scrape_reader_feature_not_enabled_test() {
+        assert!(matches!(
+            scrape_reader(&b"url: https://yaml.test.com"[..], Format::Yaml),
+            Err(ScrapeError::FeatureNotEnabledError(Format::Yaml))
+        ));
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn scraped_link_from_xml_link_test() {
+        let xml_link = crate::formats::xml::scrape(&b"<a href='https://xml.test.com'/>"[..])
+            .unwrap()
+            .remove(0);
+        let link = ScrapedLink::from(xml_link.clone());
+        assert_eq!(link.url(), "https://xml.test.com");
+        assert_eq!(link.location(), xml_link.location());
+        assert!(matches!(link, ScrapedLink::Xml(inner) if *inner == xml_link));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn scrape_any_typed_test() {
+        let links = scrape_any_typed(Path::new("test_files/xml/svg_test.svg")).unwrap();
+        assert!(!links.is_empty());
+        assert!(links.iter().all(|it| matches!(it, ScrapedLink::Svg(_))));
+    }
+
+    #[cfg(feature = "plaintext")]
+    #[test]
+    fn scrape_any_txt_test() {
+        let links = scrape_any(Path::new("test_files/plaintext/plaintext_test.txt")).unwrap();
+        assert_eq!(
+            links.iter().map(|it| it.url()).collect::<Vec<_>>(),
+            vec!["https://plaintext.test.com"]
+        );
+        assert_eq!(
+            links[0].location(),
+            crate::link::Location::LineAndPos {
+                line: 2,
+                pos: 9,
+                end: 35,
+                byte_offset: 26,
+                column: 9
+            }
+        );
+    }
+
+    #[test]
+    fn detect_format_test() {
+        assert_eq!(
+            detect_format(include_bytes!("../test_files/xml/svg_test.svg")),
+            DetectedFormat::Certain(Format::Svg)
+        );
+        assert_eq!(
+            detect_format(include_bytes!("../test_files/xml/xml_test.xml")),
+            DetectedFormat::Ambiguous(vec![Format::Xml])
+        );
+        assert_eq!(
+            detect_format(include_bytes!(
+                "../test_files/html/html_inline_svg_test.html"
+            )),
+            DetectedFormat::Certain(Format::Html)
+        );
+        assert_eq!(
+            detect_format(b"<!-- <svg> --><div><svg></svg></div>").candidates(),
+            vec![Format::Xml, Format::Html, Format::PlainText]
+        );
+        assert_eq!(
+            detect_format(b"<?xml version='1.0'?>\n<!DOCTYPE svg [ <!ENTITY a '>'> ]>\n<svg:svg/>"),
+            DetectedFormat::Certain(Format::Svg)
+        );
+        assert_eq!(
+            detect_format(b"\xEF\xBB\xBF  <?xml version='1.0'?>\n<svg/>"),
+            DetectedFormat::Certain(Format::Svg)
+        );
+        assert_eq!(
+            detect_format(include_bytes!("../test_files/pdf/pdf_test.pdf")),
+            DetectedFormat::Certain(Format::Pdf)
+        );
+        assert_eq!(
+            detect_format(include_bytes!("../test_files/ooxml/docx_test.docx")),
+            DetectedFormat::Certain(Format::Ooxml)
+        );
+        assert_eq!(
+            detect_format(include_bytes!("../test_files/odf/odt_test.odt")),
+            DetectedFormat::Certain(Format::Odf)
+        );
+        assert_eq!(
+            detect_format(b"PK\x03\x04").candidates(),
+            vec![Format::Ooxml, Format::Odf]
+        );
+        assert_eq!(detect_format(b"plain text"), DetectedFormat::Unknown);
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn scrape_any_mislabeled_svg_test() {
+        let links = scrape_any(Path::new("test_files/xml/svg_mislabeled_test.txt")).unwrap();
+        assert!(links
+            .iter()
+            .all(|it| matches!(it.location(), crate::link::Location::TextPosition(_))));
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn scrape_any_html_with_inline_svg_test() {
+        let links = scrape_any(Path::new("test_files/html/html_inline_svg_test.html")).unwrap();
+        assert!(links
+            .iter()
+            .any(|it| it.url() == "https://inline-svg.test.com/icon.svg"));
+    }
+
+    #[cfg(not(feature = "rtf"))]
+    #[test]
+    fn scrape_any_feature_not_enabled_test() {
+        let result = scrape_any(Path::new("test_files/rtf/rtf_test.rtf"));
+        assert!(matches!(
+            result,
+            Err(ScrapeError::FeatureNotEnabledError(Format::Rtf))
+        ));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn scrape_paths_test() {
+        let paths: Vec<PathBuf> = [
+            "test_files/plaintext/plaintext_test.txt",
+            "test_files/xml/svg_test.svg",
+            "test_files/xml/xml_test.xml",
+            "test_files/json/json_test.json",
+            "test_files/markdown/markdown_test.md",
+            "test_files/html/html_test.html",
+            "test_files/does_not_exist.txt",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let results =
+            scrape_paths_with_options(paths.clone(), &ScrapePathsOptions { threads: Some(3) })
+                .unwrap();
+        assert_eq!(
+            results.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+            paths.iter().collect::<Vec<_>>()
+        );
+        for (path, result) in &results[..results.len() - 1] {
+            let expected = scrape_any(path);
+            match (result, expected) {
+                (Ok(links), Ok(expected)) => assert_eq!(
+                    links.iter().map(|it| it.url()).collect::<Vec<_>>(),
+                    expected.iter().map(|it| it.url()).collect::<Vec<_>>()
+                ),
+                (Err(ScrapeError::FeatureNotEnabledError(format)), Err(_)) => {
+                    assert_eq!(Format::from_path(path), Some(*format))
+                }
+                (result, _) => panic!(
+                    "{:?} was scraped differently in parallel: {:?}",
+                    path,
+                    result.is_ok()
+                ),
+            }
+        }
+        assert!(matches!(
+            results.last().unwrap().1,
+            Err(ScrapeError::IoError(_))
+        ));
+        // One result per path on the global thread-pool as well
+        assert_eq!(scrape_paths(paths).len(), 7);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::helpers::find_css_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes the links from a stylesheet: the targets of `url()`-functions (quoted and unquoted)
+/// and of `@import`-rules, with the references of a `src`-descriptor of `@font-face` flagged as [`CssLinkKind::FontSrc`].
+///
+/// The targets are returned as they are written, so they might be relative links. Comments are skipped.
+/// # Example
+/// ```
+/// use link_scraper::formats::css::{scrape, CssLinkKind};
+/// let css = "@import url(\"theme.css\");\nbody { background: url(https://example.com/bg.png) }";
+/// let links = scrape(css.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "theme.css");
+/// assert_eq!(links[0].kind, CssLinkKind::Import);
+/// assert_eq!(links[1].url, "https://example.com/bg.png");
+/// assert_eq!(links[1].line, 2);
+/// ```
+pub fn scrape<R>(reader: R) -> Result<Vec<CssLink>, CssScrapingError>
+where
+    R: Read,
+{
+    scrape_with_config(reader, &CssScrapeConfig::default())
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<CssLink>, CssScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<CssLink>, CssScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<CssLink>, CssScrapingError>);
+
+/// Like [`scrape`], but lets `config` choose which links are reported.
+/// # Example
+/// ```
+/// use link_scraper::formats::css::{scrape_with_config, CssScrapeConfig};
+/// let css = "a { background: url(data:image/png;base64,AAAA), url(bg.png) }";
+/// let config = CssScrapeConfig {
+///     skip_data_uris: true,
+/// };
+/// let links = scrape_with_config(css.as_bytes(), &config).unwrap();
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].url, "bg.png");
+/// ```
+pub fn scrape_with_config<R>(
+    mut reader: R,
+    config: &CssScrapeConfig,
+) -> Result<Vec<CssLink>, CssScrapingError>
+where
+    R: Read,
+{
+    // Stylesheets declare their encoding with `@charset`, which is only relevant for the non-ascii characters
+    let mut content = vec![];
+    reader.read_to_end(&mut content)?;
+    let css = without_comments(&String::from_utf8_lossy(&content));
+
+    let matches = find_css_urls(&css);
+    // The css without the urls, so the classification is not confused by `;` and `{` inside of them
+    let mut skeleton = css.clone();
+    for found in &matches {
+        skeleton.replace_range(
+            found.start()..found.end(),
+            &" ".repeat(found.as_str().len()),
+        );
+    }
+
+    let mut links = vec![];
+    let mut line = 1;
+    let mut line_counted_up_to = 0;
+    for found in &matches {
+        line += css[line_counted_up_to..found.start()].matches('\n').count();
+        line_counted_up_to = found.start();
+
+        let kind = CssLinkKind::of(&skeleton, found.start(), found.as_str());
+        if config.skip_data_uris && kind == CssLinkKind::DataUri {
+            continue;
+        }
+        links.push(CssLink {
+            url: found.as_str().to_string(),
+            kind,
+            line,
+        });
+    }
+    Ok(links)
+}
+
+/// Chooses which links are reported by [`scrape_with_config`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CssScrapeConfig {
+    /// Does not report the [`CssLinkKind::DataUri`]s, like embedded images and fonts
+    pub skip_data_uris: bool,
+}
+
+#[derive(Error, Debug)]
+pub enum CssScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CssLink {
+    pub url: String,
+    pub kind: CssLinkKind,
+    /// The line (starting at 1) of the url
+    pub line: usize,
+}
+
+impl Link for CssLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.line)
+    }
+}
+
+impl Display for CssLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CssLinkKind {
+    /// The target of an `url()`-function in any other place, like a `background` or a `cursor`
+    Url,
+    /// The stylesheet imported by an `@import`-rule, written as a string or as an `url()`
+    Import,
+    /// A font referenced by the `src`-descriptor of a `@font-face`-rule
+    FontSrc,
+    /// A `data:`-uri, wherever it is used. It embeds the resource instead of linking to it,
+    /// use [`CssScrapeConfig::skip_data_uris`] to skip them.
+    DataUri,
+}
+
+impl CssLinkKind {
+    /// The kind of the `url` starting at the byte-offset `start` of the `skeleton`,
+    /// i.e. the css without comments and urls
+    fn of(skeleton: &str, start: usize, url: &str) -> Self {
+        if url
+            .get(..5)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+        {
+            return CssLinkKind::DataUri;
+        }
+        // The rule or declaration containing the url
+        let statement_start = skeleton[..start]
+            .rfind([';', '{', '}'])
+            .map_or(0, |position| position + 1);
+        let statement = skeleton[statement_start..start]
+            .trim_start()
+            .to_ascii_lowercase();
+        if statement.starts_with("@import") {
+            CssLinkKind::Import
+        } else if statement
+            .strip_prefix("src")
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        {
+            CssLinkKind::FontSrc
+        } else {
+            CssLinkKind::Url
+        }
+    }
+}
+
+/// Replaces the comments of `css` with spaces, keeping the line breaks and the byte-offsets of everything else
+fn without_comments(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut chars = css.chars().peekable();
+    let mut quote = None;
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(_) if c == '\\' => {
+                result.push(c);
+                result.extend(chars.next());
+            }
+            Some(open) if c == open || c == '\n' => {
+                quote = None;
+                result.push(c);
+            }
+            Some(_) => result.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                result.push(c);
+            }
+            None if c == '/' && chars.peek() == Some(&'*') => {
+                chars.next();
+                result.push_str("  ");
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        result.push('\n');
+                    } else {
+                        result.push_str(&" ".repeat(c.len_utf8()));
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CSS: &[u8] = include_bytes!("../../../test_files/css/css_test.css");
+
+    fn summary(links: &[CssLink]) -> Vec<(&str, CssLinkKind, usize)> {
+        links
+            .iter()
+            .map(|it| (it.url.as_str(), it.kind, it.line))
+            .collect()
+    }
+
+    #[test]
+    fn scrape_imports_and_font_src_test() {
+        let links = scrape(TEST_CSS).unwrap();
+        println!("{:?}", links);
+        // The url inside of the comment is skipped
+        assert_eq!(
+            summary(&links),
+            vec![
+                (
+                    "https://fonts.test.com/css?family=Test",
+                    CssLinkKind::Import,
+                    2
+                ),
+                ("theme.css", CssLinkKind::Import, 3),
+                ("https://fonts.test.com/test.woff2", CssLinkKind::FontSrc, 8),
+                (
+                    "data:font/woff;base64,d09GRgABAAAAAA==",
+                    CssLinkKind::DataUri,
+                    9
+                ),
+                ("fonts/test.ttf", CssLinkKind::FontSrc, 10),
+                ("images/bg.png", CssLinkKind::Url, 13),
+                ("cursor.cur", CssLinkKind::Url, 14),
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_data_uris_test() {
+        let config = CssScrapeConfig {
+            skip_data_uris: true,
+        };
+        let links = scrape_with_config(TEST_CSS, &config).unwrap();
+        assert_eq!(links.len(), 6);
+        assert!(links.iter().all(|it| it.kind != CssLinkKind::DataUri));
+    }
+
+    #[test]
+    fn without_comments_test() {
+        let css = "a { /* ä\n url(x) */ b: \"/* kept */\" }";
+        let cleaned = without_comments(css);
+        assert_eq!(cleaned.len(), css.len());
+        assert_eq!(cleaned, "a {      \n           b: \"/* kept */\" }");
+        // An unterminated comment reaches to the end
+        assert_eq!(without_comments("a /* b"), "a     ");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_CSS).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<CssLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes links from all fields of a comma-separated file without a header-row.
+///
+/// Quoted fields may contain commas and line-breaks, and rows may have different lengths.
+pub fn scrape<R>(reader: R) -> Result<Vec<CsvLink>, CsvScrapingError>
+where
+    R: Read,
+{
+    scrape_with_options(reader, &CsvScrapeOptions::default())
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<CsvLink>, CsvScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<CsvLink>, CsvScrapingError>);
+
+/// Like [`scrape`], but lets you choose the delimiter and whether the first row contains headers.
+/// # Example
+/// ```
+/// use link_scraper::formats::csv::{scrape_with_options, CsvScrapeOptions};
+/// let tsv = "name\thomepage\nExample\thttps://example.com\n";
+/// let links = scrape_with_options(tsv.as_bytes(), &CsvScrapeOptions::tsv().with_headers()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com");
+/// assert_eq!((links[0].row, links[0].column), (1, 1));
+/// assert_eq!(links[0].header.as_deref(), Some("homepage"));
+/// ```
+pub fn scrape_with_options<R>(
+    reader: R,
+    options: &CsvScrapeOptions,
+) -> Result<Vec<CsvLink>, CsvScrapingError>
+where
+    R: Read,
+{
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut links = vec![];
+    let mut headers: Option<csv::StringRecord> = None;
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        for (column, field) in record.iter().enumerate() {
+            for link in find_urls(field) {
+                links.push(CsvLink {
+                    url: link.as_str().to_string(),
+                    row,
+                    column,
+                    header: headers
+                        .as_ref()
+                        .and_then(|headers| headers.get(column))
+                        .map(str::to_string),
+                });
+            }
+        }
+        if row == 0 && options.has_headers {
+            headers = Some(record);
+        }
+    }
+    Ok(links)
+}
+
+/// How the file is read by [`scrape_with_options`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvScrapeOptions {
+    /// The character separating the fields of a row
+    pub delimiter: u8,
+    /// Use the fields of the first row as [`CsvLink::header`] for the links in the same column.
+    /// Links inside the header-row itself are still found.
+    pub has_headers: bool,
+}
+
+impl CsvScrapeOptions {
+    /// Options for tab-separated files
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: b'\t',
+            ..Default::default()
+        }
+    }
+
+    /// Uses the first row as headers
+    pub fn with_headers(self) -> Self {
+        Self {
+            has_headers: true,
+            ..self
+        }
+    }
+}
+
+impl Default for CsvScrapeOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: false,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CsvScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    CsvError(#[from] csv::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CsvLink {
+    pub url: String,
+    /// The row of the field containing the link, starting at 0 (including the header-row)
+    pub row: usize,
+    /// The column of the field containing the link, starting at 0
+    pub column: usize,
+    /// The header of the column, if [`CsvScrapeOptions::has_headers`] is set and the header-row is long enough
+    pub header: Option<String>,
+}
+
+impl Link for CsvLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Cell {
+            row: self.row,
+            column: self.column,
+        }
+    }
+}
+
+impl Display for CsvLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CSV: &[u8] = include_bytes!("../../../test_files/csv/csv_test.csv");
+
+    #[test]
+    fn scrape_csv_test() {
+        let links =
+            scrape_with_options(TEST_CSV, &CsvScrapeOptions::default().with_headers()).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, usize, usize, Option<&str>)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.row, it.column, it.header.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://header.test.com", 0, 2, None),
+                ("https://first.test.com", 1, 1, Some("homepage")),
+                ("https://quoted.test.com/a,b", 2, 1, Some("homepage")),
+                (
+                    "https://second.test.com",
+                    2,
+                    2,
+                    Some("notes https://header.test.com")
+                ),
+                (
+                    "https://multiline.test.com",
+                    3,
+                    2,
+                    Some("notes https://header.test.com")
+                ),
+                ("https://extra.test.com", 3, 3, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_without_headers_test() {
+        let links = scrape_from_slice(TEST_CSV).unwrap();
+        assert_eq!(links.len(), 6);
+        assert!(links.iter().all(|it| it.header.is_none()));
+        assert_eq!(links[2].location(), Location::Cell { row: 2, column: 1 });
+    }
+
+    #[test]
+    fn scrape_tsv_test() {
+        let tsv = "a,b\thttps://tab.test.com\n";
+        let links = scrape_with_options(tsv.as_bytes(), &CsvScrapeOptions::tsv()).unwrap();
+        assert_eq!(links[0].url, "https://tab.test.com");
+        assert_eq!(links[0].column, 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_CSV).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<CsvLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
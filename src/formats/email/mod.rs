@@ -0,0 +1,315 @@
+use crate::formats::html::{self, HtmlScrapingError};
+use crate::formats::plaintext::{self, TextFileScrapingError};
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use mail_parser::{Message, MessageParser, MessagePart, PartType};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Headers that contain a list of urls in angle brackets, like `<https://example.com/unsubscribe>`
+/// (RFC 2369 and RFC 5064)
+const URL_LIST_HEADERS: [&str; 7] = [
+    "List-Help",
+    "List-Unsubscribe",
+    "List-Subscribe",
+    "List-Post",
+    "List-Owner",
+    "List-Archive",
+    "Archived-At",
+];
+
+/// Headers that contain a single url, like the location of a part (RFC 2557)
+const URL_HEADERS: [&str; 2] = ["Content-Location", "Content-Base"];
+
+/// Scrapes links from a MIME e-mail, like an `.eml`-file.
+///
+/// The multipart structure is walked, text parts are scraped like [`crate::formats::plaintext::scrape`]
+/// and html parts like [`crate::formats::html::scrape`]. Attached e-mails are scraped the same way.
+/// The parts are decoded from quoted-printable and base64 and converted from their charset before scraping.
+///
+/// The urls of the `List-*`-headers (e.g. `List-Unsubscribe`), `Archived-At`, `Content-Location`
+/// and `Content-Base` are taken as they are. All other headers, like `Subject`, and binary
+/// attachments are not scraped.
+/// # Example
+/// ```
+/// use link_scraper::formats::email::scrape;
+/// let email = "List-Unsubscribe: <https://example.com/unsubscribe>\r\n\r\nSee https://example.com\r\n";
+/// let links = scrape(email.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/unsubscribe");
+/// assert_eq!(links[0].header.as_deref(), Some("List-Unsubscribe"));
+/// assert_eq!(links[1].url, "https://example.com");
+/// assert_eq!(links[1].part, "1");
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<EmailLink>, EmailScrapingError>
+where
+    R: Read,
+{
+    let mut content = vec![];
+    reader.read_to_end(&mut content)?;
+
+    let message = MessageParser::default()
+        .parse(&content)
+        .ok_or(EmailScrapingError::NotAnEmailError)?;
+    let mut scraper = EmailScraper {
+        raw_message: &content,
+        links: vec![],
+    };
+    scraper.scrape_message(&message, "")?;
+    Ok(scraper.links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<EmailLink>, EmailScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<EmailLink>, EmailScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<EmailLink>, EmailScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum EmailScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    TextFileScrapingError(#[from] TextFileScrapingError),
+    #[error(transparent)]
+    HtmlScrapingError(#[from] HtmlScrapingError),
+    /// The content has neither headers nor a body
+    #[error("the content is not an e-mail")]
+    NotAnEmailError,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmailLink {
+    pub url: String,
+    /// The IMAP-style section number of the part containing the link, like `1` or `2.1`.
+    /// The headers of the e-mail itself belong to the empty section,
+    /// the headers of an attached e-mail to the section of its attachment.
+    pub part: String,
+    /// The name of the header containing the link, or [None] if the link is in the body of the part
+    pub header: Option<String>,
+}
+
+impl Link for EmailLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Unknown
+    }
+}
+
+impl Display for EmailLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Walks the parts of an e-mail and collects their links
+struct EmailScraper<'a> {
+    /// The whole e-mail, the offsets of the headers of attached e-mails are relative to it too
+    raw_message: &'a [u8],
+    links: Vec<EmailLink>,
+}
+
+impl EmailScraper<'_> {
+    /// Scrapes `message` and its parts, `section` is the section number of the part containing the message
+    fn scrape_message(
+        &mut self,
+        message: &Message,
+        section: &str,
+    ) -> Result<(), EmailScrapingError> {
+        let Some(root) = message.parts.first() else {
+            return Ok(());
+        };
+        self.scrape_headers(root, section);
+
+        let child_section = |number: usize| {
+            if section.is_empty() {
+                number.to_string()
+            } else {
+                format!("{section}.{number}")
+            }
+        };
+        match &root.body {
+            PartType::Multipart(children) => {
+                for (index, &child) in children.iter().enumerate() {
+                    self.scrape_part(message, child, &child_section(index + 1))?;
+                }
+                Ok(())
+            }
+            // The body of a message without parts is its first section
+            _ => self.scrape_body(root, &child_section(1)),
+        }
+    }
+
+    fn scrape_part(
+        &mut self,
+        message: &Message,
+        id: u32,
+        section: &str,
+    ) -> Result<(), EmailScrapingError> {
+        let Some(part) = message.part(id) else {
+            return Ok(());
+        };
+        self.scrape_headers(part, section);
+
+        match &part.body {
+            PartType::Multipart(children) => {
+                for (index, &child) in children.iter().enumerate() {
+                    self.scrape_part(message, child, &format!("{section}.{}", index + 1))?;
+                }
+                Ok(())
+            }
+            _ => self.scrape_body(part, section),
+        }
+    }
+
+    fn scrape_body(&mut self, part: &MessagePart, section: &str) -> Result<(), EmailScrapingError> {
+        let new_link = |url: String| EmailLink {
+            url,
+            part: section.to_string(),
+            header: None,
+        };
+        match &part.body {
+            PartType::Text(text) => {
+                let found = plaintext::scrape(text.as_bytes())?;
+                self.links
+                    .extend(found.into_iter().map(|link| new_link(link.url)));
+            }
+            PartType::Html(html) => {
+                let found = html::scrape(html.as_bytes())?;
+                self.links
+                    .extend(found.into_iter().map(|link| new_link(link.url)));
+            }
+            PartType::Message(message) => self.scrape_message(message, section)?,
+            PartType::Binary(_) | PartType::InlineBinary(_) | PartType::Multipart(_) => {}
+        }
+        Ok(())
+    }
+
+    fn scrape_headers(&mut self, part: &MessagePart, section: &str) {
+        for header in &part.headers {
+            let name = header.name.as_str();
+            let is_url_list = URL_LIST_HEADERS
+                .iter()
+                .any(|it| it.eq_ignore_ascii_case(name));
+            if !is_url_list && !URL_HEADERS.iter().any(|it| it.eq_ignore_ascii_case(name)) {
+                continue;
+            }
+
+            // The value as it is written, so the urls are taken exactly as they are
+            let value = self
+                .raw_message
+                .get(header.offset_start as usize..header.offset_end as usize)
+                .map(String::from_utf8_lossy)
+                .unwrap_or_default();
+            let new_link = |url: String| EmailLink {
+                url,
+                part: section.to_string(),
+                header: Some(name.to_string()),
+            };
+            if is_url_list {
+                self.links.extend(angle_bracket_urls(&value).map(new_link));
+            } else {
+                // Folded values are unfolded before matching, so long urls are found completely
+                let unfolded = value.split_whitespace().collect::<String>();
+                self.links.extend(
+                    find_urls(&unfolded)
+                        .iter()
+                        .map(|url| new_link(url.as_str().to_string())),
+                );
+            }
+        }
+    }
+}
+
+/// The urls in angle brackets of `value`, like the value of a `List-Unsubscribe`-header.
+/// Whitespace inside the brackets is removed, since long urls may be folded over multiple lines.
+fn angle_bracket_urls(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split('<')
+        .skip(1)
+        .filter_map(|it| it.split_once('>'))
+        .map(|(url, _)| url.split_whitespace().collect::<String>())
+        .filter(|url| !url.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_EMAIL: &[u8] = include_bytes!("../../../test_files/email/email_test.eml");
+
+    fn summary(links: &[EmailLink]) -> Vec<(&str, &str, Option<&str>)> {
+        links
+            .iter()
+            .map(|it| (it.url.as_str(), it.part.as_str(), it.header.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn scrape_multipart_test() {
+        let links = scrape(TEST_EMAIL).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                (
+                    "mailto:unsubscribe@test.com?subject=unsubscribe",
+                    "",
+                    Some("List-Unsubscribe")
+                ),
+                (
+                    "https://news.test.com/unsubscribe?id=123",
+                    "",
+                    Some("List-Unsubscribe")
+                ),
+                ("https://news.test.com/archive", "", Some("List-Archive")),
+                // Only found completely if the quoted-printable soft line break is decoded
+                (
+                    "https://news.test.com/issue/42?utm_source=newsletter&utm_medium=email",
+                    "1.1",
+                    None
+                ),
+                // The html part is base64-encoded
+                ("https://news.test.com/issue/42", "1.2", None),
+                ("https://cdn.test.com/logo.png", "1.2", None),
+                ("https://help.test.com", "2", Some("List-Help")),
+                ("https://news.test.com/issue/41", "2.1", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_content_location_test() {
+        let email = "Content-Type: text/html\r\nContent-Location:\r\n https://www.test.com/pages/\r\n index.html\r\n\r\n<p>Nothing here</p>\r\n";
+        let links = scrape(email.as_bytes()).unwrap();
+        assert_eq!(
+            summary(&links),
+            vec![(
+                "https://www.test.com/pages/index.html",
+                "",
+                Some("Content-Location")
+            )]
+        );
+    }
+
+    #[test]
+    fn fail_on_empty_content_test() {
+        let result = scrape(&b""[..]);
+        assert!(matches!(result, Err(EmailScrapingError::NotAnEmailError)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_EMAIL).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<EmailLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::formats::xml::{XmlLinkKind, XmlScrapingError};
+use crate::helpers::{find_attribute, find_urls, percent_decode};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Seek};
+use thiserror::Error;
+use xml::common::{Position, TextPosition};
+use xml::reader::XmlEvent;
+use xml::EventReader;
+use zip::ZipArchive;
+
+const CONTAINER_PART: &str = "META-INF/container.xml";
+
+/// Scrapes all links from a given epub-file.
+///
+/// The package document (`.opf`) is found by `META-INF/container.xml`. The manifest-items referencing an url
+/// (like a remote cover-image) are returned first, followed by the links inside the documents of the spine
+/// in reading order. Documents that are not part of the spine (e.g. a navigation document only listed in the manifest)
+/// are not scraped.
+///
+/// The documents of the spine are scraped with [`crate::formats::xml::scrape`], but the references to namespaces
+/// and doctypes are left out, as every xhtml-document contains them.
+pub fn scrape<R>(reader: R) -> Result<Vec<EpubLink>, EpubScrapingError>
+where
+    R: Read + Seek,
+{
+    let mut archive = ZipArchive::new(reader)?;
+    let package_part = read_package_part(archive.by_name(CONTAINER_PART)?)?
+        .ok_or(EpubScrapingError::MissingPackageError)?;
+    let package = read_package(archive.by_name(&package_part)?)?;
+
+    let mut links = vec![];
+    for item in &package.manifest {
+        if find_urls(&item.href)
+            .first()
+            .is_none_or(|url| url.as_str() != item.href)
+        {
+            continue;
+        }
+        let is_cover = item
+            .properties
+            .split_whitespace()
+            .any(|it| it == "cover-image")
+            || package.cover_id.as_deref() == Some(item.id.as_str());
+        links.push(EpubLink {
+            url: item.href.clone(),
+            item_id: item.id.clone(),
+            location: EpubLinkLocation {
+                file: package_part.clone(),
+                position: item.position,
+            },
+            kind: if is_cover {
+                EpubLinkKind::Cover
+            } else {
+                EpubLinkKind::Manifest
+            },
+        });
+    }
+
+    for id in &package.spine {
+        let Some(item) = package.manifest.iter().find(|item| &item.id == id) else {
+            continue;
+        };
+        let part = resolve_href(&package_part, &item.href);
+        for link in crate::formats::xml::scrape(archive.by_name(&part)?)? {
+            if matches!(link.kind, XmlLinkKind::NameSpace(_) | XmlLinkKind::Doctype) {
+                continue;
+            }
+            links.push(EpubLink {
+                url: link.url,
+                item_id: item.id.clone(),
+                location: EpubLinkLocation {
+                    file: part.clone(),
+                    position: link.location,
+                },
+                kind: EpubLinkKind::Content(link.kind),
+            });
+        }
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<EpubLink>, EpubScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<EpubLink>, EpubScrapingError>);
+gen_scrape_with_options!(scrape(Read + Seek) -> Result<Vec<EpubLink>, EpubScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum EpubScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    XmlReaderError(#[from] xml::reader::Error),
+    #[error(transparent)]
+    XmlScrapingError(#[from] XmlScrapingError),
+    #[error(transparent)]
+    ZipError(#[from] zip::result::ZipError),
+    /// `META-INF/container.xml` does not reference a package document
+    #[error("the container does not reference a package document")]
+    MissingPackageError,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpubLink {
+    pub url: String,
+    /// The id of the manifest-item containing the link, or of the manifest-item referencing the url
+    pub item_id: String,
+    pub location: EpubLinkLocation,
+    pub kind: EpubLinkKind,
+}
+
+impl Link for EpubLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::ArchiveFile {
+            file: self.location.file.clone(),
+            position: self.location.position,
+        }
+    }
+}
+
+impl Display for EpubLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// This Location references the location in the unzipped epub file-structure.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpubLinkLocation {
+    pub file: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
+    pub position: TextPosition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EpubLinkKind {
+    /// The link is inside a document of the spine
+    Content(XmlLinkKind),
+    /// The link is the `href` of a manifest-item, like a remote font
+    Manifest,
+    /// The link is the `href` of the manifest-item of the cover-image
+    /// (with the property `cover-image` or referenced by `<meta name="cover">`)
+    Cover,
+}
+
+/// An `<item>` of the manifest of the package document
+struct ManifestItem {
+    id: String,
+    href: String,
+    properties: String,
+    position: TextPosition,
+}
+
+/// The parts of the package document needed to find the links
+#[derive(Default)]
+struct Package {
+    manifest: Vec<ManifestItem>,
+    /// The ids of the manifest-items in reading order
+    spine: Vec<String>,
+    /// The id of the manifest-item declared as cover by `<meta name="cover" content="...">`
+    cover_id: Option<String>,
+}
+
+/// Reads the path of the first package document from `META-INF/container.xml`
+fn read_package_part(data: impl Read) -> Result<Option<String>, EpubScrapingError> {
+    let mut parser = EventReader::new(data);
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } if name.local_name == "rootfile" => {
+                if let Some(path) = find_attribute(&attributes, "full-path") {
+                    return Ok(Some(path.to_string()));
+                }
+            }
+            XmlEvent::EndDocument => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+fn read_package(data: impl Read) -> Result<Package, EpubScrapingError> {
+    let mut package = Package::default();
+    let mut parser = EventReader::new(data);
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => match name.local_name.as_str() {
+                "item" => {
+                    if let (Some(id), Some(href)) = (
+                        find_attribute(&attributes, "id"),
+                        find_attribute(&attributes, "href"),
+                    ) {
+                        package.manifest.push(ManifestItem {
+                            id: id.to_string(),
+                            href: href.to_string(),
+                            properties: find_attribute(&attributes, "properties")
+                                .unwrap_or_default()
+                                .to_string(),
+                            position: parser.position(),
+                        });
+                    }
+                }
+                "itemref" => {
+                    if let Some(id) = find_attribute(&attributes, "idref") {
+                        package.spine.push(id.to_string());
+                    }
+                }
+                "meta" if find_attribute(&attributes, "name") == Some("cover") => {
+                    package.cover_id = find_attribute(&attributes, "content").map(str::to_string);
+                }
+                _ => {}
+            },
+            XmlEvent::EndDocument => return Ok(package),
+            _ => {}
+        }
+    }
+}
+
+/// Resolves the percent-encoded `href` of a manifest-item relative to the package document `part`
+/// to the name of the file inside the archive,
+/// e.g. `text/chapter%201.xhtml` of `OEBPS/content.opf` to `OEBPS/text/chapter 1.xhtml`
+fn resolve_href(part: &str, href: &str) -> String {
+    let href = percent_decode(href);
+    let mut segments: Vec<&str> = part.split('/').collect();
+    segments.pop();
+    for segment in href.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_EPUB: &[u8] = include_bytes!("../../../test_files/epub/epub_test.epub");
+
+    #[test]
+    fn scrape_epub_test() {
+        let links = scrape_from_slice(TEST_EPUB).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, &str, &str, &EpubLinkKind)> = links
+            .iter()
+            .map(|it| {
+                (
+                    it.url.as_str(),
+                    it.item_id.as_str(),
+                    it.location.file.as_str(),
+                    &it.kind,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found[..2],
+            vec![
+                (
+                    "https://cover.test.com/cover.jpg",
+                    "cover",
+                    "OEBPS/content.opf",
+                    &EpubLinkKind::Cover
+                ),
+                (
+                    "https://fonts.test.com/serif.woff2",
+                    "font",
+                    "OEBPS/content.opf",
+                    &EpubLinkKind::Manifest
+                ),
+            ]
+        );
+        assert_eq!(links.len(), 3);
+        let chapter_link = &links[2];
+        assert_eq!(chapter_link.url, "https://external.test.com/chapter1");
+        assert_eq!(chapter_link.item_id, "chapter1");
+        assert_eq!(chapter_link.location.file, "OEBPS/text/chapter 1.xhtml");
+        assert_eq!(chapter_link.location.position.row, 5);
+        assert!(matches!(
+            chapter_link.kind,
+            EpubLinkKind::Content(XmlLinkKind::Attribute { .. })
+        ));
+    }
+
+    #[test]
+    fn resolve_href_test() {
+        assert_eq!(
+            resolve_href("OEBPS/content.opf", "text/chapter%201.xhtml"),
+            "OEBPS/text/chapter 1.xhtml"
+        );
+        assert_eq!(
+            resolve_href("OEBPS/content.opf", "../cover%2.jpg"),
+            "cover%2.jpg"
+        );
+        assert_eq!(resolve_href("content.opf", "./nav.xhtml"), "nav.xhtml");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_EPUB).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<EpubLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
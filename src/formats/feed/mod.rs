@@ -0,0 +1,304 @@
+use crate::helpers::find_attribute;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+use xml::common::{Position, TextPosition};
+use xml::reader::XmlEvent;
+use xml::EventReader;
+
+/// Scrapes the links from a RSS 2.0 or Atom feed and classifies them by their role in the feed.
+///
+/// Unlike [`crate::formats::xml::scrape`] only the links with a meaning for the feed are returned,
+/// so urls inside descriptions or contents are ignored.
+/// # Example
+/// ```
+/// use link_scraper::formats::feed::{scrape, FeedLinkKind};
+/// let rss = r#"<rss version="2.0"><channel><link>https://example.com</link>
+///     <item><title>First post</title><link>https://example.com/first</link></item>
+/// </channel></rss>"#;
+/// let links = scrape(rss.as_bytes()).unwrap();
+/// assert_eq!(links[1].kind, FeedLinkKind::Item);
+/// assert_eq!(links[1].item_title.as_deref(), Some("First post"));
+/// ```
+pub fn scrape<R>(reader: R) -> Result<Vec<FeedLink>, FeedScrapingError>
+where
+    R: Read,
+{
+    let mut parser = EventReader::new(reader);
+    let mut links: Vec<FeedLink> = vec![];
+    let mut ancestors: Vec<String> = vec![];
+    let mut format = None;
+    // The links of the current item, they get their title once the item ends
+    let mut item_links: Vec<FeedLink> = vec![];
+    let mut item_title: Option<String> = None;
+    let mut text = String::new();
+    let mut text_position = TextPosition::new();
+
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let element = name.local_name;
+                let format = match format {
+                    Some(format) => format,
+                    None => *format.insert(match element.as_str() {
+                        "rss" => FeedFormat::Rss,
+                        "feed" => FeedFormat::Atom,
+                        _ => return Err(FeedScrapingError::NotAFeedError),
+                    }),
+                };
+                let in_item =
+                    is_item(format, &element) || ancestors.iter().any(|it| is_item(format, it));
+                let collector = if in_item { &mut item_links } else { &mut links };
+                let position = parser.position();
+                match element.as_str() {
+                    "link" => {
+                        if let Some(href) = find_attribute(&attributes, "href") {
+                            let kind =
+                                match find_attribute(&attributes, "rel").unwrap_or("alternate") {
+                                    "self" => FeedLinkKind::SelfLink,
+                                    "enclosure" => FeedLinkKind::Enclosure,
+                                    "alternate" if in_item => FeedLinkKind::Item,
+                                    "alternate" => FeedLinkKind::Channel,
+                                    _ => FeedLinkKind::Alternate,
+                                };
+                            collector.push(FeedLink::new(href, position, kind));
+                        }
+                    }
+                    "enclosure" => {
+                        if let Some(url) = find_attribute(&attributes, "url") {
+                            collector.push(FeedLink::new(url, position, FeedLinkKind::Enclosure));
+                        }
+                    }
+                    _ => {}
+                }
+                if is_item(format, &element) {
+                    item_title = None;
+                }
+                ancestors.push(element);
+                text.clear();
+                text_position = position;
+            }
+            XmlEvent::Characters(chars) | XmlEvent::CData(chars) => text.push_str(&chars),
+            XmlEvent::EndElement { name } => {
+                ancestors.pop();
+                let Some(format) = format else { continue };
+                let parent = ancestors.last().map(String::as_str);
+                match name.local_name.as_str() {
+                    // Only rss has the url as the content of `<link>`, atom uses the `href`-attribute
+                    "link" if format == FeedFormat::Rss && !text.trim().is_empty() => {
+                        let (collector, kind) = match parent {
+                            Some("item") => (&mut item_links, FeedLinkKind::Item),
+                            Some("channel") => (&mut links, FeedLinkKind::Channel),
+                            _ => continue,
+                        };
+                        collector.push(FeedLink::new(text.trim(), text_position, kind));
+                    }
+                    "title" if parent.is_some_and(|it| is_item(format, it)) => {
+                        item_title = Some(text.trim().to_string());
+                    }
+                    element if is_item(format, element) => {
+                        for mut link in item_links.drain(..) {
+                            link.item_title.clone_from(&item_title);
+                            links.push(link);
+                        }
+                    }
+                    _ => {}
+                }
+                text.clear();
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+
+    if format.is_none() {
+        return Err(FeedScrapingError::NotAFeedError);
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<FeedLink>, FeedScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<FeedLink>, FeedScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<FeedLink>, FeedScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum FeedScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    XmlReaderError(#[from] xml::reader::Error),
+    #[error("The root-element is neither a rss- nor an atom-feed.")]
+    NotAFeedError,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeedLink {
+    pub url: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
+    pub location: TextPosition,
+    pub kind: FeedLinkKind,
+    /// The title of the rss-item or atom-entry containing the link, if it has one
+    pub item_title: Option<String>,
+}
+
+impl FeedLink {
+    fn new(url: &str, location: TextPosition, kind: FeedLinkKind) -> Self {
+        Self {
+            url: url.to_string(),
+            location,
+            kind,
+            item_title: None,
+        }
+    }
+}
+
+impl Link for FeedLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::TextPosition(self.location)
+    }
+}
+
+impl Display for FeedLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeedLinkKind {
+    /// The website of the feed<br/>
+    /// Example: `<channel><link>https://link.example.com</link></channel>` or `<feed><link href="https://link.example.com"/></feed>`
+    Channel,
+    /// The website of an item or entry<br/>
+    /// Example: `<item><link>https://link.example.com/post</link></item>` or `<entry><link href="https://link.example.com/post"/></entry>`
+    Item,
+    /// A file attached to the feed or an item, like the audio of a podcast<br/>
+    /// Example: `<enclosure url="https://link.example.com/a.mp3"/>` or `<link rel="enclosure" href="https://link.example.com/a.mp3"/>`
+    Enclosure,
+    /// The url of the feed itself<br/>
+    /// Example: `<link rel="self" href="https://link.example.com/feed.xml"/>`
+    SelfLink,
+    /// An atom-link with any other relation, like `related` or `via`<br/>
+    /// Example: `<link rel="related" href="https://link.example.com"/>`
+    Alternate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+/// Checks if `element` is an item of the feed
+fn is_item(format: FeedFormat, element: &str) -> bool {
+    match format {
+        FeedFormat::Rss => element == "item",
+        FeedFormat::Atom => element == "entry",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_RSS: &[u8] = include_bytes!("../../../test_files/feed/rss_test.xml");
+    const TEST_ATOM: &[u8] = include_bytes!("../../../test_files/feed/atom_test.xml");
+
+    fn summary(links: &[FeedLink]) -> Vec<(&str, FeedLinkKind, Option<&str>)> {
+        links
+            .iter()
+            .map(|it| (it.url.as_str(), it.kind, it.item_title.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn scrape_rss_test() {
+        let links = scrape(TEST_RSS).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://channel.test.com", FeedLinkKind::Channel, None),
+                (
+                    "https://channel.test.com/feed.xml",
+                    FeedLinkKind::SelfLink,
+                    None
+                ),
+                (
+                    "https://item.test.com/episode-1",
+                    FeedLinkKind::Item,
+                    Some("Episode 1")
+                ),
+                (
+                    "https://enclosure.test.com/episode-1.mp3",
+                    FeedLinkKind::Enclosure,
+                    Some("Episode 1")
+                ),
+                ("https://item.test.com/untitled", FeedLinkKind::Item, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_atom_test() {
+        let links = scrape(TEST_ATOM).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://channel.test.com/", FeedLinkKind::Channel, None),
+                (
+                    "https://channel.test.com/atom.xml",
+                    FeedLinkKind::SelfLink,
+                    None
+                ),
+                (
+                    "https://item.test.com/entry-1",
+                    FeedLinkKind::Item,
+                    Some("Entry 1")
+                ),
+                (
+                    "https://enclosure.test.com/entry-1.mp3",
+                    FeedLinkKind::Enclosure,
+                    Some("Entry 1")
+                ),
+                (
+                    "https://related.test.com",
+                    FeedLinkKind::Alternate,
+                    Some("Entry 1")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn fail_on_non_feed_test() {
+        let result = scrape(&b"<html><a href=\"https://a.test.com\"/></html>"[..]);
+        assert!(matches!(result, Err(FeedScrapingError::NotAFeedError)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_ATOM).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<FeedLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::helpers::{find_attribute, find_urls};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+use xml::common::{Position, TextPosition};
+use xml::reader::XmlEvent;
+use xml::EventReader;
+
+/// Scrapes the links from a GPX- or KML-file and classifies them by their role in the file.
+///
+/// For GPX the `<link href="...">`-elements (and the `<url>`-elements of GPX 1.0) are returned,
+/// for KML the `<href>` of the `<Link>` of a `<NetworkLink>` and of every `<Icon>`.
+/// The descriptions (`<desc>` and `<description>`, which often contain html) are scanned for urls.
+/// Unlike [`crate::formats::xml::scrape`] all other urls, like the ones of namespaces, are ignored.
+/// # Example
+/// ```
+/// use link_scraper::formats::geo::{scrape, GeoLinkKind};
+/// let kml = r#"<kml><NetworkLink><Link><href>https://example.com/live.kml</href></Link></NetworkLink></kml>"#;
+/// let links = scrape(kml.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/live.kml");
+/// assert_eq!(links[0].kind, GeoLinkKind::NetworkLink);
+/// ```
+pub fn scrape<R>(reader: R) -> Result<Vec<GeoLink>, GeoScrapingError>
+where
+    R: Read,
+{
+    let mut parser = EventReader::new(reader);
+    let mut links: Vec<GeoLink> = vec![];
+    let mut ancestors: Vec<String> = vec![];
+    let mut format = None;
+    let mut text = String::new();
+    let mut text_position = TextPosition::new();
+    // The text of the description the parser is in, it may contain html-elements
+    let mut description: Option<(String, TextPosition)> = None;
+
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let element = name.local_name;
+                let format = match format {
+                    Some(format) => format,
+                    None => *format.insert(match element.as_str() {
+                        "gpx" => GeoFormat::Gpx,
+                        "kml" => GeoFormat::Kml,
+                        _ => return Err(GeoScrapingError::NotAGeoFileError),
+                    }),
+                };
+                let position = parser.position();
+                if description.is_none() {
+                    match (format, element.as_str()) {
+                        (GeoFormat::Gpx, "link") => {
+                            if let Some(href) = find_attribute(&attributes, "href") {
+                                links.push(GeoLink::new(href, position, gpx_kind(&ancestors)));
+                            }
+                        }
+                        (GeoFormat::Gpx, "desc") | (GeoFormat::Kml, "description") => {
+                            description = Some((String::new(), position));
+                        }
+                        _ => {}
+                    }
+                }
+                ancestors.push(element);
+                text.clear();
+                text_position = position;
+            }
+            XmlEvent::Characters(chars) | XmlEvent::CData(chars) => match &mut description {
+                Some((description, _)) => description.push_str(&chars),
+                None => text.push_str(&chars),
+            },
+            XmlEvent::EndElement { name } => {
+                ancestors.pop();
+                let Some(format) = format else { continue };
+                let element = name.local_name.as_str();
+                match (format, element) {
+                    (GeoFormat::Gpx, "desc") | (GeoFormat::Kml, "description") => {
+                        if let Some((description, position)) = description.take() {
+                            links.extend(find_urls(&description).iter().map(|url| {
+                                GeoLink::new(url.as_str(), position, GeoLinkKind::Description)
+                            }));
+                        }
+                    }
+                    _ if description.is_some() => continue,
+                    // The url-element of GPX 1.0, which was replaced by `<link href="...">`
+                    (GeoFormat::Gpx, "url") if !text.trim().is_empty() => {
+                        links.push(GeoLink::new(
+                            text.trim(),
+                            text_position,
+                            gpx_kind(&ancestors),
+                        ));
+                    }
+                    (GeoFormat::Kml, "href") if !text.trim().is_empty() => {
+                        let kind = match ancestors.last().map(String::as_str) {
+                            Some("Icon") => GeoLinkKind::Icon,
+                            // `<Url>` is the name of `<Link>` before KML 2.1
+                            Some("Link" | "Url")
+                                if ancestors.iter().any(|it| it == "NetworkLink") =>
+                            {
+                                GeoLinkKind::NetworkLink
+                            }
+                            _ => continue,
+                        };
+                        links.push(GeoLink::new(text.trim(), text_position, kind));
+                    }
+                    _ => {}
+                }
+                text.clear();
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+
+    if format.is_none() {
+        return Err(GeoScrapingError::NotAGeoFileError);
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<GeoLink>, GeoScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<GeoLink>, GeoScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<GeoLink>, GeoScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum GeoScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    XmlReaderError(#[from] xml::reader::Error),
+    #[error("The root-element is neither a gpx- nor a kml-element.")]
+    NotAGeoFileError,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeoLink {
+    pub url: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
+    pub location: TextPosition,
+    pub kind: GeoLinkKind,
+}
+
+impl GeoLink {
+    fn new(url: &str, location: TextPosition, kind: GeoLinkKind) -> Self {
+        Self {
+            url: url.to_string(),
+            location,
+            kind,
+        }
+    }
+}
+
+impl Link for GeoLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::TextPosition(self.location)
+    }
+}
+
+impl Display for GeoLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GeoLinkKind {
+    /// The link of a GPX-waypoint, or of a point of a route or track<br/>
+    /// Example: `<wpt lat="47.3" lon="8.5"><link href="https://link.example.com"/></wpt>`
+    Waypoint,
+    /// The link of a GPX-route or -track<br/>
+    /// Example: `<trk><link href="https://link.example.com"/></trk>`
+    Track,
+    /// The link of the GPX-file itself, e.g. of its metadata or author<br/>
+    /// Example: `<metadata><link href="https://link.example.com"/></metadata>`
+    Metadata,
+    /// The KML-file loaded by a network link<br/>
+    /// Example: `<NetworkLink><Link><href>https://link.example.com/a.kml</href></Link></NetworkLink>`
+    NetworkLink,
+    /// The image of a KML-icon, e.g. of a style or an overlay<br/>
+    /// Example: `<Icon><href>https://link.example.com/pin.png</href></Icon>`
+    Icon,
+    /// An url inside a GPX `<desc>` or a KML `<description>`<br/>
+    /// Example: `<description><![CDATA[<a href="https://link.example.com">Link</a>]]></description>`
+    Description,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GeoFormat {
+    Gpx,
+    Kml,
+}
+
+/// The kind of a GPX-link, decided by the closest of its `ancestors` with a link
+fn gpx_kind(ancestors: &[String]) -> GeoLinkKind {
+    for ancestor in ancestors.iter().rev() {
+        match ancestor.as_str() {
+            "wpt" | "rtept" | "trkpt" => return GeoLinkKind::Waypoint,
+            "rte" | "trk" => return GeoLinkKind::Track,
+            _ => {}
+        }
+    }
+    GeoLinkKind::Metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KML: &[u8] = include_bytes!("../../../test_files/geo/kml_test.kml");
+    const TEST_GPX: &[u8] = include_bytes!("../../../test_files/geo/gpx_test.gpx");
+
+    fn summary(links: &[GeoLink]) -> Vec<(&str, GeoLinkKind)> {
+        links.iter().map(|it| (it.url.as_str(), it.kind)).collect()
+    }
+
+    #[test]
+    fn scrape_kml_test() {
+        let links = scrape(TEST_KML).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://icons.test.com/pin.png", GeoLinkKind::Icon),
+                ("https://live.test.com/data.kml", GeoLinkKind::NetworkLink),
+                ("https://office.test.com", GeoLinkKind::Description),
+            ]
+        );
+        assert_eq!(links[1].location.row, 12);
+    }
+
+    #[test]
+    fn scrape_gpx_test() {
+        let links = scrape(TEST_GPX).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://author.test.com", GeoLinkKind::Metadata),
+                ("https://photos.test.com/summit", GeoLinkKind::Description),
+                ("https://summit.test.com", GeoLinkKind::Waypoint),
+                ("https://hike.test.com", GeoLinkKind::Track),
+                ("https://trackpoint.test.com", GeoLinkKind::Waypoint),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_gpx_1_0_url_test() {
+        let gpx = br#"<gpx version="1.0"><url>https://gpx.test.com</url><wpt lat="1" lon="2"><url>https://wpt.test.com</url></wpt></gpx>"#;
+        let links = scrape(&gpx[..]).unwrap();
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://gpx.test.com", GeoLinkKind::Metadata),
+                ("https://wpt.test.com", GeoLinkKind::Waypoint),
+            ]
+        );
+    }
+
+    #[test]
+    fn fail_on_other_xml_test() {
+        let result = scrape(&b"<svg><a href=\"https://a.test.com\"/></svg>"[..]);
+        assert!(matches!(result, Err(GeoScrapingError::NotAGeoFileError)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_KML).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<GeoLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
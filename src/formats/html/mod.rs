@@ -0,0 +1,441 @@
+use crate::helpers::{find_css_urls, find_srcset_urls, find_urls};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use html5ever::tendril::StrTendril;
+use html5ever::tokenizer::states::RawKind;
+use html5ever::tokenizer::{
+    BufferQueue, Tag, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes links from a html-document.
+///
+/// Unlike [`crate::formats::xml::scrape`] this does not require the document to be well-formed,
+/// the document is tokenized the same way a browser would do it.
+///
+/// Attributes that are known to contain a link (e.g. `<a href>`, `<img src>`, `<form action>`)
+/// are taken as they are, even if they are relative links.
+/// All other attributes, texts, comments and scripts are searched for urls.
+///
+/// Documents that are not valid utf-8 (e.g. legacy latin-1 pages) are decoded lossily,
+/// so the invalid bytes get replaced and do not prevent the remaining links from being found.
+pub fn scrape<R>(mut reader: R) -> Result<Vec<HtmlLink>, HtmlScrapingError>
+where
+    R: Read,
+{
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let input = BufferQueue::default();
+    input.push_back(StrTendril::from_slice(&String::from_utf8_lossy(&bytes)));
+
+    let tokenizer = Tokenizer::new(HtmlLinkSink::default(), TokenizerOpts::default());
+    let _ = tokenizer.feed(&input);
+    tokenizer.end();
+
+    Ok(tokenizer.sink.links.into_inner())
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<HtmlLink>, HtmlScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<HtmlLink>, HtmlScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<HtmlLink>, HtmlScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum HtmlScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HtmlLinkKind {
+    /// The link is the value of an attribute, or found inside of it <br/>
+    /// Example: `<a href="https://link.example.com">`
+    Attribute { element: String, attribute: String },
+
+    /// The link is one of the candidates of a `srcset`-attribute <br/>
+    /// Example: `<img srcset="https://link.example.com/small.png 1x, https://link.example.com/big.png 2x">`
+    Srcset { element: String },
+
+    /// The link is referenced by a css `url()` in a `style`-attribute <br/>
+    /// Example: `<div style="background: url(https://link.example.com/bg.png)">`
+    StyleAttribute { element: String },
+
+    /// The link is referenced by a css `url()` in a `<style>`-element <br/>
+    /// Example: `<style> body { background: url(https://link.example.com/bg.png) } </style>`
+    StyleElement,
+
+    /// The link is inside a `<script>`-element <br/>
+    /// Example: `<script> const link = "https://link.example.com"; </script>`
+    Script,
+
+    /// The link is the target of a redirect <br/>
+    /// Example: `<meta http-equiv="refresh" content="5; url=https://link.example.com">`
+    MetaRefresh,
+
+    /// The link is inside a html-comment <br/>
+    /// Example: `<!-- Just a comment with a link to https://link.example.com -->`
+    Comment,
+
+    /// The link is inside a plaintext portion <br/>
+    /// Example: `<p> Just a text with a link to https://link.example.com </p>`
+    PlainText,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtmlLink {
+    pub url: String,
+    /// The line (starting at 1) the link was found in
+    pub location: usize,
+    pub kind: HtmlLinkKind,
+}
+
+impl Link for HtmlLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.location)
+    }
+}
+
+impl Display for HtmlLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Collects the links while the document is tokenized.
+#[derive(Default)]
+struct HtmlLinkSink {
+    links: RefCell<Vec<HtmlLink>>,
+    /// The text since the last non-text token and the line it ends in
+    text: RefCell<(String, usize)>,
+    /// Set while inside of a `<script>` or `<style>`-element
+    raw_text_element: RefCell<Option<String>>,
+}
+
+impl TokenSink for HtmlLinkSink {
+    type Handle = ();
+
+    fn process_token(&self, token: Token, line_number: u64) -> TokenSinkResult<()> {
+        let line_number = line_number as usize;
+        if let Token::CharacterTokens(chars) = &token {
+            // The tokenizer reports the line at the end of the characters
+            let mut text = self.text.borrow_mut();
+            text.0.push_str(chars);
+            text.1 = line_number;
+            return TokenSinkResult::Continue;
+        }
+        self.flush_text();
+
+        match token {
+            Token::TagToken(tag) if tag.kind == TagKind::StartTag => {
+                self.scrape_from_start_tag(&tag, line_number);
+                match &*tag.name {
+                    "script" if !tag.self_closing => {
+                        self.raw_text_element.replace(Some("script".to_string()));
+                        return TokenSinkResult::RawData(RawKind::ScriptData);
+                    }
+                    "style" if !tag.self_closing => {
+                        self.raw_text_element.replace(Some("style".to_string()));
+                        return TokenSinkResult::RawData(RawKind::Rawtext);
+                    }
+                    "title" | "textarea" => return TokenSinkResult::RawData(RawKind::Rcdata),
+                    _ => {}
+                }
+            }
+            Token::TagToken(_) => {
+                self.raw_text_element.replace(None);
+            }
+            Token::CommentToken(comment) => {
+                self.push_all(
+                    find_urls(&comment).iter().map(|it| it.as_str()),
+                    line_number,
+                    || HtmlLinkKind::Comment,
+                );
+            }
+            _ => {}
+        }
+        TokenSinkResult::Continue
+    }
+
+    fn end(&self) {
+        self.flush_text();
+    }
+}
+
+impl HtmlLinkSink {
+    fn push_all<'a>(
+        &self,
+        urls: impl IntoIterator<Item = &'a str>,
+        line_number: usize,
+        kind: impl Fn() -> HtmlLinkKind,
+    ) {
+        self.links
+            .borrow_mut()
+            .extend(urls.into_iter().map(|url| HtmlLink {
+                url: url.to_string(),
+                location: line_number,
+                kind: kind(),
+            }));
+    }
+
+    /// Scrapes the text collected since the last tag.
+    fn flush_text(&self) {
+        let (text, end_line) = std::mem::take(&mut *self.text.borrow_mut());
+        if text.is_empty() {
+            return;
+        }
+
+        let raw_text_element = self.raw_text_element.borrow();
+        let matches = match raw_text_element.as_deref() {
+            Some("style") => find_css_urls(&text),
+            _ => find_urls(&text),
+        };
+        let kind = match raw_text_element.as_deref() {
+            Some("style") => HtmlLinkKind::StyleElement,
+            Some(_) => HtmlLinkKind::Script,
+            None => HtmlLinkKind::PlainText,
+        };
+        self.links
+            .borrow_mut()
+            .extend(matches.iter().map(|it| HtmlLink {
+                url: it.as_str().to_string(),
+                location: end_line - text[it.start()..].matches('\n').count(),
+                kind: kind.clone(),
+            }));
+    }
+
+    fn scrape_from_start_tag(&self, tag: &Tag, line_number: usize) {
+        let element = &*tag.name;
+        let is_meta_refresh = element == "meta"
+            && tag.attrs.iter().any(|it| {
+                &*it.name.local == "http-equiv" && it.value.eq_ignore_ascii_case("refresh")
+            });
+        for attribute in &tag.attrs {
+            let name = &*attribute.name.local;
+            let value = attribute.value.trim();
+            let attribute_kind = || HtmlLinkKind::Attribute {
+                element: element.to_string(),
+                attribute: name.to_string(),
+            };
+
+            if is_meta_refresh && name == "content" {
+                self.push_all(parse_meta_refresh(value), line_number, || {
+                    HtmlLinkKind::MetaRefresh
+                });
+            } else if is_link_attribute(element, name) {
+                if !value.is_empty() {
+                    self.push_all([value], line_number, attribute_kind);
+                }
+            } else if name == "srcset" {
+                self.push_all(
+                    find_srcset_urls(value).iter().map(|it| it.as_str()),
+                    line_number,
+                    || HtmlLinkKind::Srcset {
+                        element: element.to_string(),
+                    },
+                );
+            } else if name == "style" {
+                self.push_all(
+                    find_css_urls(value).iter().map(|it| it.as_str()),
+                    line_number,
+                    || HtmlLinkKind::StyleAttribute {
+                        element: element.to_string(),
+                    },
+                );
+            } else {
+                self.push_all(
+                    find_urls(value).iter().map(|it| it.as_str()),
+                    line_number,
+                    attribute_kind,
+                );
+            }
+        }
+    }
+}
+
+/// Whether the attribute `name` of `element` is defined to contain a link.
+fn is_link_attribute(element: &str, name: &str) -> bool {
+    matches!(
+        (element, name),
+        ("a" | "area" | "base" | "link", "href")
+            | (
+                "img"
+                    | "script"
+                    | "iframe"
+                    | "embed"
+                    | "audio"
+                    | "video"
+                    | "source"
+                    | "track"
+                    | "input",
+                "src"
+            )
+            | ("form", "action")
+            | ("button" | "input", "formaction")
+            | ("video", "poster")
+            | ("object", "data")
+            | ("blockquote" | "q" | "del" | "ins", "cite")
+    )
+}
+
+/// Returns the redirect target of the `content`-attribute of a `<meta http-equiv="refresh">`
+/// like `5; url=https://link.example.com`.
+fn parse_meta_refresh(content: &str) -> Option<&str> {
+    let (_, target) = content.split_once([';', ','])?;
+    let target = target.trim_start();
+    let target = match target.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url") => {
+            target[3..].trim_start().strip_prefix('=')?.trim()
+        }
+        _ => target.trim(),
+    };
+    let target = target
+        .strip_prefix(['"', '\''])
+        .map(|it| it.trim_end_matches(['"', '\'']))
+        .unwrap_or(target);
+    Some(target).filter(|it| !it.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_HTML: &[u8] = include_bytes!("../../../test_files/html/html_test.html");
+
+    fn find<'a>(links: &'a [HtmlLink], url: &str) -> &'a HtmlLink {
+        links
+            .iter()
+            .find(|it| it.url == url)
+            .unwrap_or_else(|| panic!("{} was not found in {:?}", url, links))
+    }
+
+    #[test]
+    fn scrape_anchor_test() {
+        let links = scrape_from_slice(TEST_HTML).unwrap();
+        println!("{:?}", links);
+        let link = find(&links, "https://anchor.test.com");
+        assert_eq!(
+            link.kind,
+            HtmlLinkKind::Attribute {
+                element: "a".to_string(),
+                attribute: "href".to_string()
+            }
+        );
+        assert_eq!(link.location, 13);
+        assert_eq!(find(&links, "relative/page.html").location, 14);
+    }
+
+    #[test]
+    fn scrape_image_test() {
+        let links = scrape_from_slice(TEST_HTML).unwrap();
+        assert_eq!(
+            find(&links, "https://image.test.com/image.png").kind,
+            HtmlLinkKind::Attribute {
+                element: "img".to_string(),
+                attribute: "src".to_string()
+            }
+        );
+        assert_eq!(
+            find(&links, "https://poster.test.com/poster.png").kind,
+            HtmlLinkKind::Attribute {
+                element: "video".to_string(),
+                attribute: "poster".to_string()
+            }
+        );
+        assert_eq!(
+            find(&links, "https://style.test.com/bg.png").kind,
+            HtmlLinkKind::StyleAttribute {
+                element: "div".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn scrape_srcset_test() {
+        let links = scrape_from_slice(TEST_HTML).unwrap();
+        let srcset: Vec<&str> = links
+            .iter()
+            .filter(|it| matches!(it.kind, HtmlLinkKind::Srcset { .. }))
+            .map(|it| it.url.as_str())
+            .collect();
+        assert_eq!(
+            srcset,
+            vec![
+                "https://srcset.test.com/small.png",
+                "https://srcset.test.com/medium.png",
+                "https://srcset.test.com/large,wide.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_meta_refresh_test() {
+        let links = scrape_from_slice(TEST_HTML).unwrap();
+        let link = find(&links, "https://redirect.test.com/target");
+        assert_eq!(link.kind, HtmlLinkKind::MetaRefresh);
+        assert_eq!(link.location, 4);
+
+        assert_eq!(
+            parse_meta_refresh("0;URL='https://a.com'"),
+            Some("https://a.com")
+        );
+        assert_eq!(parse_meta_refresh("5"), None);
+    }
+
+    #[test]
+    fn scrape_text_test() {
+        let links = scrape_from_slice(TEST_HTML).unwrap();
+        assert_eq!(
+            find(&links, "https://plaintext.test.com").kind,
+            HtmlLinkKind::PlainText
+        );
+        assert_eq!(find(&links, "https://plaintext.test.com").location, 17);
+        assert_eq!(
+            find(&links, "https://comment.test.com").kind,
+            HtmlLinkKind::Comment
+        );
+        assert_eq!(
+            find(&links, "https://script.test.com").kind,
+            HtmlLinkKind::Script
+        );
+        assert_eq!(
+            find(&links, "https://style-element.test.com/font.woff").kind,
+            HtmlLinkKind::StyleElement
+        );
+        assert_eq!(
+            find(&links, "https://form.test.com/submit").kind,
+            HtmlLinkKind::Attribute {
+                element: "form".to_string(),
+                attribute: "action".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn scrape_non_utf8_test() {
+        let links = scrape_from_slice(b"<p>caf\xe9 https://a.test.com</p>").unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://a.test.com");
+        assert_eq!(links[0].kind, HtmlLinkKind::PlainText);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_HTML).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<HtmlLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
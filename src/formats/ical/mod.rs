@@ -0,0 +1,239 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// The properties whose value is a single uri, which is taken as is
+const URI_PROPERTIES: [&str; 6] = ["URL", "ATTACH", "TZURL", "SOURCE", "CONFERENCE", "IMAGE"];
+
+/// Scrapes links from an iCalendar-file (.ics).
+///
+/// Folded lines are unfolded first. The values of properties holding a uri (like `URL` and `ATTACH`) are taken as is,
+/// inline binary attachments are skipped. All other values (like `DESCRIPTION` or `LOCATION`) are unescaped
+/// and scanned for urls.
+/// # Example
+/// ```
+/// use link_scraper::formats::ical::scrape;
+/// let ics = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDESCRIPTION:Join at https://meet.example.com/a\r\n bc\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+/// let links = scrape(ics.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://meet.example.com/abc");
+/// assert_eq!((links[0].property.as_str(), links[0].component.as_str()), ("DESCRIPTION", "VEVENT"));
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<IcalLink>, IcalScrapingError>
+where
+    R: Read,
+{
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+
+    let mut links = vec![];
+    let mut components: Vec<String> = vec![];
+    for (line, content_line) in unfold(&content) {
+        let Some((name, parameters, value)) = split_content_line(&content_line) else {
+            continue;
+        };
+        match name.as_str() {
+            "BEGIN" => {
+                components.push(value.to_ascii_uppercase());
+                continue;
+            }
+            "END" => {
+                components.pop();
+                continue;
+            }
+            _ => {}
+        }
+        let component = components.last().cloned().unwrap_or_default();
+        if URI_PROPERTIES.contains(&name.as_str()) {
+            let is_binary = parameters.iter().any(|parameter| {
+                parameter.eq_ignore_ascii_case("VALUE=BINARY")
+                    || parameter.eq_ignore_ascii_case("ENCODING=BASE64")
+            });
+            if !is_binary && !value.is_empty() {
+                links.push(IcalLink {
+                    url: value.to_string(),
+                    property: name,
+                    component,
+                    location: line,
+                });
+            }
+            continue;
+        }
+        links.extend(find_urls(&unescape(value)).iter().map(|url| IcalLink {
+            url: url.as_str().to_string(),
+            property: name.clone(),
+            component: component.clone(),
+            location: line,
+        }));
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<IcalLink>, IcalScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<IcalLink>, IcalScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<IcalLink>, IcalScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum IcalScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IcalLink {
+    pub url: String,
+    /// The name of the property containing the link in upper case, like `ATTACH` or `DESCRIPTION`
+    pub property: String,
+    /// The innermost component containing the property, like `VEVENT`, `VTODO` or `VALARM`
+    pub component: String,
+    /// The line (starting at 1) where the property starts, before unfolding
+    pub location: usize,
+}
+
+impl Link for IcalLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.location)
+    }
+}
+
+impl Display for IcalLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Joins the lines starting with a space or tab to the previous one, without the leading whitespace-character.
+///
+/// Returns every unfolded content line together with the line (starting at 1) it starts at.
+fn unfold(content: &str) -> Vec<(usize, String)> {
+    let mut unfolded: Vec<(usize, String)> = vec![];
+    for (index, line) in content.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), unfolded.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ => unfolded.push((index + 1, line.to_string())),
+        }
+    }
+    unfolded
+}
+
+/// Splits a content line like `ATTACH;FMTTYPE=text/plain:https://example.com` into
+/// its upper case name, its parameters and its value
+fn split_content_line(line: &str) -> Option<(String, Vec<&str>, &str)> {
+    let (head, value) = split_outside_quotes(line, ':')?;
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+    Some((name, parts.collect(), value))
+}
+
+/// Splits `text` at the first `separator`, that is not inside a quoted parameter value
+fn split_outside_quotes(text: &str, separator: char) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => return Some((&text[..index], &text[index + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Resolves the escaped characters of a text value (`\n`, `\,`, `\;` and `\\`)
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(escaped) => unescaped.push(escaped),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_ICS: &[u8] = include_bytes!("../../../test_files/ical/ical_test.ics");
+
+    #[test]
+    fn scrape_ical_test() {
+        let links = scrape(TEST_ICS).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, &str, &str, usize)> = links
+            .iter()
+            .map(|it| {
+                (
+                    it.url.as_str(),
+                    it.property.as_str(),
+                    it.component.as_str(),
+                    it.location,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://calendar.test.com/tz", "TZURL", "VTIMEZONE", 5),
+                ("https://meeting.test.com/event", "URL", "VEVENT", 13),
+                (
+                    "https://files.test.com/agenda/2026/october/planning.pdf",
+                    "ATTACH",
+                    "VEVENT",
+                    14
+                ),
+                ("https://meet.test.com/j/123", "DESCRIPTION", "VEVENT", 16),
+                ("https://docs.test.com/a,b", "DESCRIPTION", "VEVENT", 16),
+                ("https://alarm.test.com", "DESCRIPTION", "VALARM", 20),
+                ("https://todo.test.com", "URL", "VTODO", 25),
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_binary_attachments_test() {
+        let ics = "BEGIN:VEVENT\nATTACH;ENCODING=BASE64;VALUE=BINARY:aHR0cHM6Ly9oaWRkZW4udGVzdC5jb20=\nEND:VEVENT\n";
+        assert!(scrape(ics.as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn split_content_line_test() {
+        assert_eq!(
+            split_content_line(r#"attach;FMTTYPE="a:b";X=1:https://test.com"#),
+            Some((
+                "ATTACH".to_string(),
+                vec![r#"FMTTYPE="a:b""#, "X=1"],
+                "https://test.com"
+            ))
+        );
+        assert_eq!(split_content_line("no value"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_ICS).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<IcalLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
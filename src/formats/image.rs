@@ -4,7 +4,8 @@ use std::io;
 use thiserror::Error;
 
 use crate::helpers::find_urls;
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
 
 pub fn scrape<R>(mut reader: R) -> Result<Vec<ImageLink>, ImageScrapingError>
 where
@@ -19,7 +20,7 @@ where
 
     Ok(exif
         .fields()
-        .map(|field| {
+        .flat_map(|field| {
             if let Value::Ascii(_) = &field.value {
                 find_urls(&field.display_value().to_string())
                     .iter()
@@ -32,11 +33,11 @@ where
                 vec![]
             }
         })
-        .flatten()
         .collect())
 }
 gen_scrape_from_file!(scrape(Read) -> Result<Vec<ImageLink>, ImageScrapingError>);
 gen_scrape_from_slice!(scrape(Read) -> Result<Vec<ImageLink>, ImageScrapingError>);
+gen_scrape_with_options!(scrape(BufRead + Seek) -> Result<Vec<ImageLink>, ImageScrapingError>);
 
 #[derive(Error, Debug)]
 pub enum ImageScrapingError {
@@ -47,11 +48,26 @@ pub enum ImageScrapingError {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageLink {
     pub url: String,
     pub exif_field: String,
 }
 
+impl Link for ImageLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::ExifField(self.exif_field.clone())
+    }
+}
+
 impl Display for ImageLink {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.url)
@@ -84,4 +100,13 @@ mod tests {
         let links = scrape_from_slice(TEST_JPG_NO_EXIF).unwrap();
         assert_eq!(links.len(), 0)
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_JPG).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<ImageLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
 }
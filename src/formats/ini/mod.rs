@@ -0,0 +1,200 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes the links from an INI-style configuration file, like a `php.ini`, a systemd unit or a `.gitconfig`.
+///
+/// The values of all keys are scanned for urls and reported with their section and key.
+/// Besides `key = value` the whitespace-separated form `key value` of directives is supported.
+/// A value ending in `\` continues on the next line, the lines are joined without the backslash.
+///
+/// Lines starting with `;` or `#` are comments, which are scanned too and flagged with [`IniLink::in_comment`].
+/// Comments behind a value are not recognized, since `;` and `#` are also part of many urls.
+/// # Example
+/// ```
+/// use link_scraper::formats::ini::scrape;
+/// let ini = "[remote \"origin\"]\nurl = https://example.com/repo.git\n; see https://example.com/docs\n";
+/// let links = scrape(ini.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/repo.git");
+/// assert_eq!(links[0].section.as_deref(), Some("remote \"origin\""));
+/// assert_eq!(links[0].key, "url");
+/// assert!(links[1].in_comment);
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<IniLink>, IniScrapingError>
+where
+    R: Read,
+{
+    // Configuration files are not always written in utf-8, like a php.ini with latin-1 comments
+    let mut content = vec![];
+    reader.read_to_end(&mut content)?;
+    let content = String::from_utf8_lossy(&content);
+
+    let mut links = vec![];
+    let mut section: Option<String> = None;
+    let mut lines = content.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim();
+        let new_link = |url: &str, key: &str, in_comment: bool| IniLink {
+            url: url.to_string(),
+            section: section.clone(),
+            key: key.to_string(),
+            in_comment,
+            line: index + 1,
+        };
+        if line.starts_with(['#', ';']) {
+            links.extend(
+                find_urls(line)
+                    .iter()
+                    .map(|url| new_link(url.as_str(), "", true)),
+            );
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|it| it.strip_suffix(']')) {
+            section = Some(name.trim().to_string());
+            continue;
+        }
+
+        let Some((key, value)) = line
+            .split_once('=')
+            .or_else(|| line.split_once(char::is_whitespace))
+        else {
+            continue;
+        };
+        let mut value = value.trim().to_string();
+        while let Some(continued) = value.strip_suffix('\\') {
+            let continued = continued.to_string();
+            value = match lines.next() {
+                Some((_, next)) => continued + next.trim(),
+                None => continued,
+            };
+        }
+        links.extend(
+            find_urls(&value)
+                .iter()
+                .map(|url| new_link(url.as_str(), key.trim(), false)),
+        );
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<IniLink>, IniScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<IniLink>, IniScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<IniLink>, IniScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum IniScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IniLink {
+    pub url: String,
+    /// The name of the section containing the link, like `Unit` for `[Unit]`.
+    /// [None] if the link comes before the first section.
+    pub section: Option<String>,
+    /// The key whose value contains the link, empty for a link inside a comment
+    pub key: String,
+    /// The link is inside a comment instead of a value
+    pub in_comment: bool,
+    /// The line (starting at 1) of the key or comment containing the link
+    pub line: usize,
+}
+
+impl Link for IniLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.line)
+    }
+}
+
+impl Display for IniLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INI: &[u8] = include_bytes!("../../../test_files/ini/ini_test.conf");
+
+    fn summary(links: &[IniLink]) -> Vec<(&str, Option<&str>, &str, bool, usize)> {
+        links
+            .iter()
+            .map(|it| {
+                (
+                    it.url.as_str(),
+                    it.section.as_deref(),
+                    it.key.as_str(),
+                    it.in_comment,
+                    it.line,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scrape_values_and_comments_test() {
+        let links = scrape(TEST_INI).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://global.test.com", None, "homepage", false, 2),
+                ("https://docs.test.com/unit", Some("Unit"), "", true, 5),
+                (
+                    "https://docs.test.com/service",
+                    Some("Unit"),
+                    "Documentation",
+                    false,
+                    6
+                ),
+                // The value is continued over three lines
+                (
+                    "https://mirror.test.com/releases/latest",
+                    Some("Service"),
+                    "ExecStart",
+                    false,
+                    10
+                ),
+                (
+                    "https://upstream.test.com:8080",
+                    Some("proxy"),
+                    "proxy_pass",
+                    false,
+                    15
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_lines_without_value_test() {
+        let ini = "[core]\nbare\n[broken\nkey =\n";
+        assert!(scrape(ini.as_bytes()).unwrap().is_empty());
+        // A continuation at the end of the file
+        let links = scrape("key = https://test.com\\".as_bytes()).unwrap();
+        assert_eq!(links[0].url, "https://test.com");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_INI).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<IniLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
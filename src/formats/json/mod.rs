@@ -0,0 +1,351 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::location::SourceLocation;
+use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::Deserializer;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::io::{BufReader, Read};
+use std::rc::Rc;
+use thiserror::Error;
+
+/// Scrapes links from all strings (keys and values) of a json-document.
+///
+/// Objects with duplicate keys are scraped completely, every occurrence of the key gets its own links.
+/// Returns an error, if the document is not valid json or nested deeper than 128 levels.
+pub fn scrape<R>(reader: R) -> Result<Vec<JsonLink>, JsonScrapingError>
+where
+    R: Read,
+{
+    scrape_with_options(reader, &JsonScrapeOptions::default())
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<JsonLink>, JsonScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<JsonLink>, JsonScrapingError>);
+
+/// Like [`scrape`], but lets you choose which strings are scraped.
+pub fn scrape_with_options<R>(
+    reader: R,
+    options: &JsonScrapeOptions,
+) -> Result<Vec<JsonLink>, JsonScrapingError>
+where
+    R: Read,
+{
+    let line = Rc::new(Cell::new(1));
+    let collector = JsonLinkCollector {
+        options,
+        line: line.clone(),
+        links: RefCell::new(vec![]),
+    };
+
+    // The deserializer reads the input byte by byte, so the line is up-to-date whenever a string is visited.
+    let mut deserializer = serde_json::Deserializer::from_reader(LineTrackingReader {
+        inner: BufReader::new(reader),
+        line,
+    });
+    JsonValueSeed {
+        pointer: String::new(),
+        collector: &collector,
+    }
+    .deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    Ok(collector.links.into_inner())
+}
+
+/// Which strings of a json-document are scraped by [`scrape_with_options`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonScrapeOptions {
+    /// Scrape the keys of objects
+    pub scrape_keys: bool,
+    /// Scrape string-values
+    pub scrape_values: bool,
+}
+
+impl Default for JsonScrapeOptions {
+    fn default() -> Self {
+        Self {
+            scrape_keys: true,
+            scrape_values: true,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum JsonScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JsonLinkKind {
+    /// The link is inside the key of an object-member <br/>
+    /// Example: `{"https://link.example.com": true}`
+    Key,
+
+    /// The link is inside a string-value <br/>
+    /// Example: `{"homepage": "https://link.example.com"}`
+    Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JsonLink {
+    pub url: String,
+    /// The line (starting at 1) of the string containing the link
+    pub location: usize,
+    /// The [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) json-pointer to the value containing the link.
+    /// For links inside keys, this points to the value of the member.
+    pub json_pointer: String,
+    pub kind: JsonLinkKind,
+}
+
+impl Link for JsonLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.location)
+    }
+
+    fn source_location(&self) -> SourceLocation {
+        SourceLocation::Pointer(self.json_pointer.clone())
+    }
+}
+
+impl Display for JsonLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Escapes a key to be used as a reference-token of a json-pointer
+fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Counts the lines of everything read through it.
+struct LineTrackingReader<R> {
+    inner: R,
+    line: Rc<Cell<usize>>,
+}
+
+impl<R: Read> Read for LineTrackingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let new_lines = buf[..read].iter().filter(|it| **it == b'\n').count();
+        self.line.set(self.line.get() + new_lines);
+        Ok(read)
+    }
+}
+
+struct JsonLinkCollector<'a> {
+    options: &'a JsonScrapeOptions,
+    line: Rc<Cell<usize>>,
+    links: RefCell<Vec<JsonLink>>,
+}
+
+impl JsonLinkCollector<'_> {
+    fn scrape(&self, s: &str, pointer: &str, kind: JsonLinkKind) {
+        let enabled = match kind {
+            JsonLinkKind::Key => self.options.scrape_keys,
+            JsonLinkKind::Value => self.options.scrape_values,
+        };
+        if !enabled {
+            return;
+        }
+
+        self.links
+            .borrow_mut()
+            .extend(find_urls(s).iter().map(|link| JsonLink {
+                url: link.as_str().to_string(),
+                location: self.line.get(),
+                json_pointer: pointer.to_string(),
+                kind: kind.clone(),
+            }));
+    }
+}
+
+/// Visits a json-value and all of its children without keeping them in memory.
+struct JsonValueSeed<'a> {
+    pointer: String,
+    collector: &'a JsonLinkCollector<'a>,
+}
+
+impl JsonValueSeed<'_> {
+    fn child(&self, token: &str) -> Self {
+        Self {
+            pointer: format!("{}/{}", self.pointer, token),
+            collector: self.collector,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for JsonValueSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for JsonValueSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("any json-value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        self.collector.scrape(v, &self.pointer, JsonLinkKind::Value);
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut index = 0usize;
+        while seq
+            .next_element_seed(self.child(&index.to_string()))?
+            .is_some()
+        {
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key::<String>()? {
+            let child = self.child(&escape_pointer_token(&key));
+            self.collector
+                .scrape(&key, &child.pointer, JsonLinkKind::Key);
+            map.next_value_seed(child)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_JSON: &[u8] = include_bytes!("../../../test_files/json/json_test.json");
+
+    fn find<'a>(links: &'a [JsonLink], url: &str) -> &'a JsonLink {
+        links
+            .iter()
+            .find(|it| it.url == url)
+            .unwrap_or_else(|| panic!("{} was not found in {:?}", url, links))
+    }
+
+    #[test]
+    fn scrape_json_test() {
+        let links = scrape_from_slice(TEST_JSON).unwrap();
+        println!("{:?}", links);
+
+        let value = find(&links, "https://value.test.com");
+        assert_eq!(value.json_pointer, "/homepage");
+        assert_eq!(value.kind, JsonLinkKind::Value);
+        assert_eq!(value.location, 2);
+
+        let key = find(&links, "https://key.test.com");
+        assert_eq!(key.json_pointer, "/https:~1~1key.test.com");
+        assert_eq!(key.kind, JsonLinkKind::Key);
+    }
+
+    #[test]
+    fn scrape_nested_arrays_test() {
+        let links = scrape_from_slice(TEST_JSON).unwrap();
+        let nested = find(&links, "https://nested.test.com");
+        assert_eq!(nested.json_pointer, "/nested/1/0/a~0b");
+        assert_eq!(nested.location, 8);
+    }
+
+    #[test]
+    fn scrape_duplicate_keys_test() {
+        let links = scrape_from_slice(TEST_JSON).unwrap();
+        let duplicates: Vec<&str> = links
+            .iter()
+            .filter(|it| it.json_pointer == "/duplicate")
+            .map(|it| it.url.as_str())
+            .collect();
+        assert_eq!(
+            duplicates,
+            vec!["https://first.test.com", "https://second.test.com"]
+        );
+    }
+
+    #[test]
+    fn scrape_deeply_nested_test() {
+        let depth = 100;
+        let json = format!(
+            "{}\"https://deep.test.com\"{}",
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+        let links = scrape_from_slice(json).unwrap();
+        assert_eq!(links[0].json_pointer, "/0".repeat(depth));
+    }
+
+    #[test]
+    fn scrape_with_options_test() {
+        let options = JsonScrapeOptions {
+            scrape_keys: false,
+            ..Default::default()
+        };
+        let links = scrape_with_options(TEST_JSON, &options).unwrap();
+        assert!(links.iter().all(|it| it.kind == JsonLinkKind::Value));
+    }
+
+    #[test]
+    fn fail_on_invalid_json_test() {
+        let result = scrape_from_slice(b"{\"a\": \"https://test.com\"");
+        assert!(matches!(result, Err(JsonScrapingError::JsonError(_))));
+        let result = scrape_from_slice(b"{} trailing");
+        assert!(matches!(result, Err(JsonScrapingError::JsonError(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_JSON).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<JsonLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
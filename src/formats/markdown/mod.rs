@@ -0,0 +1,342 @@
+use crate::helpers::{find_urls, UrlMatch};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes links from a markdown-document.
+///
+/// Finds inline-, reference- and autolinks, images, link reference definitions
+/// and bare urls inside the text. Urls inside code spans and code blocks are ignored.
+///
+/// Link destinations are taken as they are, even if they are relative links.
+pub fn scrape<R>(mut reader: R) -> Result<Vec<MarkdownLink>, MarkdownScrapingError>
+where
+    R: Read,
+{
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let lines = LineIndex::new(&content);
+
+    let mut collector: Vec<MarkdownLink> = vec![];
+    let mut push = |url: &str, span: std::ops::Range<usize>, kind: MarkdownLinkKind| {
+        collector.push(MarkdownLink {
+            url: url.to_string(),
+            location: lines.location(&content, span),
+            kind,
+        })
+    };
+
+    // Links and images may contain text, which would otherwise be found a second time
+    let mut link_depth = 0usize;
+    let mut code_block_depth = 0usize;
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let mut parser = Parser::new_ext(&content, options).into_offset_iter();
+    for (event, range) in parser.by_ref() {
+        match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                id,
+                ..
+            }) => {
+                link_depth += 1;
+                let kind = match link_type {
+                    LinkType::Autolink | LinkType::Email => MarkdownLinkKind::Autolink,
+                    LinkType::Inline => MarkdownLinkKind::Inline,
+                    LinkType::Reference
+                    | LinkType::ReferenceUnknown
+                    | LinkType::Collapsed
+                    | LinkType::CollapsedUnknown
+                    | LinkType::Shortcut
+                    | LinkType::ShortcutUnknown => MarkdownLinkKind::Reference {
+                        label: id.to_string(),
+                    },
+                    // Wikilinks are not enabled in the parser-options
+                    LinkType::WikiLink { .. } => continue,
+                };
+                if link_type == LinkType::Email {
+                    push(&format!("mailto:{}", dest_url), range, kind)
+                } else {
+                    push(&dest_url, range, kind)
+                }
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                link_depth += 1;
+                push(&dest_url, range, MarkdownLinkKind::Image)
+            }
+            Event::End(TagEnd::Link | TagEnd::Image) => link_depth -= 1,
+            Event::Start(Tag::CodeBlock(_)) => code_block_depth += 1,
+            Event::End(TagEnd::CodeBlock) => code_block_depth -= 1,
+            Event::Text(text) if link_depth == 0 && code_block_depth == 0 => {
+                find_urls(&text).iter().for_each(|link| {
+                    push(
+                        link.as_str(),
+                        text_span(&content, &range, &text, link),
+                        MarkdownLinkKind::PlainText,
+                    )
+                })
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                find_urls(&html).iter().for_each(|link| {
+                    push(
+                        link.as_str(),
+                        text_span(&content, &range, &html, link),
+                        MarkdownLinkKind::Html,
+                    )
+                })
+            }
+            _ => {}
+        }
+    }
+
+    let mut definitions: Vec<_> = parser.reference_definitions().iter().collect();
+    definitions.sort_by_key(|(_, definition)| definition.span.start);
+    for (label, definition) in definitions {
+        push(
+            &definition.dest,
+            definition.span.clone(),
+            MarkdownLinkKind::ReferenceDefinition {
+                label: label.to_string(),
+            },
+        );
+    }
+
+    collector.sort_by_key(|link| link.location.byte_offset);
+    Ok(collector)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<MarkdownLink>, MarkdownScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<MarkdownLink>, MarkdownScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<MarkdownLink>, MarkdownScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum MarkdownScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarkdownLinkKind {
+    /// The link is an inline-link <br/>
+    /// Example: `[text](https://link.example.com)`
+    Inline,
+
+    /// The link is a reference-link, which got its destination from a [`MarkdownLinkKind::ReferenceDefinition`] <br/>
+    /// Example: `[text][label]`, `[label][]` or `[label]`
+    Reference { label: String },
+
+    /// The link is the destination of a link reference definition <br/>
+    /// Example: `[label]: https://link.example.com`
+    ReferenceDefinition { label: String },
+
+    /// The link is an autolink, email-addresses are prefixed with `mailto:` <br/>
+    /// Example: `<https://link.example.com>`
+    Autolink,
+
+    /// The link is the source of an image <br/>
+    /// Example: `![alt](https://link.example.com/image.png)`
+    Image,
+
+    /// The link is inside raw html <br/>
+    /// Example: `<a href="https://link.example.com">text</a>`
+    Html,
+
+    /// The link is inside a plaintext portion <br/>
+    /// Example: `Just a text with a link to https://link.example.com`
+    PlainText,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkdownLink {
+    pub url: String,
+    pub location: MarkdownLinkLocation,
+    pub kind: MarkdownLinkKind,
+}
+
+impl Link for MarkdownLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::LineAndPos {
+            line: self.location.line,
+            pos: self.location.pos,
+            end: self.location.end,
+            byte_offset: self.location.byte_offset,
+            column: self.location.column,
+        }
+    }
+}
+
+impl Display for MarkdownLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkdownLinkLocation {
+    /// The line of the link, starting at 1
+    pub line: usize,
+    /// The byte-offset of the link inside its line
+    pub pos: usize,
+    /// The byte-offset of the end of the link relative to the start of its line (exclusive).
+    /// The link may span multiple lines, `end - pos` is always its length inside the document.
+    pub end: usize,
+    /// The byte-offset of the link from the start of the file
+    pub byte_offset: usize,
+    /// The column of the link inside its line, counted in unicode scalar values and starting at 0
+    pub column: usize,
+}
+
+/// The byte-offsets of all line starts, to convert byte-offsets into lines.
+struct LineIndex(Vec<usize>);
+
+impl LineIndex {
+    fn new(content: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+        Self(line_starts)
+    }
+
+    fn location(&self, content: &str, span: std::ops::Range<usize>) -> MarkdownLinkLocation {
+        let line = self.0.partition_point(|start| *start <= span.start);
+        let line_start = self.0[line - 1];
+        let pos = span.start - line_start;
+        MarkdownLinkLocation {
+            line,
+            pos,
+            end: pos + span.len(),
+            byte_offset: span.start,
+            column: content[line_start..span.start].chars().count(),
+        }
+    }
+}
+
+/// Maps a link found inside of the `text` of an event back into the document.
+///
+/// Falls back to the whole event, if the text was changed by the parser (e.g. by escapes or entities).
+fn text_span(
+    content: &str,
+    range: &std::ops::Range<usize>,
+    text: &str,
+    link: &UrlMatch,
+) -> std::ops::Range<usize> {
+    content[range.clone()]
+        .find(text)
+        .map_or(range.clone(), |start| {
+            range.start + start + link.start()..range.start + start + link.end()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MARKDOWN: &[u8] = include_bytes!("../../../test_files/markdown/markdown_test.md");
+
+    fn find_all<'a>(links: &'a [MarkdownLink], url: &str) -> Vec<&'a MarkdownLink> {
+        links.iter().filter(|it| it.url == url).collect()
+    }
+
+    #[test]
+    fn scrape_inline_and_image_test() {
+        let links = scrape_from_slice(TEST_MARKDOWN).unwrap();
+        println!("{:?}", links);
+
+        let inline = find_all(&links, "https://inline.test.com");
+        assert_eq!(inline.len(), 1);
+        assert_eq!(inline[0].kind, MarkdownLinkKind::Inline);
+        assert_eq!(inline[0].location.line, 3);
+        assert_eq!(inline[0].location.pos, 3);
+        assert_eq!(
+            inline[0].location.end - inline[0].location.pos,
+            "[inline](https://inline.test.com)".len()
+        );
+
+        let image = find_all(&links, "https://image.test.com/image.png");
+        assert_eq!(image.len(), 1);
+        assert_eq!(image[0].kind, MarkdownLinkKind::Image);
+    }
+
+    #[test]
+    fn scrape_autolink_test() {
+        let links = scrape_from_slice(TEST_MARKDOWN).unwrap();
+        let autolink = find_all(&links, "https://autolink.test.com");
+        assert_eq!(autolink.len(), 1);
+        assert_eq!(autolink[0].kind, MarkdownLinkKind::Autolink);
+        assert_eq!(
+            find_all(&links, "mailto:mail@autolink.test.com")[0].kind,
+            MarkdownLinkKind::Autolink
+        );
+    }
+
+    #[test]
+    fn scrape_reference_test() {
+        let links = scrape_from_slice(TEST_MARKDOWN).unwrap();
+        let reference = find_all(&links, "https://reference.test.com");
+        assert_eq!(reference.len(), 3);
+        assert_eq!(
+            reference
+                .iter()
+                .filter(|it| it.kind
+                    == MarkdownLinkKind::Reference {
+                        label: "ref".to_string()
+                    })
+                .count(),
+            2
+        );
+        assert_eq!(
+            reference[2].kind,
+            MarkdownLinkKind::ReferenceDefinition {
+                label: "ref".to_string()
+            }
+        );
+        assert_eq!(reference[2].location.line, 15);
+    }
+
+    #[test]
+    fn scrape_plaintext_ignores_code_test() {
+        let links = scrape_from_slice(TEST_MARKDOWN).unwrap();
+        let plaintext = find_all(&links, "https://plaintext.test.com");
+        assert_eq!(plaintext.len(), 1);
+        assert_eq!(plaintext[0].kind, MarkdownLinkKind::PlainText);
+        assert_eq!(plaintext[0].location.line, 9);
+        assert_eq!(plaintext[0].location.pos, 10);
+        assert_eq!(
+            plaintext[0].location.end - plaintext[0].location.pos,
+            "https://plaintext.test.com".len()
+        );
+
+        assert!(find_all(&links, "https://code.test.com").is_empty());
+        assert!(find_all(&links, "https://codeblock.test.com").is_empty());
+    }
+
+    #[test]
+    fn ignore_extension_syntax_test() {
+        let links = scrape_from_slice(b"A [[Wiki Page]] link").unwrap();
+        assert!(links.is_empty(), "{:?}", links);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_MARKDOWN).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<MarkdownLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -1,20 +1,73 @@
 #[cfg(any(feature = "odf", feature = "ooxml"))]
 mod compressed_formats_common;
+#[cfg(feature = "css")]
+/// Stylesheets (.css)
+pub mod css;
+#[cfg(feature = "csv")]
+/// Comma- and tab-separated files
+pub mod csv;
+#[cfg(feature = "email")]
+/// MIME e-mails (.eml)
+pub mod email;
+#[cfg(feature = "epub")]
+pub mod epub;
+#[cfg(feature = "feed")]
+/// RSS 2.0 and Atom feeds
+pub mod feed;
+#[cfg(feature = "geo")]
+/// GPX and KML
+pub mod geo;
+#[cfg(feature = "html")]
+pub mod html;
+#[cfg(feature = "ical")]
+/// iCalendar (.ics)
+pub mod ical;
 #[cfg(feature = "image")]
 pub mod image;
+#[cfg(feature = "ini")]
+/// .ini and .conf
+pub mod ini;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 #[cfg(feature = "odf")]
 /// .odt, .ods, .odp
 pub mod odf;
 #[cfg(feature = "ooxml")]
 /// .docx, .pptx, .xlsx
 pub mod ooxml;
+#[cfg(feature = "opml")]
+/// OPML outlines, like the subscription lists of feed readers
+pub mod opml;
 #[cfg(feature = "pdf")]
 pub mod pdf;
 #[cfg(feature = "plaintext")]
 /// Any plaintext-format
 pub mod plaintext;
+#[cfg(feature = "plist")]
+/// Apple property lists, in XML- and binary encoding
+pub mod plist;
 #[cfg(feature = "rtf")]
 pub mod rtf;
+#[cfg(feature = "shortcut")]
+/// .url and .desktop
+pub mod shortcut;
+#[cfg(feature = "source_code")]
+/// Comments and string literals of source code (.rs, .c, .py, .sh, ...)
+pub mod source_code;
+#[cfg(feature = "subtitle")]
+/// .srt and .vtt
+pub mod subtitle;
+#[cfg(feature = "toml")]
+/// .toml
+pub mod toml;
+#[cfg(feature = "warc")]
+/// Web archives (.warc)
+pub mod warc;
 #[cfg(any(feature = "xml", feature = "xlink"))]
 /// Also contains xml-based formats
 pub mod xml;
+#[cfg(feature = "yaml")]
+/// .yaml and .yml
+pub mod yaml;
@@ -1,7 +1,8 @@
 use crate::formats::compressed_formats_common::unified_unzip_scrape;
 use crate::formats::odf::OdfLinkKind::{Hyperlink, PlainText};
 use crate::helpers::find_urls;
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
 use std::fmt::{Display, Formatter};
 use std::io::{Read, Seek};
 use thiserror::Error;
@@ -19,7 +20,7 @@ where
 {
     unified_unzip_scrape(reader, |reader, file_name, links| {
         if file_name.ends_with(".xml") {
-            scrape_from_xml_file(reader, &file_name, links)
+            scrape_from_xml_file(reader, file_name, links)
         } else {
             Ok(())
         }
@@ -27,6 +28,7 @@ where
 }
 gen_scrape_from_file!(scrape(Read) -> Result<Vec<OdfLink>, OdfScrapingError>);
 gen_scrape_from_slice!(scrape(Read) -> Result<Vec<OdfLink>, OdfScrapingError>);
+gen_scrape_with_options!(scrape(Read + Seek) -> Result<Vec<OdfLink>, OdfScrapingError>);
 
 #[derive(Error, Debug)]
 pub enum OdfScrapingError {
@@ -39,12 +41,30 @@ pub enum OdfScrapingError {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OdfLink {
     pub url: String,
     pub location: OdfLinkLocation,
     pub kind: OdfLinkKind,
 }
 
+impl Link for OdfLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::ArchiveFile {
+            file: self.location.file.clone(),
+            position: self.location.position,
+        }
+    }
+}
+
 impl Display for OdfLink {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.url)
@@ -53,12 +73,18 @@ impl Display for OdfLink {
 
 /// This Location references the location in the unzipped odf file-structure.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OdfLinkLocation {
     pub file: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
     pub position: TextPosition,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OdfLinkKind {
     /// The link is contained as Text or as a Comment inside the document
     PlainText,
@@ -74,7 +100,7 @@ where
     R: Read + Seek,
 {
     crate::formats::compressed_formats_common::scrape_unfiltered(reader)
-        .map_err(|e| OdfScrapingError::from(e))
+        .map_err(OdfScrapingError::from)
 }
 
 /// Scrapes links from given .xml file-text
@@ -113,7 +139,7 @@ fn scrape_from_xml_file(
                 }
             }
             XmlEvent::Characters(chars) => collector.append(
-                &mut find_urls(&chars)
+                &mut find_urls(chars)
                     .iter()
                     .map(|link| OdfLink {
                         url: link.as_str().to_string(),
@@ -136,7 +162,9 @@ fn scrape_from_xml_file(
 mod tests {
     use super::*;
     use std::include_bytes;
-    use std::io::Cursor;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
 
     const TEST_ODT: &[u8] = include_bytes!("../../test_files/odf/odt_test.odt");
     const TEST_ODS: &[u8] = include_bytes!("../../test_files/odf/ods_test.ods");
@@ -191,9 +219,52 @@ mod tests {
             .any(|it| it.url == "https://hyperlink.test.com/" && it.kind == Hyperlink));
     }
 
+    #[test]
+    pub fn scrape_text_and_draw_hyperlinks_test() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0" xmlns:xlink="http://www.w3.org/1999/xlink">
+<office:body><office:text>
+<text:p>See <text:a xlink:type="simple" xlink:href="https://text.test.com/">the text</text:a> or https://plain.test.com</text:p>
+<text:p><draw:a xlink:type="simple" xlink:href="https://frame.test.com/"><draw:frame draw:name="Image"/></draw:a></text:p>
+</office:text></office:body></office:document-content>"#;
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("mimetype", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"application/vnd.oasis.opendocument.text")
+            .unwrap();
+        zip.start_file("content.xml", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+        let odt = zip.finish().unwrap().into_inner();
+
+        let links = scrape_from_slice(odt).unwrap();
+        let found: Vec<(&str, OdfLinkKind, &str)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.kind, it.location.file.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://text.test.com/", Hyperlink, "content.xml"),
+                ("https://plain.test.com", PlainText, "content.xml"),
+                ("https://frame.test.com/", Hyperlink, "content.xml"),
+            ]
+        );
+    }
+
     #[test]
     pub fn scrape_unfiltered_test() {
         let links = scrape_unfiltered(Cursor::new(TEST_ODT)).unwrap();
         assert_eq!(links.len(), 47);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_ODT).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<OdfLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
 }
@@ -0,0 +1,303 @@
+use crate::formats::ooxml::{
+    is_relationship_id, read_relationship_targets, relationships_part, OoxmlLinkLocation,
+    OoxmlScrapingError,
+};
+use crate::helpers::{find_attribute, find_urls};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Seek};
+use xml::common::{Position, TextPosition};
+use xml::reader::XmlEvent;
+use xml::EventReader;
+use zip::result::ZipError;
+
+/// Scrapes all links from a given docx-file
+///
+/// Unlike [`crate::formats::ooxml::scrape`] this resolves the `r:id` of every `w:hyperlink` with the
+/// relationships of its part, so only the targets of hyperlinks that are actually used are returned.
+/// `HYPERLINK`-fields and urls in the text of the document are found as well.
+pub fn scrape<R>(reader: R) -> Result<Vec<DocxLink>, OoxmlScrapingError>
+where
+    R: Read + Seek,
+{
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let parts = archive
+        .file_names()
+        .filter(|name| is_content_part(name))
+        .map(|name| name.to_owned())
+        .sorted()
+        .collect_vec();
+
+    let mut links = vec![];
+    for part in parts {
+        let targets = match archive.by_name(&relationships_part(&part)) {
+            Ok(relationships) => read_relationship_targets(relationships, "hyperlink")?,
+            Err(ZipError::FileNotFound) => HashMap::new(),
+            Err(error) => return Err(error.into()),
+        };
+        scrape_from_part(archive.by_name(&part)?, &part, &targets, &mut links)?;
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<DocxLink>, OoxmlScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<DocxLink>, OoxmlScrapingError>);
+gen_scrape_with_options!(scrape(Read + Seek) -> Result<Vec<DocxLink>, OoxmlScrapingError>);
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocxLink {
+    pub url: String,
+    pub location: OoxmlLinkLocation,
+    pub kind: DocxLinkKind,
+}
+
+impl Link for DocxLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::ArchiveFile {
+            file: self.location.file.clone(),
+            position: self.location.position,
+        }
+    }
+}
+
+impl Display for DocxLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DocxLinkKind {
+    /// The target of a `w:hyperlink`, resolved with the relationships of its part
+    Hyperlink,
+    /// The target of a `HYPERLINK`-field
+    Field,
+    /// The link is contained as text inside the document
+    Text,
+}
+
+/// Checks if `file_name` is a part with text of the document, like `word/document.xml` or `word/footnotes.xml`
+fn is_content_part(file_name: &str) -> bool {
+    file_name
+        .strip_prefix("word/")
+        .is_some_and(|name| name.ends_with(".xml") && !name.contains('/'))
+}
+
+/// Scrapes the links from a part of the document, `targets` are the hyperlink-relationships of this part
+fn scrape_from_part(
+    data: impl Read,
+    file_name: &str,
+    targets: &HashMap<String, String>,
+    collector: &mut Vec<DocxLink>,
+) -> Result<(), OoxmlScrapingError> {
+    let mut parser = EventReader::new(data);
+    let push = |collector: &mut Vec<DocxLink>, url: &str, position, kind| {
+        collector.push(DocxLink {
+            url: url.to_string(),
+            location: OoxmlLinkLocation {
+                file: file_name.to_string(),
+                position,
+            },
+            kind,
+        })
+    };
+
+    // A complex field may split its instruction over multiple runs, so it is collected until the field ends
+    let mut in_instruction = false;
+    let mut instruction = String::new();
+    let mut instruction_position = TextPosition::new();
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => match name.local_name.as_str() {
+                "hyperlink" => {
+                    let id = attributes
+                        .iter()
+                        .find(|it| is_relationship_id(it))
+                        .and_then(|it| targets.get(&it.value));
+                    if let Some(target) = id {
+                        push(
+                            collector,
+                            target,
+                            parser.position(),
+                            DocxLinkKind::Hyperlink,
+                        );
+                    }
+                }
+                "fldSimple" => {
+                    if let Some(target) =
+                        find_attribute(&attributes, "instr").and_then(find_field_target)
+                    {
+                        push(collector, target, parser.position(), DocxLinkKind::Field);
+                    }
+                }
+                "fldChar" => {
+                    if find_attribute(&attributes, "fldCharType") != Some("begin") {
+                        if let Some(target) = find_field_target(&instruction) {
+                            push(collector, target, instruction_position, DocxLinkKind::Field);
+                        }
+                    }
+                    instruction.clear();
+                }
+                "instrText" => {
+                    if instruction.is_empty() {
+                        instruction_position = parser.position();
+                    }
+                    in_instruction = true;
+                }
+                _ => {}
+            },
+            XmlEvent::EndElement { name } if name.local_name == "instrText" => {
+                in_instruction = false;
+            }
+            XmlEvent::Characters(text) | XmlEvent::Whitespace(text) => {
+                if in_instruction {
+                    instruction.push_str(&text);
+                } else {
+                    for link in find_urls(&text) {
+                        push(
+                            collector,
+                            link.as_str(),
+                            parser.position(),
+                            DocxLinkKind::Text,
+                        );
+                    }
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Finds the target in a field-instruction like `HYPERLINK "https://example.com" \o "Tooltip"`
+///
+/// Links to bookmarks inside the document (`HYPERLINK \l "bookmark"`) are ignored.
+fn find_field_target(instruction: &str) -> Option<&str> {
+    let instruction = instruction.trim_start();
+    let (keyword, arguments) = instruction.split_at_checked("HYPERLINK".len())?;
+    if !keyword.eq_ignore_ascii_case("HYPERLINK") || !arguments.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let arguments = arguments.trim_start();
+    let target = match arguments.strip_prefix('"') {
+        Some(quoted) => &quoted[..quoted.find('"')?],
+        None => arguments.split_whitespace().next()?,
+    };
+    if target.is_empty() || target.starts_with('\\') {
+        None
+    } else {
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::include_bytes;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    const TEST_DOCX: &[u8] = include_bytes!("../../../test_files/ooxml/docx_test.docx");
+
+    #[test]
+    fn scrape_docx_test() {
+        let links = scrape_from_slice(TEST_DOCX).unwrap();
+        println!("{:?}", links);
+        let hyperlinks = links
+            .iter()
+            .filter(|it| {
+                it.kind == DocxLinkKind::Hyperlink && it.location.file == "word/document.xml"
+            })
+            .collect_vec();
+        assert_eq!(hyperlinks.len(), 1);
+        assert_eq!(hyperlinks[0].url, "https://hyperlink.test.com/");
+        assert!(links.iter().any(|it| it.url == "https://comment.test.com/"
+            && it.kind == DocxLinkKind::Hyperlink
+            && it.location.file == "word/comments.xml"));
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://plaintext.test.com" && it.kind == DocxLinkKind::Text));
+        // Urls of the ooxml-functionalities are not returned
+        assert!(links
+            .iter()
+            .all(|it| !it.url.contains("schemas.openxmlformats.org")));
+    }
+
+    #[test]
+    fn scrape_fields_test() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<w:body><w:p>
+<w:r><w:fldChar w:fldCharType="begin"/></w:r>
+<w:r><w:instrText xml:space="preserve"> HYPERLINK "https://field</w:instrText></w:r>
+<w:r><w:instrText>.test.com/a" \o "Tooltip" </w:instrText></w:r>
+<w:r><w:fldChar w:fldCharType="separate"/></w:r>
+<w:r><w:t>Click here</w:t></w:r>
+<w:r><w:fldChar w:fldCharType="end"/></w:r>
+<w:fldSimple w:instr=" HYPERLINK \l &quot;bookmark&quot; "><w:r><w:t>Jump</w:t></w:r></w:fldSimple>
+<w:fldSimple w:instr="HYPERLINK https://simple.test.com"><w:r><w:t>Simple</w:t></w:r></w:fldSimple>
+<w:hyperlink r:id="rId1"><w:r><w:t>Relationship</w:t></w:r></w:hyperlink>
+</w:p></w:body></w:document>"#;
+        let relationships = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://relationship.test.com" TargetMode="External"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://unused.test.com" TargetMode="External"/>
+</Relationships>"#;
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("word/document.xml", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(document.as_bytes()).unwrap();
+        zip.start_file("word/_rels/document.xml.rels", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(relationships.as_bytes()).unwrap();
+        let docx = zip.finish().unwrap().into_inner();
+
+        let links = scrape_from_slice(docx).unwrap();
+        let found = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.kind))
+            .collect_vec();
+        assert_eq!(
+            found,
+            vec![
+                ("https://field.test.com/a", DocxLinkKind::Field),
+                ("https://simple.test.com", DocxLinkKind::Field),
+                ("https://relationship.test.com", DocxLinkKind::Hyperlink),
+            ]
+        );
+        assert_eq!(links[0].location.position.row, 4);
+    }
+
+    #[test]
+    fn find_field_target_test() {
+        assert_eq!(
+            find_field_target(r#" HYPERLINK "https://a.com" \o "b""#),
+            Some("https://a.com")
+        );
+        assert_eq!(
+            find_field_target("hyperlink https://a.com"),
+            Some("https://a.com")
+        );
+        assert_eq!(find_field_target(r#"HYPERLINK \l "bookmark""#), None);
+        assert_eq!(find_field_target("HYPERLINKS https://a.com"), None);
+        assert_eq!(find_field_target(r#"INCLUDEPICTURE "https://a.com""#), None);
+    }
+}
@@ -1,14 +1,22 @@
 use crate::formats::compressed_formats_common::unified_unzip_scrape;
 use crate::formats::ooxml::OoxmlLinkKind::{Comment, Hyperlink, PlainText};
-use crate::helpers::find_urls;
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use crate::helpers::{find_attribute, find_urls};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::io::{Cursor, Read, Seek};
 use thiserror::Error;
+use xml::attribute::OwnedAttribute;
 use xml::common::{Position, TextPosition};
 use xml::reader::XmlEvent;
 use xml::EventReader;
 
+/// .docx with resolved hyperlink-relationships
+pub mod docx;
+/// .xlsx with resolved hyperlinks and shared strings
+pub mod xlsx;
+
 /// Scrapes all links from a given ooxml-file
 ///
 /// Tries to filter out urls related to ooxml-functionalities, but might be a bit too aggressive at times
@@ -19,9 +27,9 @@ where
 {
     unified_unzip_scrape(reader, |reader, file_name, links| {
         if file_name.ends_with(".rels") {
-            scrape_from_rels_file(reader, &file_name, links)
+            scrape_from_rels_file(reader, file_name, links)
         } else if file_name.ends_with(".xml") {
-            scrape_from_xml_file(reader, &file_name, links)
+            scrape_from_xml_file(reader, file_name, links)
         } else {
             Ok(())
         }
@@ -29,6 +37,7 @@ where
 }
 gen_scrape_from_file!(scrape(Read) -> Result<Vec<OoxmlLink>, OoxmlScrapingError>);
 gen_scrape_from_slice!(scrape(Read) -> Result<Vec<OoxmlLink>, OoxmlScrapingError>);
+gen_scrape_with_options!(scrape(Read + Seek) -> Result<Vec<OoxmlLink>, OoxmlScrapingError>);
 
 #[derive(Error, Debug)]
 pub enum OoxmlScrapingError {
@@ -40,13 +49,31 @@ pub enum OoxmlScrapingError {
     ZipError(#[from] zip::result::ZipError),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OoxmlLink {
     pub url: String,
     pub location: OoxmlLinkLocation,
     pub kind: OoxmlLinkKind,
 }
 
+impl Link for OoxmlLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::ArchiveFile {
+            file: self.location.file.clone(),
+            position: self.location.position,
+        }
+    }
+}
+
 impl Display for OoxmlLink {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.url)
@@ -54,13 +81,19 @@ impl Display for OoxmlLink {
 }
 
 /// This Location references the location in the unzipped ooxml file-structure.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OoxmlLinkLocation {
     pub file: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
     pub position: TextPosition,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OoxmlLinkKind {
     /// The link is contained as Text inside the document
     PlainText,
@@ -78,7 +111,7 @@ where
     R: Read + Seek,
 {
     crate::formats::compressed_formats_common::scrape_unfiltered(reader)
-        .map_err(|e| OoxmlScrapingError::from(e))
+        .map_err(OoxmlScrapingError::from)
 }
 
 /// Scrapes all links from a given ooxml file.
@@ -121,7 +154,7 @@ fn scrape_from_rels_file(
             }
         }
 
-        if let XmlEvent::EndDocument {} = xml_event {
+        if let XmlEvent::EndDocument = xml_event {
             break;
         }
     }
@@ -145,7 +178,7 @@ fn scrape_from_xml_file(
             _ => None,
         };
         if let Some(text) = raw_text {
-            find_urls(&text).iter().for_each(|link| {
+            find_urls(text).iter().for_each(|link| {
                 collector.push(OoxmlLink {
                     url: link.as_str().to_string(),
                     location: OoxmlLinkLocation {
@@ -165,21 +198,68 @@ fn scrape_from_xml_file(
             });
         }
 
-        if let XmlEvent::EndDocument {} = xml_event {
+        if let XmlEvent::EndDocument = xml_event {
             break;
         }
     }
     Ok(())
 }
 
+/// The name of the part containing the relationships of `part`, e.g. `word/_rels/document.xml.rels`
+fn relationships_part(part: &str) -> String {
+    match part.rsplit_once('/') {
+        Some((directory, name)) => format!("{directory}/_rels/{name}.rels"),
+        None => format!("_rels/{part}.rels"),
+    }
+}
+
+/// Reads the targets of all relationships of the given type (like `hyperlink` or `worksheet`)
+/// from a `.rels`-part by their id
+fn read_relationship_targets(
+    data: impl Read,
+    relationship_type: &str,
+) -> Result<HashMap<String, String>, OoxmlScrapingError> {
+    let type_suffix = format!("/{relationship_type}");
+    let mut targets = HashMap::new();
+    let mut parser = EventReader::new(data);
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } if name.local_name == "Relationship" => {
+                let has_type = find_attribute(&attributes, "Type")
+                    .is_some_and(|kind| kind.ends_with(&type_suffix));
+                let id = find_attribute(&attributes, "Id");
+                let target = find_attribute(&attributes, "Target").filter(|it| !it.is_empty());
+                if let (true, Some(id), Some(target)) = (has_type, id, target) {
+                    targets.insert(id.to_string(), target.to_string());
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(targets)
+}
+
+/// Checks if `attribute` is a `r:id`, which references a relationship of the part
+fn is_relationship_id(attribute: &OwnedAttribute) -> bool {
+    attribute.name.local_name == "id"
+        && attribute
+            .name
+            .namespace
+            .as_deref()
+            .is_some_and(|namespace| namespace.ends_with("/relationships"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::include_bytes;
 
-    const TEST_DOCX: &[u8] = include_bytes!("../../test_files/ooxml/docx_test.docx");
-    const TEST_PPTX: &[u8] = include_bytes!("../../test_files/ooxml/pptx_test.pptx");
-    const TEST_XLSX: &[u8] = include_bytes!("../../test_files/ooxml/xlsx_test.xlsx");
+    const TEST_DOCX: &[u8] = include_bytes!("../../../test_files/ooxml/docx_test.docx");
+    const TEST_PPTX: &[u8] = include_bytes!("../../../test_files/ooxml/pptx_test.pptx");
+    const TEST_XLSX: &[u8] = include_bytes!("../../../test_files/ooxml/xlsx_test.xlsx");
 
     #[test]
     pub fn scrape_docx_test() {
@@ -232,4 +312,13 @@ mod tests {
         links.sort();
         assert_eq!(links.len(), 50);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_DOCX).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<OoxmlLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
 }
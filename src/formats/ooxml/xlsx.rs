@@ -0,0 +1,444 @@
+use crate::formats::ooxml::{
+    is_relationship_id, read_relationship_targets, relationships_part, OoxmlScrapingError,
+};
+use crate::helpers::{find_attribute, find_urls};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Seek};
+use xml::reader::XmlEvent;
+use xml::EventReader;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+/// The workbook-part, that lists the sheets of a spreadsheet
+const WORKBOOK_PART: &str = "xl/workbook.xml";
+
+/// Scrapes all links from the sheets of a given xlsx-file
+///
+/// Unlike [`crate::formats::ooxml::scrape`] every link is reported with the sheet and cell it belongs to.
+/// The `r:id` of every `hyperlink` is resolved with the relationships of its sheet,
+/// and cells referencing the shared-strings table are searched for urls in their resolved text.
+pub fn scrape<R>(reader: R) -> Result<Vec<XlsxLink>, OoxmlScrapingError>
+where
+    R: Read + Seek,
+{
+    let mut archive = ZipArchive::new(reader)?;
+    let workbook_relationships = relationships_part(WORKBOOK_PART);
+    let shared_strings = match read_optional(&mut archive, &workbook_relationships, |data| {
+        read_relationship_targets(data, "sharedStrings")
+    })?
+    .and_then(|targets| targets.into_values().next())
+    {
+        Some(target) => read_optional(
+            &mut archive,
+            &resolve_target(WORKBOOK_PART, &target),
+            |data| read_shared_strings(data),
+        )?
+        .unwrap_or_default(),
+        None => vec![],
+    };
+    let sheet_targets = read_optional(&mut archive, &workbook_relationships, |data| {
+        read_relationship_targets(data, "worksheet")
+    })?
+    .unwrap_or_default();
+    let sheets = read_sheets(archive.by_name(WORKBOOK_PART)?)?;
+
+    let mut links = vec![];
+    for (sheet, id) in sheets {
+        let Some(target) = sheet_targets.get(&id) else {
+            continue;
+        };
+        let part = resolve_target(WORKBOOK_PART, target);
+        let hyperlinks = read_optional(&mut archive, &relationships_part(&part), |data| {
+            read_relationship_targets(data, "hyperlink")
+        })?
+        .unwrap_or_default();
+        scrape_from_sheet(
+            archive.by_name(&part)?,
+            &sheet,
+            &shared_strings,
+            &hyperlinks,
+            &mut links,
+        )?;
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<XlsxLink>, OoxmlScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<XlsxLink>, OoxmlScrapingError>);
+gen_scrape_with_options!(scrape(Read + Seek) -> Result<Vec<XlsxLink>, OoxmlScrapingError>);
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XlsxLink {
+    pub url: String,
+    /// The name of the sheet as shown in its tab
+    pub sheet: String,
+    /// The reference of the cell, like `B3`.
+    /// A hyperlink spanning several cells keeps its range, like `A1:C1`.
+    pub cell: String,
+    pub kind: XlsxLinkKind,
+}
+
+impl Link for XlsxLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    /// The (top-left) cell of the link
+    fn location(&self) -> Location {
+        match parse_cell_reference(&self.cell) {
+            Some((row, column)) => Location::Cell { row, column },
+            None => Location::Unknown,
+        }
+    }
+}
+
+impl Display for XlsxLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum XlsxLinkKind {
+    /// The target of a `hyperlink` of the sheet, resolved with the relationships of the sheet
+    Hyperlink,
+    /// The link is contained in the text of a cell
+    CellText,
+}
+
+/// Reads the part `name` with `read`, or returns `None` if the archive has no such part
+fn read_optional<R, T>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+    read: impl FnOnce(zip::read::ZipFile<'_>) -> Result<T, OoxmlScrapingError>,
+) -> Result<Option<T>, OoxmlScrapingError>
+where
+    R: Read + Seek,
+{
+    match archive.by_name(name) {
+        Ok(data) => read(data).map(Some),
+        Err(ZipError::FileNotFound) => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Resolves the `target` of a relationship of `part` to the name of the targeted part,
+/// e.g. `worksheets/sheet1.xml` of `xl/workbook.xml` to `xl/worksheets/sheet1.xml`
+fn resolve_target(part: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+    let mut segments: Vec<&str> = part.split('/').collect();
+    segments.pop();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// Reads the names of all sheets of the workbook together with the id of their relationship
+fn read_sheets(data: impl Read) -> Result<Vec<(String, String)>, OoxmlScrapingError> {
+    let mut sheets = vec![];
+    let mut parser = EventReader::new(data);
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } if name.local_name == "sheet" => {
+                let id = attributes.iter().find(|it| is_relationship_id(it));
+                if let (Some(name), Some(id)) = (find_attribute(&attributes, "name"), id) {
+                    sheets.push((name.to_string(), id.value.clone()));
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(sheets)
+}
+
+/// Reads the text of all entries of the shared-strings table, which cells reference by their index
+///
+/// The text of rich-text runs is joined, phonetic hints (`rPh`) are left out.
+fn read_shared_strings(data: impl Read) -> Result<Vec<String>, OoxmlScrapingError> {
+    let mut strings = vec![];
+    let mut parser = EventReader::new(data);
+    let mut in_text = false;
+    let mut in_phonetic = false;
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement { name, .. } => match name.local_name.as_str() {
+                "si" => strings.push(String::new()),
+                "rPh" => in_phonetic = true,
+                "t" => in_text = !in_phonetic,
+                _ => {}
+            },
+            XmlEvent::EndElement { name } => match name.local_name.as_str() {
+                "rPh" => in_phonetic = false,
+                "t" => in_text = false,
+                _ => {}
+            },
+            XmlEvent::Characters(text) | XmlEvent::Whitespace(text) if in_text => {
+                if let Some(string) = strings.last_mut() {
+                    string.push_str(&text);
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+/// Scrapes the links from the part of a sheet, `hyperlinks` are the hyperlink-relationships of this sheet
+fn scrape_from_sheet(
+    data: impl Read,
+    sheet: &str,
+    shared_strings: &[String],
+    hyperlinks: &HashMap<String, String>,
+    collector: &mut Vec<XlsxLink>,
+) -> Result<(), OoxmlScrapingError> {
+    let push = |collector: &mut Vec<XlsxLink>, url: &str, cell: &str, kind| {
+        collector.push(XlsxLink {
+            url: url.to_string(),
+            sheet: sheet.to_string(),
+            cell: cell.to_string(),
+            kind,
+        })
+    };
+
+    let mut parser = EventReader::new(data);
+    let mut cell = String::new();
+    let mut cell_type = String::new();
+    // The value of the current cell, for shared strings this is the index into the table
+    let mut value: Option<String> = None;
+    let mut in_value = false;
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => match name.local_name.as_str() {
+                "c" => {
+                    cell = find_attribute(&attributes, "r")
+                        .unwrap_or_default()
+                        .to_string();
+                    cell_type = find_attribute(&attributes, "t").unwrap_or("n").to_string();
+                    value = None;
+                }
+                "v" if matches!(cell_type.as_str(), "s" | "str") => in_value = true,
+                "t" if cell_type == "inlineStr" => in_value = true,
+                "hyperlink" => {
+                    let target = attributes
+                        .iter()
+                        .find(|it| is_relationship_id(it))
+                        .and_then(|it| hyperlinks.get(&it.value));
+                    if let (Some(target), Some(reference)) =
+                        (target, find_attribute(&attributes, "ref"))
+                    {
+                        push(collector, target, reference, XlsxLinkKind::Hyperlink);
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::Characters(text) | XmlEvent::Whitespace(text) if in_value => {
+                value.get_or_insert_with(String::new).push_str(&text);
+            }
+            XmlEvent::EndElement { name } => match name.local_name.as_str() {
+                "v" | "t" => in_value = false,
+                "c" => {
+                    let text = match (cell_type.as_str(), value.take()) {
+                        ("s", Some(index)) => index
+                            .trim()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| shared_strings.get(index))
+                            .cloned(),
+                        (_, value) => value,
+                    };
+                    for link in find_urls(text.as_deref().unwrap_or_default()) {
+                        push(collector, link.as_str(), &cell, XlsxLinkKind::CellText);
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Parses the (first) cell of a reference like `B3` or `A1:C1` to its row and column, both starting at 0
+fn parse_cell_reference(reference: &str) -> Option<(usize, usize)> {
+    let reference = reference.split(':').next()?;
+    let digits = reference.find(|c: char| c.is_ascii_digit())?;
+    let (letters, number) = reference.split_at(digits);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let column = letters.chars().try_fold(0usize, |column, letter| {
+        column
+            .checked_mul(26)?
+            .checked_add((letter.to_ascii_uppercase() as u8 - b'A') as usize + 1)
+    })?;
+    let row = number.parse::<usize>().ok()?.checked_sub(1)?;
+    Some((row, column - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    const TEST_XLSX: &[u8] = include_bytes!("../../../test_files/ooxml/xlsx_test.xlsx");
+
+    fn summary(links: &[XlsxLink]) -> Vec<(&str, &str, &str, XlsxLinkKind)> {
+        links
+            .iter()
+            .map(|it| {
+                (
+                    it.url.as_str(),
+                    it.sheet.as_str(),
+                    it.cell.as_str(),
+                    it.kind,
+                )
+            })
+            .collect_vec()
+    }
+
+    #[test]
+    fn scrape_xlsx_test() {
+        let links = scrape_from_slice(TEST_XLSX).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                (
+                    "https://plaintext.test.com",
+                    "Tabelle1",
+                    "A1",
+                    XlsxLinkKind::CellText
+                ),
+                (
+                    "https://hyperlink.test.com/",
+                    "Tabelle1",
+                    "A2",
+                    XlsxLinkKind::Hyperlink
+                ),
+            ]
+        );
+        assert_eq!(links[1].location(), Location::Cell { row: 1, column: 0 });
+    }
+
+    #[test]
+    fn scrape_shared_strings_and_ranges_test() {
+        let workbook = r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<sheets><sheet name="First" sheetId="1" r:id="rId1"/><sheet name="Second" sheetId="2" r:id="rId2"/></sheets></workbook>"#;
+        let workbook_relationships = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="/xl/worksheets/other.xml"/>
+<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings" Target="sharedStrings.xml"/>
+</Relationships>"#;
+        let shared_strings = r#"<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<si><t>no link</t></si>
+<si><r><t>See https://rich</t></r><r><t>.test.com</t></r><rPh><t>https://phonetic.test.com</t></rPh></si>
+</sst>"#;
+        let first = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<sheetData><row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="C1"><v>42</v></c></row></sheetData>
+<hyperlinks><hyperlink ref="AA10:AB12" r:id="rId1"/><hyperlink ref="A1" location="Second!A1"/></hyperlinks></worksheet>"#;
+        let first_relationships = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://range.test.com" TargetMode="External"/>
+</Relationships>"#;
+        let second = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<sheetData><row r="3"><c r="D3" t="inlineStr"><is><t>https://inline.test.com</t></is></c><c r="E3" t="str"><f>A1</f><v>https://formula.test.com</v></c></row></sheetData></worksheet>"#;
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in [
+            ("xl/workbook.xml", workbook),
+            ("xl/_rels/workbook.xml.rels", workbook_relationships),
+            ("xl/sharedStrings.xml", shared_strings),
+            ("xl/worksheets/sheet1.xml", first),
+            ("xl/worksheets/_rels/sheet1.xml.rels", first_relationships),
+            ("xl/worksheets/other.xml", second),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        let xlsx = zip.finish().unwrap().into_inner();
+
+        let links = scrape_from_slice(xlsx).unwrap();
+        assert_eq!(
+            summary(&links),
+            vec![
+                (
+                    "https://rich.test.com",
+                    "First",
+                    "B1",
+                    XlsxLinkKind::CellText
+                ),
+                (
+                    "https://range.test.com",
+                    "First",
+                    "AA10:AB12",
+                    XlsxLinkKind::Hyperlink
+                ),
+                (
+                    "https://inline.test.com",
+                    "Second",
+                    "D3",
+                    XlsxLinkKind::CellText
+                ),
+                (
+                    "https://formula.test.com",
+                    "Second",
+                    "E3",
+                    XlsxLinkKind::CellText
+                ),
+            ]
+        );
+        assert_eq!(links[1].location(), Location::Cell { row: 9, column: 26 });
+    }
+
+    #[test]
+    fn parse_cell_reference_test() {
+        assert_eq!(parse_cell_reference("A1"), Some((0, 0)));
+        assert_eq!(parse_cell_reference("Z3"), Some((2, 25)));
+        assert_eq!(parse_cell_reference("aa10:ab12"), Some((9, 26)));
+        assert_eq!(parse_cell_reference("XFD1048576"), Some((1048575, 16383)));
+        assert_eq!(parse_cell_reference("A0"), None);
+        assert_eq!(parse_cell_reference("12"), None);
+    }
+
+    #[test]
+    fn resolve_target_test() {
+        assert_eq!(
+            resolve_target("xl/workbook.xml", "worksheets/sheet1.xml"),
+            "xl/worksheets/sheet1.xml"
+        );
+        assert_eq!(
+            resolve_target("xl/worksheets/sheet1.xml", "../sharedStrings.xml"),
+            "xl/sharedStrings.xml"
+        );
+        assert_eq!(
+            resolve_target("xl/workbook.xml", "/xl/other.xml"),
+            "xl/other.xml"
+        );
+    }
+}
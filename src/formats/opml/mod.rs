@@ -0,0 +1,189 @@
+use crate::helpers::find_attribute;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+use xml::common::{Position, TextPosition};
+use xml::reader::XmlEvent;
+use xml::EventReader;
+
+/// Scrapes the feeds and websites of the `<outline>`-elements of an OPML-file, like the subscription list
+/// exported by a feed reader.
+///
+/// The `xmlUrl` of an outline is returned as [`OpmlLinkKind::FeedUrl`] and its `htmlUrl` as
+/// [`OpmlLinkKind::SiteUrl`], both with the title of the outline. The values are taken as is.
+/// All other urls, e.g. inside the `<head>`, are ignored.
+/// # Example
+/// ```
+/// use link_scraper::formats::opml::{scrape, OpmlLinkKind};
+/// let opml = r#"<opml version="2.0"><body><outline text="Blog" xmlUrl="https://example.com/feed.xml"/></body></opml>"#;
+/// let links = scrape(opml.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/feed.xml");
+/// assert_eq!(links[0].kind, OpmlLinkKind::FeedUrl);
+/// assert_eq!(links[0].outline_title.as_deref(), Some("Blog"));
+/// ```
+pub fn scrape<R>(reader: R) -> Result<Vec<OpmlLink>, OpmlScrapingError>
+where
+    R: Read,
+{
+    let mut parser = EventReader::new(reader);
+    let mut links = vec![];
+    let mut is_opml = None;
+
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                if !*is_opml.get_or_insert(name.local_name == "opml") {
+                    return Err(OpmlScrapingError::NotAnOpmlFileError);
+                }
+                if name.local_name != "outline" {
+                    continue;
+                }
+                let position = parser.position();
+                // `text` is required by OPML 2.0, but some exporters only write a `title`
+                let outline_title = find_attribute(&attributes, "text")
+                    .or_else(|| find_attribute(&attributes, "title"))
+                    .map(str::to_string);
+                for attribute in &attributes {
+                    let kind = match attribute.name.local_name.as_str() {
+                        "xmlUrl" => OpmlLinkKind::FeedUrl,
+                        "htmlUrl" => OpmlLinkKind::SiteUrl,
+                        _ => continue,
+                    };
+                    let url = attribute.value.trim();
+                    if !url.is_empty() {
+                        links.push(OpmlLink {
+                            url: url.to_string(),
+                            kind,
+                            outline_title: outline_title.clone(),
+                            location: position,
+                        });
+                    }
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+
+    if is_opml.is_none() {
+        return Err(OpmlScrapingError::NotAnOpmlFileError);
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<OpmlLink>, OpmlScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<OpmlLink>, OpmlScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<OpmlLink>, OpmlScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum OpmlScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    XmlReaderError(#[from] xml::reader::Error),
+    #[error("The root-element is no opml-element.")]
+    NotAnOpmlFileError,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpmlLink {
+    pub url: String,
+    pub kind: OpmlLinkKind,
+    /// The `text`-attribute of the outline containing the link, or its `title` if it has no `text`.
+    /// The feed- and site-url of an outline have the same title (and location).
+    pub outline_title: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
+    pub location: TextPosition,
+}
+
+impl Link for OpmlLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::TextPosition(self.location)
+    }
+}
+
+impl Display for OpmlLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OpmlLinkKind {
+    /// The feed of an outline<br/>
+    /// Example: `<outline text="Blog" xmlUrl="https://link.example.com/feed.xml"/>`
+    FeedUrl,
+    /// The website belonging to the feed of an outline<br/>
+    /// Example: `<outline text="Blog" htmlUrl="https://link.example.com"/>`
+    SiteUrl,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_OPML: &[u8] = include_bytes!("../../../test_files/opml/opml_test.opml");
+
+    #[test]
+    fn scrape_nested_outlines_test() {
+        let links = scrape(TEST_OPML).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, OpmlLinkKind, Option<&str>)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.kind, it.outline_title.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "https://blog.test.com/feed.xml",
+                    OpmlLinkKind::FeedUrl,
+                    Some("Test Blog")
+                ),
+                (
+                    "https://blog.test.com",
+                    OpmlLinkKind::SiteUrl,
+                    Some("Test Blog")
+                ),
+                (
+                    "https://news.test.com/rss",
+                    OpmlLinkKind::FeedUrl,
+                    Some("Daily News")
+                ),
+                ("https://untitled.test.com", OpmlLinkKind::SiteUrl, None),
+            ]
+        );
+        assert_eq!(links[0].location, links[1].location);
+    }
+
+    #[test]
+    fn fail_on_other_xml_test() {
+        let result = scrape(&b"<rss><channel><link>https://a.test.com</link></channel></rss>"[..]);
+        assert!(matches!(result, Err(OpmlScrapingError::NotAnOpmlFileError)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_OPML).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<OpmlLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
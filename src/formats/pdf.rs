@@ -1,8 +1,9 @@
-use crate::gen_scrape_from_file;
 use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_with_options};
 use mupdf::{Document, Page};
 use std::fmt::{Display, Formatter};
-use std::io::{Read};
+use std::io::Read;
 use std::string::String;
 use thiserror::Error;
 
@@ -21,6 +22,7 @@ where
     scrape_from_slice(buffer)
 }
 gen_scrape_from_file!(scrape_from_slice(AsRef<[u8]>) -> Result<Vec<PdfLink>, PdfScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<PdfLink>, PdfScrapingError>);
 
 /// Takes a PDF as a byte slice and scrapes all links from it.
 ///
@@ -47,12 +49,27 @@ pub enum PdfScrapingError {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PdfLink {
     pub url: String,
     pub location: PdfLinkLocation,
     pub kind: PdfLinkKind,
 }
 
+impl Link for PdfLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Page(self.location.page)
+    }
+}
+
 impl Display for PdfLink {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.url)
@@ -60,13 +77,18 @@ impl Display for PdfLink {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PdfLinkLocation {
+    /// The page the link was found on, starting at 1
     pub page: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PdfLinkKind {
+    /// A url found in the text of a page
     PlainText,
+    /// The target of a link annotation (`/A /URI`), i.e. a clickable link
     Hyperlink,
 }
 
@@ -209,4 +231,13 @@ mod tests {
         let error = bytes_to_pdf(NOT_A_PDF);
         assert!(error.is_err())
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_PDF).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<PdfLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
 }
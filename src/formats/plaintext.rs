@@ -1,36 +1,374 @@
 use crate::helpers::find_urls;
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use crate::link::{Limited, Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
 use std::fmt::{Display, Formatter};
 use std::io::BufRead;
 use thiserror::Error;
 
-pub fn scrape<R>(mut buf_reader: R) -> Result<Vec<TextFileLink>, TextFileScrapingError>
+/// Scrapes all links from a text, line by line.
+///
+/// The text is read as UTF-8, unless it starts with a UTF-16 byte order mark.
+/// A leading byte order mark is not part of the text, so all positions are relative to the text after it.
+/// For UTF-16 texts the byte-offsets refer to the text after decoding it to UTF-8.
+pub fn scrape<R>(buf_reader: R) -> Result<Vec<TextFileLink>, TextFileScrapingError>
+where
+    R: BufRead,
+{
+    scrape_internal(buf_reader, &LineOptions::default(), None).map(|limited| limited.links)
+}
+gen_scrape_from_file!(scrape(Read)-> Result<Vec<TextFileLink>, TextFileScrapingError>);
+gen_scrape_from_slice!(scrape(Read)-> Result<Vec<TextFileLink>, TextFileScrapingError>);
+gen_scrape_with_options!(scrape(BufRead) -> Result<Vec<TextFileLink>, TextFileScrapingError>);
+
+/// Like [`scrape`], but stops reading the text as soon as more than `max_links` links were found.
+/// # Example
+/// ```
+/// use link_scraper::formats::plaintext::scrape_limited;
+/// let limited = scrape_limited("https://a.com https://b.com\nhttps://c.com".as_bytes(), 2).unwrap();
+/// assert_eq!(limited.links.len(), 2);
+/// assert!(limited.truncated);
+/// ```
+pub fn scrape_limited<R>(
+    buf_reader: R,
+    max_links: usize,
+) -> Result<Limited<TextFileLink>, TextFileScrapingError>
+where
+    R: BufRead,
+{
+    let options = LineOptions {
+        max_links: Some(max_links),
+        ..Default::default()
+    };
+    scrape_internal(buf_reader, &options, None)
+}
+
+/// Returns the first link [`scrape`] would return, e.g. to check if a text contains any link.
+///
+/// Reading stops after the line containing the first link.
+/// # Example
+/// ```
+/// use link_scraper::formats::plaintext::find_first_link;
+/// let link = find_first_link("no link\nhttps://a.com https://b.com".as_bytes()).unwrap();
+/// assert_eq!(link.unwrap().location.line, 2);
+/// assert_eq!(find_first_link("no link".as_bytes()).unwrap(), None);
+/// ```
+pub fn find_first_link<R>(buf_reader: R) -> Result<Option<TextFileLink>, TextFileScrapingError>
+where
+    R: BufRead,
+{
+    let options = LineOptions {
+        max_links: Some(1),
+        stop_at_first_link: true,
+        ..Default::default()
+    };
+    scrape_internal(buf_reader, &options, None).map(|limited| limited.links.into_iter().next())
+}
+
+/// Counts the links [`scrape`] would return, without building them.
+/// # Example
+/// ```
+/// use link_scraper::formats::plaintext::count_links;
+/// assert_eq!(count_links("https://a.com https://b.com\nhttps://c.com".as_bytes()).unwrap(), 3);
+/// ```
+pub fn count_links<R>(buf_reader: R) -> Result<usize, TextFileScrapingError>
+where
+    R: BufRead,
+{
+    with_decoded_text(buf_reader, |text| {
+        let mut lines = Lines {
+            reader: text,
+            pending: None,
+        };
+        let mut count = 0;
+        while let Some(line) = lines.next()? {
+            count += find_urls(&line).len();
+        }
+        Ok(count)
+    })
+}
+
+/// Like [`scrape`], but reassembles urls that were hard-wrapped across two or more lines,
+/// like in the bodies of e-mails.
+///
+/// To avoid gluing unrelated lines together, a line is only joined with the next one, if
+/// - it ends with an url without any whitespace or punctuation before the line break,
+/// - that url ends with one of [`WRAP_CHARACTERS`] (like `/`), where a wrapped url is likely to continue,
+/// - and the next line directly continues with characters of an url and does not start a new url.
+///
+/// A reassembled link is reported at the line and position where it starts. Its [`TextFileLinkLocation::end`]
+/// is the end of the reassembled url as if it was written on the line it starts on.
+/// # Example
+/// ```
+/// use link_scraper::formats::plaintext::scrape_unwrapped;
+/// let mail = "Read the guide at https://example.com/docs/\nsetup/install.html before Monday.\n";
+/// let links = scrape_unwrapped(mail.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/docs/setup/install.html");
+/// assert_eq!(links[0].location.line, 1);
+/// ```
+pub fn scrape_unwrapped<R>(buf_reader: R) -> Result<Vec<TextFileLink>, TextFileScrapingError>
+where
+    R: BufRead,
+{
+    let options = LineOptions {
+        unwrap_lines: true,
+        ..Default::default()
+    };
+    scrape_internal(buf_reader, &options, None).map(|limited| limited.links)
+}
+
+/// Like [`scrape`], but calls `callback` with the [`Progress`] whenever another `interval` bytes were read,
+/// e.g. to show a progress bar while scraping a large log-file.
+///
+/// The text is read line by line, so `callback` is called after the first line reaching or passing each
+/// multiple of `interval` (at least 1). A line passing several multiples at once only triggers one call.
+/// # Example
+/// ```
+/// use link_scraper::formats::plaintext::scrape_with_progress;
+/// let text = "https://a.com\nhttps://b.com\n".repeat(100);
+/// let mut reports = vec![];
+/// let links = scrape_with_progress(text.as_bytes(), 1000, |progress| reports.push(progress)).unwrap();
+/// assert_eq!(links.len(), 200);
+/// assert_eq!(reports.len(), 2);
+/// assert_eq!(reports[0].byte_offset, 1008);
+/// assert_eq!(reports[0].links, 72);
+/// ```
+pub fn scrape_with_progress<R, F>(
+    buf_reader: R,
+    interval: usize,
+    mut callback: F,
+) -> Result<Vec<TextFileLink>, TextFileScrapingError>
+where
+    R: BufRead,
+    F: FnMut(Progress),
+{
+    let mut reporter = ProgressReporter {
+        interval: interval.max(1),
+        next_report: interval.max(1),
+        callback: &mut callback,
+    };
+    scrape_internal(buf_reader, &LineOptions::default(), Some(&mut reporter))
+        .map(|limited| limited.links)
+}
+
+/// How far [`scrape_with_progress`] got
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// The number of bytes of the text read so far, like [`TextFileLinkLocation::byte_offset`]
+    pub byte_offset: usize,
+    /// The number of links found so far
+    pub links: usize,
+}
+
+/// Calls the callback of [`scrape_with_progress`]
+struct ProgressReporter<'a> {
+    interval: usize,
+    /// The byte-offset at which the callback is called next
+    next_report: usize,
+    callback: &'a mut dyn FnMut(Progress),
+}
+
+impl ProgressReporter<'_> {
+    /// Calls the callback, if `byte_offset` reached the next multiple of the interval
+    fn update(&mut self, byte_offset: usize, links: usize) {
+        if byte_offset < self.next_report {
+            return;
+        }
+        (self.callback)(Progress { byte_offset, links });
+        self.next_report = (byte_offset / self.interval + 1) * self.interval;
+    }
+}
+
+/// The characters at the end of a line, after which [`scrape_unwrapped`] expects a wrapped url to continue
+pub const WRAP_CHARACTERS: [char; 8] = ['/', '-', '_', '&', '=', '#', '%', '~'];
+
+/// How [`scrape_lines`] reads the lines of a text
+#[derive(Debug, Default, Copy, Clone)]
+struct LineOptions {
+    /// Stop reading as soon as more than this many links were found
+    max_links: Option<usize>,
+    /// Join hard-wrapped urls, see [`scrape_unwrapped`]
+    unwrap_lines: bool,
+    /// Stop reading after the first line containing a link, see [`find_first_link`]
+    stop_at_first_link: bool,
+}
+
+fn scrape_internal<R>(
+    buf_reader: R,
+    options: &LineOptions,
+    progress: Option<&mut ProgressReporter>,
+) -> Result<Limited<TextFileLink>, TextFileScrapingError>
+where
+    R: BufRead,
+{
+    with_decoded_text(buf_reader, |text| scrape_lines(text, options, progress))
+}
+
+/// Passes the text read from `buf_reader` to `f` as UTF-8, without its byte order mark
+fn with_decoded_text<R, T>(
+    mut buf_reader: R,
+    f: impl FnOnce(&mut dyn BufRead) -> Result<T, TextFileScrapingError>,
+) -> Result<T, TextFileScrapingError>
+where
+    R: BufRead,
+{
+    let head = buf_reader.fill_buf()?;
+    if head.starts_with(&UTF8_BOM) {
+        buf_reader.consume(UTF8_BOM.len());
+        return f(&mut buf_reader);
+    }
+
+    let decode_unit: fn([u8; 2]) -> u16 = if head.starts_with(&UTF16_LE_BOM) {
+        u16::from_le_bytes
+    } else if head.starts_with(&UTF16_BE_BOM) {
+        u16::from_be_bytes
+    } else {
+        return f(&mut buf_reader);
+    };
+    buf_reader.consume(UTF16_LE_BOM.len());
+    let mut bytes = vec![];
+    buf_reader.read_to_end(&mut bytes)?;
+    let units = bytes.chunks_exact(2);
+    let incomplete_unit = !units.remainder().is_empty();
+    let mut text: String = char::decode_utf16(units.map(|unit| decode_unit([unit[0], unit[1]])))
+        .map(|character| character.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    if incomplete_unit {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    f(&mut text.as_bytes())
+}
+
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BOM: [u8; 2] = [0xFE, 0xFF];
+
+/// Scrapes the links from an UTF-8 text, line by line, until more than [`LineOptions::max_links`] links were found.
+///
+/// Reports the progress after every line to `progress`, if set.
+fn scrape_lines<R>(
+    buf_reader: R,
+    options: &LineOptions,
+    mut progress: Option<&mut ProgressReporter>,
+) -> Result<Limited<TextFileLink>, TextFileScrapingError>
 where
     R: BufRead,
 {
     let mut collector: Vec<TextFileLink> = vec![];
-    let mut contents = String::new();
-    let mut line_result = buf_reader.read_line(&mut contents)?;
+    let mut lines = Lines {
+        reader: buf_reader,
+        pending: None,
+    };
     let mut current_line = 1;
-    while line_result > 0 {
-        find_urls(&contents).iter().for_each(|link| {
+    let mut line_offset = 0;
+    while let Some(mut contents) = lines.next()? {
+        // The lines joined into `contents`, only more than one if they were unwrapped
+        let mut segments = vec![Segment {
+            line: current_line,
+            start: 0,
+            byte_offset: line_offset,
+        }];
+        line_offset += contents.len();
+        current_line += 1;
+        while options.unwrap_lines && ends_with_wrapped_url(&contents) {
+            let Some(next) = lines.next()? else { break };
+            if !continues_wrapped_url(&next) {
+                lines.pending = Some(next);
+                break;
+            }
+            contents.truncate(contents.trim_end_matches(['\r', '\n']).len());
+            segments.push(Segment {
+                line: current_line,
+                start: contents.len(),
+                byte_offset: line_offset,
+            });
+            line_offset += next.len();
+            current_line += 1;
+            contents.push_str(&next);
+        }
+
+        for link in find_urls(&contents) {
+            if options
+                .max_links
+                .is_some_and(|max_links| collector.len() >= max_links)
+            {
+                return Ok(Limited {
+                    links: collector,
+                    truncated: true,
+                });
+            }
+            let segment = segments
+                .iter()
+                .rev()
+                .find(|segment| segment.start <= link.start())
+                .unwrap_or(&segments[0]);
+            let pos = link.start() - segment.start;
             collector.push(TextFileLink {
                 url: link.as_str().to_string(),
                 location: TextFileLinkLocation {
-                    line: current_line,
-                    pos: link.start(),
+                    line: segment.line,
+                    pos,
+                    end: link.end() - segment.start,
+                    byte_offset: segment.byte_offset + pos,
+                    column: contents[segment.start..link.start()].chars().count(),
                 },
-            })
-        });
+            });
+        }
+        if let Some(progress) = &mut progress {
+            progress.update(line_offset, collector.len());
+        }
+        if options.stop_at_first_link && !collector.is_empty() {
+            break;
+        }
+    }
+    Ok(Limited {
+        links: collector,
+        truncated: false,
+    })
+}
 
-        contents.clear();
-        line_result = buf_reader.read_line(&mut contents)?;
-        current_line += 1;
+/// Reads the lines of a text, with a line that was read too far put back into `pending`
+struct Lines<R> {
+    reader: R,
+    pending: Option<String>,
+}
+
+impl<R: BufRead> Lines<R> {
+    /// The next line including its line break, if any
+    fn next(&mut self) -> std::io::Result<Option<String>> {
+        if let Some(line) = self.pending.take() {
+            return Ok(Some(line));
+        }
+        let mut line = String::new();
+        let read = self.reader.read_line(&mut line)?;
+        Ok((read > 0).then_some(line))
     }
-    Ok(collector)
 }
-gen_scrape_from_file!(scrape(Read)-> Result<Vec<TextFileLink>, TextFileScrapingError>);
-gen_scrape_from_slice!(scrape(Read)-> Result<Vec<TextFileLink>, TextFileScrapingError>);
+
+/// A line joined into the text scanned by [`scrape_lines`]
+struct Segment {
+    /// The line, starting at 1
+    line: usize,
+    /// The byte-offset of the line inside the joined text
+    start: usize,
+    /// The byte-offset of the line from the start of the document
+    byte_offset: usize,
+}
+
+/// Checks if `contents` ends with a line break directly after an url, that looks like it was wrapped
+fn ends_with_wrapped_url(contents: &str) -> bool {
+    let line = contents.trim_end_matches(['\r', '\n']);
+    line.len() < contents.len()
+        && find_urls(line)
+            .last()
+            .is_some_and(|url| url.end() == line.len() && url.as_str().ends_with(WRAP_CHARACTERS))
+}
+
+/// Checks if `line` starts with the rest of a wrapped url instead of text or another url
+fn continues_wrapped_url(line: &str) -> bool {
+    let token = line.split_whitespace().next().unwrap_or_default();
+    line.starts_with(|c: char| c.is_alphanumeric() || WRAP_CHARACTERS.contains(&c))
+        && !token.contains("://")
+}
 
 #[derive(Error, Debug)]
 pub enum TextFileScrapingError {
@@ -38,22 +376,52 @@ pub enum TextFileScrapingError {
     IoError(#[from] std::io::Error),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextFileLink {
     pub url: String,
     pub location: TextFileLinkLocation,
 }
 
+impl Link for TextFileLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::LineAndPos {
+            line: self.location.line,
+            pos: self.location.pos,
+            end: self.location.end,
+            byte_offset: self.location.byte_offset,
+            column: self.location.column,
+        }
+    }
+}
+
 impl Display for TextFileLink {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.url)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextFileLinkLocation {
+    /// The line of the link, starting at 1
     pub line: usize,
+    /// The byte-offset of the link inside its line
     pub pos: usize,
+    /// The byte-offset of the end of the link inside its line (exclusive)
+    pub end: usize,
+    /// The byte-offset of the link from the start of the file
+    pub byte_offset: usize,
+    /// The column of the link inside its line, counted in unicode scalar values and starting at 0
+    pub column: usize,
 }
 
 #[cfg(test)]
@@ -61,6 +429,23 @@ mod tests {
     use super::*;
 
     const TEST_XML: &[u8] = include_bytes!("../../test_files/xml/xml_test.xml");
+    const TEST_PLAINTEXT: &[u8] = include_bytes!("../../test_files/plaintext/plaintext_test.txt");
+
+    #[test]
+    fn count_links_test() {
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(
+                "https://a.test.com\nhttps://b.test.com"
+                    .encode_utf16()
+                    .flat_map(u16::to_le_bytes),
+            )
+            .collect();
+        for text in [TEST_XML, TEST_PLAINTEXT, &utf16] {
+            assert_eq!(count_links(text).unwrap(), scrape(text).unwrap().len());
+        }
+        assert_eq!(count_links(&utf16[..]).unwrap(), 2);
+    }
 
     #[test]
     fn scrape_test() {
@@ -76,4 +461,220 @@ mod tests {
         assert!(links.iter().any(|it| it.url == "https://cdata.test.com"));
         assert!(links.iter().any(|it| it.url == "https://ns.test.com"));
     }
+
+    #[test]
+    fn find_first_link_test() {
+        /// Fails when the text is read past the part before it
+        struct FailingReader;
+
+        impl std::io::Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("read too far"))
+            }
+        }
+
+        let text = "first line\nsee https://a.test.com and https://b.test.com\n";
+        let reader = std::io::BufReader::new(std::io::Read::chain(text.as_bytes(), FailingReader));
+        let link = find_first_link(reader).unwrap().unwrap();
+        assert_eq!(link, scrape(text.as_bytes()).unwrap()[0]);
+        assert_eq!(link.url, "https://a.test.com");
+        assert_eq!(link.location.line, 2);
+
+        let reader = std::io::BufReader::new(std::io::Read::chain(text.as_bytes(), FailingReader));
+        assert!(scrape(reader).is_err());
+        assert_eq!(find_first_link("no links".as_bytes()).unwrap(), None);
+    }
+
+    #[test]
+    fn scrape_with_progress_test() {
+        // 100 lines of 20 bytes with one link each
+        let text = "https://test.com/ab\n".repeat(100);
+        assert_eq!(text.len(), 2000);
+        let mut reports = vec![];
+        let links =
+            scrape_with_progress(text.as_bytes(), 500, |progress| reports.push(progress)).unwrap();
+        assert_eq!(links.len(), 100);
+        let expected: Vec<Progress> = [500, 1000, 1500, 2000]
+            .into_iter()
+            .map(|byte_offset| Progress {
+                byte_offset,
+                links: byte_offset / 20,
+            })
+            .collect();
+        assert_eq!(reports, expected);
+
+        // Every line passes two or three multiples of the interval, but only triggers one call
+        let mut reports = vec![];
+        scrape_with_progress(text.as_bytes(), 7, |progress| reports.push(progress)).unwrap();
+        assert_eq!(reports.len(), 100);
+        let mut count = 0;
+        scrape_with_progress(&b""[..], 7, |_| count += 1).unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn scrape_limited_test() {
+        let text = (1..=50)
+            .map(|it| format!("https://{it}.test.com and https://{it}.test.com/second\n"))
+            .collect::<String>();
+        assert_eq!(scrape(text.as_bytes()).unwrap().len(), 100);
+
+        let limited = scrape_limited(text.as_bytes(), 10).unwrap();
+        assert!(limited.truncated);
+        assert_eq!(limited.links.len(), 10);
+        assert_eq!(limited.links[..], scrape(text.as_bytes()).unwrap()[..10]);
+
+        let limited = scrape_limited(text.as_bytes(), 100).unwrap();
+        assert!(!limited.truncated);
+        assert_eq!(limited.links.len(), 100);
+    }
+
+    #[test]
+    fn scrape_from_slice_test() {
+        let links = scrape_from_slice(TEST_PLAINTEXT).unwrap();
+        println!("{:?}", links);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://plaintext.test.com");
+        assert_eq!(links[0].location.line, 2);
+        assert_eq!(links, scrape(TEST_PLAINTEXT).unwrap());
+    }
+
+    #[test]
+    fn scrape_from_file_located_test() {
+        let path = std::path::Path::new("test_files/plaintext/plaintext_test.txt");
+        let links = scrape_from_file_located(path).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].path, path);
+        assert_eq!(links[0].url(), "https://plaintext.test.com");
+        assert_eq!(links[0].link, scrape_from_file(path).unwrap()[0]);
+    }
+
+    #[test]
+    fn scrape_location_test() {
+        let text = "first line\nhttps://first.test.com ä https://second.test.com\n".as_bytes();
+        let links = scrape(text).unwrap();
+        println!("{:?}", links);
+        assert_eq!(links.len(), 2);
+
+        let first = &links[0].location;
+        assert_eq!(first.line, 2);
+        assert_eq!(first.pos, 0);
+        assert_eq!(first.byte_offset, 11);
+        assert_eq!(first.column, 0);
+
+        assert_eq!(first.end - first.pos, links[0].url.len());
+
+        let second = &links[1].location;
+        assert_eq!(second.line, 2);
+        assert_eq!(second.pos, 26);
+        assert_eq!(second.byte_offset, 37);
+        assert_eq!(second.column, 25);
+    }
+
+    #[test]
+    fn scrape_from_slice_line_numbers_test() {
+        let text = b"https://line-one.test.com\nno link here\nsee https://line-three.test.com\n";
+        let links = scrape_from_slice(text).unwrap();
+        let found: Vec<(&str, usize)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.location.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://line-one.test.com", 1),
+                ("https://line-three.test.com", 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_unwrapped_test() {
+        let text = "Hello,\nthe docs are at https://docs.test.com/guides/\ninstall/linux.html and\nhttps://other.test.com\n";
+        let links = scrape_unwrapped(text.as_bytes()).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, usize, usize)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.location.line, it.location.byte_offset))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://docs.test.com/guides/install/linux.html", 2, 23),
+                ("https://other.test.com", 4, 76),
+            ]
+        );
+        assert_eq!(
+            scrape(text.as_bytes()).unwrap()[0].url,
+            "https://docs.test.com/guides/"
+        );
+    }
+
+    #[test]
+    fn scrape_unwrapped_keeps_unrelated_lines_test() {
+        let text = "See https://a.test.com/\n\nThanks\nhttps://b.test.com/x\n  indented\nhttps://c.test.com/-\nhttps://d.test.com\n";
+        let urls: Vec<String> = scrape_unwrapped(text.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|it| it.url)
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://a.test.com/",
+                "https://b.test.com/x",
+                "https://c.test.com/-",
+                "https://d.test.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_utf8_with_bom_test() {
+        let text = b"\xEF\xBB\xBFhttps://bom.test.com\nhttps://second.test.com";
+        let links = scrape(&text[..]).unwrap();
+        assert_eq!(links[0].url, "https://bom.test.com");
+        assert_eq!(links[0].location.pos, 0);
+        assert_eq!(links[0].location.byte_offset, 0);
+        assert_eq!(
+            links[1].location.byte_offset,
+            "https://bom.test.com\n".len()
+        );
+    }
+
+    #[test]
+    fn scrape_utf16_test() {
+        let text = "\u{FEFF}first line\r\nsee ä https://utf16.test.com/path now";
+        let little_endian: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let big_endian: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        for bytes in [little_endian, big_endian] {
+            let links = scrape_from_slice(bytes).unwrap();
+            println!("{:?}", links);
+            assert_eq!(links.len(), 1);
+            assert_eq!(links[0].url, "https://utf16.test.com/path");
+            let location = &links[0].location;
+            assert_eq!(location.line, 2);
+            assert_eq!(location.pos, "see ä ".len());
+            assert_eq!(location.column, 6);
+            assert_eq!(location.byte_offset, "first line\r\nsee ä ".len());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_XML).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<TextFileLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+
+    #[test]
+    fn scrape_end_offset_test() {
+        let links = scrape(TEST_XML).unwrap();
+        assert!(!links.is_empty());
+        for link in links {
+            assert_eq!(link.location.end - link.location.pos, link.url.len());
+        }
+    }
 }
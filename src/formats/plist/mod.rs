@@ -0,0 +1,179 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::location::SourceLocation;
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use plist::Value;
+use std::fmt::{Display, Formatter, Write};
+use std::io::{Cursor, Read};
+use thiserror::Error;
+
+/// Scrapes links from all string values of a property list, like the `Info.plist` of an app
+/// or a preferences-file.
+///
+/// Both the XML- and the binary encoding are supported, the encoding is detected from the content.
+/// Keys and data values are not scraped. The links are ordered like the values inside the document.
+/// # Example
+/// ```
+/// use link_scraper::formats::plist::scrape;
+/// let plist = r#"<plist version="1.0"><dict><key>SUFeedURL</key><string>https://example.com/appcast.xml</string></dict></plist>"#;
+/// let links = scrape(plist.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/appcast.xml");
+/// assert_eq!(links[0].key_path, "SUFeedURL");
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<PlistLink>, PlistScrapingError>
+where
+    R: Read,
+{
+    // The encoding is detected by seeking back to the start, which not every reader supports
+    let mut content = vec![];
+    reader.read_to_end(&mut content)?;
+
+    let value = Value::from_reader(Cursor::new(content))?;
+    let mut links = vec![];
+    scrape_value(&value, &mut String::new(), &mut links);
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<PlistLink>, PlistScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<PlistLink>, PlistScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<PlistLink>, PlistScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum PlistScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    PlistError(#[from] plist::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlistLink {
+    pub url: String,
+    /// The keys and indices leading to the value containing the link, like `CFBundleURLTypes[0].CFBundleURLName`.
+    /// Keys that are not made of letters, digits, `_` and `-` are quoted, like `["com.example.homepage"]`.
+    /// The path of a string at the root of the property list is empty.
+    pub key_path: String,
+}
+
+impl Link for PlistLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Unknown
+    }
+
+    fn source_location(&self) -> SourceLocation {
+        SourceLocation::Pointer(self.key_path.clone())
+    }
+}
+
+impl Display for PlistLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+fn scrape_value(value: &Value, path: &mut String, links: &mut Vec<PlistLink>) {
+    match value {
+        Value::String(string) => {
+            links.extend(find_urls(string).iter().map(|url| PlistLink {
+                url: url.as_str().to_string(),
+                key_path: path.clone(),
+            }));
+        }
+        Value::Array(array) => {
+            for (index, item) in array.iter().enumerate() {
+                let path_length = path.len();
+                let _ = write!(path, "[{index}]");
+                scrape_value(item, path, links);
+                path.truncate(path_length);
+            }
+        }
+        Value::Dictionary(dictionary) => {
+            for (key, item) in dictionary {
+                let path_length = path.len();
+                push_key(path, key);
+                scrape_value(item, path, links);
+                path.truncate(path_length);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Appends `key` to `path`, quoted if it is not made of letters, digits, `_` and `-`
+fn push_key(path: &mut String, key: &str) {
+    let is_plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    if !is_plain {
+        let _ = write!(path, "[{:?}]", key);
+    } else if path.is_empty() {
+        path.push_str(key);
+    } else {
+        let _ = write!(path, ".{key}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PLIST: &[u8] = include_bytes!("../../../test_files/plist/plist_test.plist");
+    const TEST_BINARY_PLIST: &[u8] =
+        include_bytes!("../../../test_files/plist/plist_binary_test.plist");
+
+    fn summary(links: &[PlistLink]) -> Vec<(&str, &str)> {
+        links
+            .iter()
+            .map(|it| (it.url.as_str(), it.key_path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn scrape_xml_plist_test() {
+        let links = scrape(TEST_PLIST).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://updates.test.com/appcast.xml", "SUFeedURL"),
+                ("https://legal.test.com/terms", "NSHumanReadableCopyright"),
+                ("https://legal.test.com/privacy", "NSHumanReadableCopyright"),
+                ("https://www.test.com", r#"["com.test.homepage"]"#),
+                ("https://mirror1.test.com", "Mirrors[0]"),
+                ("https://mirror2.test.com", "Mirrors[2]"),
+                ("https://support.test.com/help", "Nested.Support.Url"),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_binary_plist_test() {
+        assert!(TEST_BINARY_PLIST.starts_with(b"bplist00"));
+        let links = scrape(TEST_BINARY_PLIST).unwrap();
+        assert_eq!(links, scrape(TEST_PLIST).unwrap());
+    }
+
+    #[test]
+    fn fail_on_invalid_plist_test() {
+        let result = scrape(&b"bplist00 https://a.test.com"[..]);
+        assert!(matches!(result, Err(PlistScrapingError::PlistError(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_PLIST).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<PlistLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -0,0 +1,409 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, Read};
+use std::iter::Peekable;
+use std::str::Chars;
+use thiserror::Error;
+
+/// Scrapes the targets of all `HYPERLINK`-fields and the urls inside the text of a rtf-document.
+///
+/// Reads the whole stream before processing the contents and converts it to str.
+/// Use [`scrape_from_string`] to omit the [`BufRead`].
+pub fn scrape<R>(mut reader: R) -> Result<Vec<RtfLink>, RtfScrapingError>
+where
+    R: BufRead,
+{
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    scrape_from_slice(buffer)
+}
+gen_scrape_with_options!(scrape(BufRead) -> Result<Vec<RtfLink>, RtfScrapingError>);
+
+/// Scrapes the targets of all `HYPERLINK`-fields and the urls inside the text of a rtf-document.
+///
+/// The escapes of the document (like `\'e9` or `\u8364?`) are decoded before the text is scanned for urls,
+/// `\'xx` is read as latin-1. The text of comments (`\annotation`) is scanned as well,
+/// the tables of fonts, colors and styles, the document information, pictures and
+/// all other ignorable destinations (`{\*\...}`) are not.
+/// # Example
+/// ```
+/// use link_scraper::formats::rtf::{scrape_from_string, RtfLinkKind};
+/// let rtf = r#"{\rtf1{\field{\*\fldinst HYPERLINK "https://example.com/caf\'e9"}{\fldrslt Link}}}"#;
+/// let links = scrape_from_string(rtf).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/café");
+/// assert_eq!(links[0].kind, RtfLinkKind::Field);
+/// ```
+pub fn scrape_from_string<S>(s: S) -> Result<Vec<RtfLink>, RtfScrapingError>
+where
+    S: AsRef<str>,
+{
+    let document = RtfDocument::parse(s.as_ref());
+    let mut fields = document.fields.into_iter().peekable();
+    let mut links = vec![];
+    for url in find_urls(&document.text) {
+        // Keeps the links in the order of the document
+        while let Some((_, field)) = fields.next_if(|(offset, _)| *offset <= url.start()) {
+            links.push(RtfLink::new(field, RtfLinkKind::Field));
+        }
+        links.push(RtfLink::new(url.as_str().to_string(), RtfLinkKind::Text));
+    }
+    links.extend(fields.map(|(_, field)| RtfLink::new(field, RtfLinkKind::Field)));
+    Ok(links)
+}
+
+/// Like [`scrape_from_string`].
+///
+/// Returns an error if `bytes` is not valid in UTF-8
+pub fn scrape_from_slice<T>(bytes: T) -> Result<Vec<RtfLink>, RtfScrapingError>
+where
+    T: AsRef<[u8]>,
+{
+    scrape_from_string(std::str::from_utf8(bytes.as_ref())?)
+}
+
+gen_scrape_from_file!(scrape_from_slice(AsRef<[u8]>) -> Result<Vec<RtfLink>, RtfScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum RtfScrapingError {
+    #[error(transparent)]
+    Utf8Error(#[from] std::str::Utf8Error),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RtfLink {
+    pub url: String,
+    pub kind: RtfLinkKind,
+}
+
+impl RtfLink {
+    fn new(url: String, kind: RtfLinkKind) -> Self {
+        Self { url, kind }
+    }
+}
+
+impl Link for RtfLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Unknown
+    }
+}
+
+impl Display for RtfLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RtfLinkKind {
+    /// The target of a `HYPERLINK`-field, taken as is even if it is relative<br/>
+    /// Example: `{\field{\*\fldinst HYPERLINK "https://link.example.com"}{\fldrslt Link}}`
+    Field,
+    /// The link is inside the text of the document or of a comment<br/>
+    /// Example: `{\pard Just a text with a link to https://link.example.com\par}`
+    Text,
+}
+
+/// Where the text of a group goes
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Destination {
+    Text,
+    FieldInstruction,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Group {
+    destination: Destination,
+    /// The number of fallback-characters following a `\uN`, set by `\ucN`
+    unicode_fallback: usize,
+}
+
+/// The decoded text of a rtf-document and the targets of its `HYPERLINK`-fields
+#[derive(Debug, Default)]
+struct RtfDocument {
+    text: String,
+    /// The targets together with the length of the text before their field
+    fields: Vec<(usize, String)>,
+    instruction: String,
+    /// The number of characters still to skip after a `\uN`
+    fallback: usize,
+    high_surrogate: Option<u16>,
+}
+
+impl RtfDocument {
+    fn parse(rtf: &str) -> Self {
+        let mut document = Self::default();
+        let mut groups = vec![Group {
+            destination: Destination::Text,
+            unicode_fallback: 1,
+        }];
+        // Whether nothing but `\*` followed the last `{`, so the next control word may start a destination
+        let mut at_group_start = false;
+        let mut ignorable = false;
+
+        let mut chars = rtf.chars().peekable();
+        while let Some(c) = chars.next() {
+            let group = *groups.last().expect("the document group is never closed");
+            match c {
+                '{' => {
+                    groups.push(group);
+                    at_group_start = true;
+                    document.fallback = 0;
+                    continue;
+                }
+                '}' => {
+                    if groups.len() > 1 {
+                        groups.pop();
+                    }
+                    let parent = groups.last().expect("the document group is never closed");
+                    if group.destination == Destination::FieldInstruction
+                        && parent.destination != Destination::FieldInstruction
+                    {
+                        document.finish_field();
+                    }
+                    document.fallback = 0;
+                }
+                '\\' => match chars.peek().copied() {
+                    Some(letter) if letter.is_ascii_alphabetic() => {
+                        let (word, parameter) = read_control_word(&mut chars);
+                        let destination = match word.as_str() {
+                            _ if !at_group_start => None,
+                            "fldinst" => Some(Destination::FieldInstruction),
+                            "annotation" => Some(Destination::Text),
+                            "fonttbl" | "colortbl" | "stylesheet" | "info" | "pict" => {
+                                Some(Destination::Skipped)
+                            }
+                            _ if ignorable => Some(Destination::Skipped),
+                            _ => None,
+                        };
+                        at_group_start = false;
+                        ignorable = false;
+                        let group = groups
+                            .last_mut()
+                            .expect("the document group is never closed");
+                        match destination {
+                            Some(destination) if group.destination != Destination::Skipped => {
+                                group.destination = destination;
+                            }
+                            Some(_) => {}
+                            None => document.control_word(&word, parameter, group),
+                        }
+                        continue;
+                    }
+                    Some('\'') => {
+                        chars.next();
+                        let hex: String = chars.by_ref().take(2).collect();
+                        if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                            document.push(char::from(byte), group.destination);
+                        }
+                    }
+                    Some('*') => {
+                        chars.next();
+                        ignorable = true;
+                        continue;
+                    }
+                    Some(escaped @ ('\\' | '{' | '}')) => {
+                        chars.next();
+                        document.push(escaped, group.destination);
+                    }
+                    Some('~') => {
+                        chars.next();
+                        document.push(' ', group.destination);
+                    }
+                    Some('_') => {
+                        chars.next();
+                        document.push('-', group.destination);
+                    }
+                    // An escaped line-break is a paragraph
+                    Some('\n' | '\r') => {
+                        chars.next();
+                        document.push_unskipped(' ', group.destination);
+                    }
+                    // Other control symbols like `\-` (an optional hyphen) are no text
+                    Some(_) => {
+                        chars.next();
+                    }
+                    None => {}
+                },
+                // Line-breaks only format the source of the document
+                '\n' | '\r' => continue,
+                c => document.push(c, group.destination),
+            }
+            at_group_start = false;
+            ignorable = false;
+        }
+        document
+    }
+
+    fn control_word(&mut self, word: &str, parameter: Option<i32>, group: &mut Group) {
+        match word {
+            "u" => {
+                let Some(parameter) = parameter else { return };
+                // Code points above 32767 are written as negative numbers
+                let unit = parameter.rem_euclid(0x10000) as u16;
+                self.push_utf16(unit, group.destination);
+                self.fallback = group.unicode_fallback;
+            }
+            "uc" => group.unicode_fallback = parameter.unwrap_or(1).max(0) as usize,
+            "par" | "line" | "tab" | "cell" | "row" | "sect" | "page" => {
+                self.push_unskipped(' ', group.destination);
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds a character of the document, unless it is the fallback of a `\uN`
+    fn push(&mut self, c: char, destination: Destination) {
+        if self.fallback > 0 {
+            self.fallback -= 1;
+        } else {
+            self.push_unskipped(c, destination);
+        }
+    }
+
+    fn push_unskipped(&mut self, c: char, destination: Destination) {
+        match destination {
+            Destination::Text => self.text.push(c),
+            Destination::FieldInstruction => self.instruction.push(c),
+            Destination::Skipped => {}
+        }
+    }
+
+    /// Adds a character written as `\uN`, which is one UTF-16 code unit
+    fn push_utf16(&mut self, unit: u16, destination: Destination) {
+        let units = match self.high_surrogate.take() {
+            Some(high) => vec![high, unit],
+            None if (0xD800..0xDC00).contains(&unit) => {
+                self.high_surrogate = Some(unit);
+                return;
+            }
+            None => vec![unit],
+        };
+        for c in char::decode_utf16(units) {
+            self.push_unskipped(c.unwrap_or(char::REPLACEMENT_CHARACTER), destination);
+        }
+    }
+
+    fn finish_field(&mut self) {
+        let instruction = std::mem::take(&mut self.instruction);
+        if let Some(target) = hyperlink_target(&instruction) {
+            self.fields.push((self.text.len(), target.to_string()));
+        }
+    }
+}
+
+/// Reads the name and parameter of a control word like `\fs24` or `\u-3913`, the backslash is already read.
+///
+/// A space after the control word delimits it and is no text.
+fn read_control_word(chars: &mut Peekable<Chars>) -> (String, Option<i32>) {
+    let mut word = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_alphabetic) {
+        word.push(c);
+    }
+    let mut digits = String::new();
+    if let Some(minus) = chars.next_if_eq(&'-') {
+        digits.push(minus);
+    }
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    chars.next_if_eq(&' ');
+    (word, digits.parse().ok())
+}
+
+/// The target of a field-instruction like `HYPERLINK "https://example.com" \o "Tooltip"`.
+///
+/// Links to a bookmark of the document itself (`HYPERLINK \l "bookmark"`) have no target.
+fn hyperlink_target(instruction: &str) -> Option<&str> {
+    let instruction = instruction.trim_start();
+    let keyword = instruction.get(..9)?;
+    if !keyword.eq_ignore_ascii_case("HYPERLINK") {
+        return None;
+    }
+    let arguments = instruction[9..].trim_start();
+    let target = match arguments.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next()?,
+        None => arguments.split_whitespace().next()?,
+    };
+    let target = target.trim();
+    Some(target).filter(|it| !it.is_empty() && !it.starts_with('\\'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_RTF: &[u8] = include_bytes!("../../../test_files/rtf/rtf_test.rtf");
+
+    fn summary(links: &[RtfLink]) -> Vec<(&str, RtfLinkKind)> {
+        links.iter().map(|it| (it.url.as_str(), it.kind)).collect()
+    }
+
+    #[test]
+    fn scrape_rtf_test() {
+        let links = scrape_from_slice(TEST_RTF).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://plaintext.test.com", RtfLinkKind::Text),
+                ("https://hyperlink.test.com/", RtfLinkKind::Field),
+                ("https://comment.test.com/", RtfLinkKind::Field),
+                ("https://comment.test.com", RtfLinkKind::Text),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_field_and_inline_url_test() {
+        let rtf = r#"{\rtf1\ansi{\fonttbl{\f0 https://font.test.com;}}
+{\pard See {\field{\*\fldinst {HYPERLINK "https://caf\'e9.test.com/\u8364?/\uc2\u-10179\'3f\'3f\u-8704\'3f\'3f" \\o "Tip"}}{\fldrslt {\ul Caf\'e9}}} or visit https://inline.test.com/a\{b\}\par}
+{\*\generator https://generator.test.com}}"#;
+        let links = scrape_from_string(rtf).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://café.test.com/€/😀", RtfLinkKind::Field),
+                ("https://inline.test.com/a{b}", RtfLinkKind::Text),
+            ]
+        );
+    }
+
+    #[test]
+    fn hyperlink_target_test() {
+        assert_eq!(
+            hyperlink_target(r#" HYPERLINK "https://a.test.com" \o "Tip" "#),
+            Some("https://a.test.com")
+        );
+        assert_eq!(
+            hyperlink_target("hyperlink https://b.test.com"),
+            Some("https://b.test.com")
+        );
+        assert_eq!(hyperlink_target(r#"HYPERLINK \l "bookmark""#), None);
+        assert_eq!(hyperlink_target("PAGEREF _Toc1"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape_from_slice(TEST_RTF).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<RtfLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes the links from an internet shortcut, like a Windows `.url`-file (`[InternetShortcut]`)
+/// or a freedesktop `.desktop`-file (`[Desktop Entry]`).
+///
+/// The value of every `URL`-key is taken as is, the command of every `Exec`-key is scanned for urls.
+/// All other keys, comments and lines that are not part of the ini-format are ignored,
+/// so a file without any `URL`-key results in no links instead of an error.
+/// # Example
+/// ```
+/// use link_scraper::formats::shortcut::scrape;
+/// let url_file = "[InternetShortcut]\r\nURL=https://www.example.com/\r\n";
+/// let links = scrape(url_file.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://www.example.com/");
+/// assert_eq!(links[0].section, "InternetShortcut");
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<ShortcutLink>, ShortcutScrapingError>
+where
+    R: Read,
+{
+    // Windows writes .url-files in the ansi code page, so the content is not required to be utf-8
+    let mut content = vec![];
+    reader.read_to_end(&mut content)?;
+    let content = String::from_utf8_lossy(&content);
+
+    let mut links = vec![];
+    let mut section = String::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|it| it.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.eq_ignore_ascii_case("URL") {
+            if !value.is_empty() {
+                links.push(ShortcutLink {
+                    url: value.to_string(),
+                    section: section.clone(),
+                    location: index + 1,
+                });
+            }
+        } else if key == "Exec" {
+            links.extend(find_urls(value).iter().map(|url| ShortcutLink {
+                url: url.as_str().to_string(),
+                section: section.clone(),
+                location: index + 1,
+            }));
+        }
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<ShortcutLink>, ShortcutScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<ShortcutLink>, ShortcutScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<ShortcutLink>, ShortcutScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum ShortcutScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortcutLink {
+    pub url: String,
+    /// The name of the section containing the link, like `InternetShortcut` or `Desktop Entry`.
+    /// Empty if the key comes before the first section.
+    pub section: String,
+    /// The line (starting at 1) of the key containing the link
+    pub location: usize,
+}
+
+impl Link for ShortcutLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.location)
+    }
+}
+
+impl Display for ShortcutLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_URL: &[u8] = include_bytes!("../../../test_files/shortcut/shortcut_test.url");
+    const TEST_DESKTOP: &[u8] =
+        include_bytes!("../../../test_files/shortcut/shortcut_test.desktop");
+
+    #[test]
+    fn scrape_windows_shortcut_test() {
+        let links = scrape(TEST_URL).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            links,
+            vec![ShortcutLink {
+                url: "https://shortcut.test.com/page?id=1".to_string(),
+                section: "InternetShortcut".to_string(),
+                location: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn scrape_desktop_entry_test() {
+        let links = scrape(TEST_DESKTOP).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, &str, usize)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.section.as_str(), it.location))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://desktop.test.com/start", "Desktop Entry", 7),
+                ("https://action.test.com/open", "Desktop Action Open", 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_url_key_test() {
+        let malformed = "[InternetShortcut\nIconFile=https://icon.test.com\nnot an ini-file\n=\n";
+        assert!(scrape(malformed.as_bytes()).unwrap().is_empty());
+        assert!(scrape(&b"\xff\xfeURL\xff"[..]).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_DESKTOP).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<ShortcutLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
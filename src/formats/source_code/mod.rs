@@ -0,0 +1,485 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::Path;
+use thiserror::Error;
+
+/// Scrapes links from the comments and string literals of a source file written in `language`.
+///
+/// The file is split into comments, string literals and code by the rules of the language.
+/// Only the comments and string literals are scanned for urls, so urls inside of the code
+/// (like an include path or an identifier) are skipped. The escape sequences of string literals
+/// are decoded before scanning, so `"https:\/\/example.com"` is found as `https://example.com`.
+///
+/// Files that are not valid utf-8 are decoded lossily.
+/// # Example
+/// ```
+/// use link_scraper::formats::source_code::{scrape, Language, SourceCodeLinkKind};
+/// let rust = "// See https://example.com/docs\nconst API: &str = \"https://api.example.com\";\n";
+/// let links = scrape(rust.as_bytes(), Language::Rust).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/docs");
+/// assert_eq!(links[0].kind, SourceCodeLinkKind::Comment);
+/// assert_eq!(links[1].url, "https://api.example.com");
+/// assert_eq!((links[1].kind, links[1].line), (SourceCodeLinkKind::StringLiteral, 2));
+/// ```
+pub fn scrape<R>(
+    mut reader: R,
+    language: Language,
+) -> Result<Vec<SourceCodeLink>, SourceCodeScrapingError>
+where
+    R: Read,
+{
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    let source = String::from_utf8_lossy(&bytes);
+
+    let mut links = vec![];
+    for span in Lexer::new(&source, language).spans() {
+        for url in find_urls(&span.text) {
+            links.push(SourceCodeLink {
+                url: url.as_str().to_string(),
+                kind: span.kind,
+                line: span.line + span.text[..url.start()].matches('\n').count(),
+            });
+        }
+    }
+    Ok(links)
+}
+
+/// Convenience function, that uses [`scrape`] to scrape links from a buffer.
+pub fn scrape_from_slice<T>(
+    buffer: T,
+    language: Language,
+) -> Result<Vec<SourceCodeLink>, SourceCodeScrapingError>
+where
+    T: AsRef<[u8]>,
+{
+    scrape(buffer.as_ref(), language)
+}
+
+/// Convenience function, that reads a file and uses [`scrape`] to scrape links from its content.
+///
+/// The language is chosen by the extension of the file, see [`Language::from_extension`].
+pub fn scrape_from_file<P>(path: P) -> Result<Vec<SourceCodeLink>, SourceCodeScrapingError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .map(|it| it.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let language = Language::from_extension(&extension)
+        .ok_or(SourceCodeScrapingError::UnknownLanguageError(extension))?;
+    scrape(std::fs::File::open(path)?, language)
+}
+
+/// The rules used to find the comments and string literals of a source file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Language {
+    /// `//`- and nested `/* */`-comments, normal, byte and raw string literals (like `r#"..."#`).
+    /// Character literals and lifetimes are told apart, so `'"'` does not start a string.
+    Rust,
+    /// `//`- and `/* */`-comments, `"`- and `'`-quoted literals with backslash-escapes,
+    /// like in C, C++, Java, C# or JavaScript
+    CStyle,
+    /// `#`-comments, `'`- and `"`-quoted strings with backslash-escapes and triple-quoted strings
+    Python,
+    /// `#`-comments at the start of a word, `"`-quoted strings with backslash-escapes
+    /// and `'`-quoted strings without escapes, like in sh, bash or zsh
+    Shell,
+}
+
+impl Language {
+    /// The language of a file with the extension `extension` (without the leading `.`),
+    /// if it is one of the supported languages
+    pub fn from_extension(extension: &str) -> Option<Language> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(Language::Rust),
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" | "java" | "cs" | "js"
+            | "mjs" | "cjs" | "ts" | "kt" | "swift" | "scala" => Some(Language::CStyle),
+            "py" | "pyi" | "pyw" => Some(Language::Python),
+            "sh" | "bash" | "zsh" | "ksh" => Some(Language::Shell),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SourceCodeScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    /// The language of the file could not be chosen by its extension
+    #[error("no supported language uses the extension {0:?}")]
+    UnknownLanguageError(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceCodeLink {
+    pub url: String,
+    pub kind: SourceCodeLinkKind,
+    /// The line (starting at 1) containing the link
+    pub line: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SourceCodeLinkKind {
+    /// The link is inside a comment <br/>
+    /// Example: `// See https://link.example.com`
+    Comment,
+    /// The link is inside a string literal <br/>
+    /// Example: `let link = "https://link.example.com";`
+    StringLiteral,
+}
+
+impl Link for SourceCodeLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.line)
+    }
+}
+
+impl Display for SourceCodeLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// The content of a comment or string literal, without its delimiters
+struct Span {
+    kind: SourceCodeLinkKind,
+    /// The line the content starts in
+    line: usize,
+    text: String,
+}
+
+/// Splits a source file into the spans of its comments and string literals
+struct Lexer<'a> {
+    source: &'a str,
+    language: Language,
+    position: usize,
+    line: usize,
+    spans: Vec<Span>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str, language: Language) -> Self {
+        Self {
+            source,
+            language,
+            position: 0,
+            line: 1,
+            spans: vec![],
+        }
+    }
+
+    fn spans(mut self) -> Vec<Span> {
+        let source = self.source;
+        let c_comments = matches!(self.language, Language::Rust | Language::CStyle);
+        while let Some(c) = self.peek(0) {
+            match c {
+                '/' if c_comments && self.peek(1) == Some('/') => self.line_comment(2),
+                '/' if c_comments && self.peek(1) == Some('*') => self.block_comment(),
+                '#' if self.language == Language::Python => self.line_comment(1),
+                '#' if self.language == Language::Shell && self.is_word_start() => {
+                    self.line_comment(1)
+                }
+                '"' | '\''
+                    if self.language == Language::Python
+                        && (self.rest().starts_with("\"\"\"")
+                            || self.rest().starts_with("'''")) =>
+                {
+                    self.string(&source[self.position..self.position + 3], true)
+                }
+                '"' => self.string("\"", true),
+                '\'' => match self.language {
+                    Language::Rust => self.rust_quote(),
+                    Language::Shell => self.string("'", false),
+                    Language::CStyle | Language::Python => self.string("'", true),
+                },
+                'r' | 'b' if self.language == Language::Rust && self.is_word_start() => {
+                    if !self.rust_raw_string() {
+                        self.advance(1);
+                    }
+                }
+                _ => self.advance(c.len_utf8()),
+            }
+        }
+        self.spans
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    /// The `n`-th char after the current position
+    fn peek(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    /// Whether the current char is not preceded by a part of a word
+    fn is_word_start(&self) -> bool {
+        self.source[..self.position]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_' || c == '$' || c == '{'))
+    }
+
+    /// Moves `length` bytes forward, counting the skipped lines
+    fn advance(&mut self, length: usize) {
+        let end = (self.position + length).min(self.source.len());
+        self.line += self.source[self.position..end].matches('\n').count();
+        self.position = end;
+    }
+
+    fn push(&mut self, kind: SourceCodeLinkKind, text: String) {
+        self.spans.push(Span {
+            kind,
+            line: self.line,
+            text,
+        });
+    }
+
+    /// A comment from after its `delimiter_length` bytes long start to the end of the line
+    fn line_comment(&mut self, delimiter_length: usize) {
+        self.advance(delimiter_length);
+        let length = self.rest().find('\n').unwrap_or(self.rest().len());
+        self.push(
+            SourceCodeLinkKind::Comment,
+            self.rest()[..length].to_string(),
+        );
+        self.advance(length);
+    }
+
+    /// A `/* */`-comment, which may be nested in rust
+    fn block_comment(&mut self) {
+        self.advance(2);
+        let rest = self.rest();
+        let mut depth = 1;
+        let mut index = 0;
+        while index < rest.len() {
+            if rest[index..].starts_with("*/") {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                index += 2;
+            } else if self.language == Language::Rust && rest[index..].starts_with("/*") {
+                depth += 1;
+                index += 2;
+            } else {
+                index += rest[index..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        self.push(SourceCodeLinkKind::Comment, rest[..index].to_string());
+        self.advance(index + 2);
+    }
+
+    /// A string literal enclosed by `quote`, which may be escaped by a backslash if `escapes` is set
+    fn string(&mut self, quote: &str, escapes: bool) {
+        self.advance(quote.len());
+        let rest = self.rest();
+        let mut text = String::new();
+        let mut chars = rest.char_indices();
+        let mut end = rest.len();
+        while let Some((index, c)) = chars.next() {
+            if rest[index..].starts_with(quote) {
+                end = index;
+                break;
+            }
+            if c == '\\' && escapes {
+                match chars.next() {
+                    // Escaped whitespace ends a link like a space, without changing the lines of the links
+                    Some((_, 'n' | 'r' | 't')) => text.push(' '),
+                    Some((_, escaped)) => text.push(escaped),
+                    None => {}
+                }
+            } else {
+                text.push(c);
+            }
+        }
+        self.push(SourceCodeLinkKind::StringLiteral, text);
+        self.advance(end + quote.len());
+    }
+
+    /// A rust character literal like `'"'` or `'\''`, or a lifetime like `'a`
+    fn rust_quote(&mut self) {
+        match (self.peek(1), self.peek(2)) {
+            (Some('\\'), _) => {
+                let length = self.rest()[2..]
+                    .find('\'')
+                    .map_or(self.rest().len(), |it| it + 3);
+                self.advance(length);
+            }
+            (Some(c), Some('\'')) => self.advance(2 + c.len_utf8()),
+            _ => self.advance(1),
+        }
+    }
+
+    /// A rust raw string like `r#"..."#` or `br"..."`, returns false if there is none at the current position
+    fn rust_raw_string(&mut self) -> bool {
+        let rest = self.rest();
+        let Some(raw) = rest.strip_prefix("br").or_else(|| rest.strip_prefix('r')) else {
+            return false;
+        };
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        if !raw[hashes..].starts_with('"') {
+            return false;
+        }
+        let start = rest.len() - raw.len() + hashes + 1;
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let length = rest[start..].find(&closing).unwrap_or(rest.len() - start);
+        self.advance(start);
+        self.push(
+            SourceCodeLinkKind::StringLiteral,
+            self.rest()[..length].to_string(),
+        );
+        self.advance(length + closing.len());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use SourceCodeLinkKind::{Comment, StringLiteral};
+
+    fn summary(links: &[SourceCodeLink]) -> Vec<(&str, SourceCodeLinkKind, usize)> {
+        links
+            .iter()
+            .map(|it| (it.url.as_str(), it.kind, it.line))
+            .collect()
+    }
+
+    #[test]
+    fn scrape_rust_test() {
+        let source = r###"//! Crate docs at https://docs.test.com
+use std::fmt; // https://line.test.com
+/* outer /* https://nested.test.com */ still https://outer.test.com */
+fn main<'a>(x: &'a str) {
+    let quote = '"'; let escaped = '\''; let url = "https://string.test.com";
+    let raw = r#"a "quoted" https://raw.test.com"#;
+    let bytes = b"https://bytes.test.com";
+    let code = https_test_com::foo();
+    let escapes = "https:\/\/escaped.test.com\n";
+}
+"###;
+        let links = scrape_from_slice(source, Language::Rust).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://docs.test.com", Comment, 1),
+                ("https://line.test.com", Comment, 2),
+                ("https://nested.test.com", Comment, 3),
+                ("https://outer.test.com", Comment, 3),
+                ("https://string.test.com", StringLiteral, 5),
+                ("https://raw.test.com", StringLiteral, 6),
+                ("https://bytes.test.com", StringLiteral, 7),
+                ("https://escaped.test.com", StringLiteral, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_c_style_test() {
+        let source = r#"#include <https://include.test.com/header.h>
+/*
+ * Licensed under https://license.test.com
+ */
+const char *url = "https://string.test.com/\"quoted\"";
+char quote = '"'; // https://comment.test.com
+const link = 'https://single.test.com';
+"#;
+        let links = scrape_from_slice(source, Language::CStyle).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://license.test.com", Comment, 3),
+                ("https://string.test.com/", StringLiteral, 5),
+                ("https://comment.test.com", Comment, 6),
+                ("https://single.test.com", StringLiteral, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_python_test() {
+        let source = r#"import requests  # https://comment.test.com
+"""Module docs
+
+See https://docstring.test.com
+"""
+URL = 'https://single.test.com'
+other = "it's https://double.test.com"
+requests.get(url)  # not a link: https_test_com
+"#;
+        let links = scrape_from_slice(source, Language::Python).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://comment.test.com", Comment, 1),
+                ("https://docstring.test.com", StringLiteral, 4),
+                ("https://single.test.com", StringLiteral, 6),
+                ("https://double.test.com", StringLiteral, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_shell_test() {
+        let source = r#"#!/bin/sh
+# Installs from https://comment.test.com
+curl -fsSL https://code.test.com/install.sh | sh
+echo "${#name} https://double.test.com" 'https://single.test.com\'
+"#;
+        let links = scrape_from_slice(source, Language::Shell).unwrap();
+        println!("{:?}", links);
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://comment.test.com", Comment, 2),
+                ("https://double.test.com", StringLiteral, 4),
+                ("https://single.test.com", StringLiteral, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn language_from_extension_test() {
+        assert_eq!(Language::from_extension("rs"), Some(Language::Rust));
+        assert_eq!(Language::from_extension("CPP"), Some(Language::CStyle));
+        assert_eq!(Language::from_extension("py"), Some(Language::Python));
+        assert_eq!(Language::from_extension("sh"), Some(Language::Shell));
+        assert_eq!(Language::from_extension("txt"), None);
+        assert!(matches!(
+            scrape_from_file("Cargo.toml"),
+            Err(SourceCodeScrapingError::UnknownLanguageError(_))
+        ));
+        assert!(!scrape_from_file("src/formats/source_code/mod.rs")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape_from_slice("// https://test.com", Language::Rust).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<SourceCodeLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
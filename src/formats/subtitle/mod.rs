@@ -0,0 +1,263 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes links from the cue text of a subtitle-file, either SubRip (.srt) or WebVTT (.vtt).
+///
+/// WebVTT-files are recognized by their `WEBVTT`-header. The cue identifiers (like the numbers of SubRip-cues)
+/// and timings are skipped and the markup-tags (like `<i>`, `<v Speaker>` or `<00:00:01.000>`) are removed
+/// from the cue text before it is scanned for urls. The text of WebVTT `NOTE`-blocks is scanned as well,
+/// `STYLE`- and `REGION`-blocks are not.
+/// # Example
+/// ```
+/// use link_scraper::formats::subtitle::scrape;
+/// let srt = "1\n00:00:01,000 --> 00:00:04,000\nSubtitles by <i>https://subs.example.com</i>\n";
+/// let links = scrape(srt.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://subs.example.com");
+/// assert_eq!(links[0].timestamp.as_deref(), Some("00:00:01,000 --> 00:00:04,000"));
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<SubtitleLink>, SubtitleScrapingError>
+where
+    R: Read,
+{
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+    let is_webvtt = content.starts_with("WEBVTT");
+
+    let mut links = vec![];
+    let mut cue_index = 0;
+    for (block_index, block) in blocks(content).into_iter().enumerate() {
+        let (first_line, first) = block[0];
+        if is_webvtt {
+            // The header
+            if block_index == 0 {
+                continue;
+            }
+            if let Some(note) = first.strip_prefix("NOTE") {
+                if note.is_empty() || note.starts_with([' ', '\t']) {
+                    let lines = [(first_line, note)]
+                        .into_iter()
+                        .chain(block[1..].iter().copied());
+                    links.extend(scrape_lines(lines, cue_index, None));
+                    continue;
+                }
+            }
+            if first == "STYLE" || first == "REGION" {
+                continue;
+            }
+        }
+        let Some(timing_index) = block.iter().position(|(_, line)| line.contains("-->")) else {
+            continue;
+        };
+        // Only the start and end, without the settings of a WebVTT-cue like `align:start`
+        let timestamp = block[timing_index]
+            .1
+            .split_whitespace()
+            .take(3)
+            .collect::<Vec<_>>()
+            .join(" ");
+        links.extend(scrape_lines(
+            block[timing_index + 1..].iter().copied(),
+            cue_index,
+            Some(timestamp),
+        ));
+        cue_index += 1;
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<SubtitleLink>, SubtitleScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<SubtitleLink>, SubtitleScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<SubtitleLink>, SubtitleScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum SubtitleScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubtitleLink {
+    pub url: String,
+    /// The index (starting at 0) of the cue containing the link, counted in the order of the file.
+    /// For a link inside a WebVTT `NOTE`-block it is the index of the cue following the note.
+    pub cue_index: usize,
+    /// The timing of the cue like `00:00:01,000 --> 00:00:04,000`, `None` for a link inside a WebVTT `NOTE`-block
+    pub timestamp: Option<String>,
+    /// The line (starting at 1) containing the link
+    pub location: usize,
+}
+
+impl Link for SubtitleLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.location)
+    }
+}
+
+impl Display for SubtitleLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Splits `content` into the blocks separated by blank lines.
+///
+/// Every line of a block is returned together with its line number (starting at 1).
+fn blocks(content: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut blocks: Vec<Vec<(usize, &str)>> = vec![];
+    let mut current: Vec<(usize, &str)> = vec![];
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push((index + 1, line));
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+fn scrape_lines<'a>(
+    lines: impl Iterator<Item = (usize, &'a str)>,
+    cue_index: usize,
+    timestamp: Option<String>,
+) -> Vec<SubtitleLink> {
+    let mut links = vec![];
+    for (line, text) in lines {
+        let text = strip_tags(text);
+        links.extend(find_urls(&text).iter().map(|url| SubtitleLink {
+            url: url.as_str().to_string(),
+            cue_index,
+            timestamp: timestamp.clone(),
+            location: line,
+        }));
+    }
+    links
+}
+
+/// Removes the markup-tags from a line of cue text and resolves the character references
+/// (`&amp;`, `&lt;`, `&gt;` and `&nbsp;`).
+///
+/// Angle brackets around an url, like `<https://example.com>`, are no tag, so only they are removed.
+fn strip_tags(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(length) = rest[start..].find('>') else {
+            break;
+        };
+        stripped.push_str(&rest[..start]);
+        let tag = &rest[start + 1..start + length];
+        if tag.contains("://") {
+            stripped.push(' ');
+            stripped.push_str(tag);
+            stripped.push(' ');
+        }
+        rest = &rest[start + length + 1..];
+    }
+    stripped.push_str(rest);
+    stripped
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SRT: &[u8] = include_bytes!("../../../test_files/subtitle/subtitle_test.srt");
+    const TEST_VTT: &[u8] = include_bytes!("../../../test_files/subtitle/subtitle_test.vtt");
+
+    fn summary(links: &[SubtitleLink]) -> Vec<(&str, usize, Option<&str>, usize)> {
+        links
+            .iter()
+            .map(|it| {
+                (
+                    it.url.as_str(),
+                    it.cue_index,
+                    it.timestamp.as_deref(),
+                    it.location,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scrape_srt_test() {
+        let links = scrape(TEST_SRT).unwrap();
+        println!("{:?}", links);
+        let timing = Some("00:00:05,500 --> 00:00:08,250");
+        assert_eq!(
+            summary(&links),
+            vec![
+                (
+                    "https://subs.test.com/team",
+                    0,
+                    Some("00:00:01,000 --> 00:00:04,000"),
+                    3
+                ),
+                ("https://sponsor.test.com/?ref=srt&x=1", 1, timing, 7),
+                ("https://angle.test.com", 1, timing, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_vtt_test() {
+        let links = scrape(TEST_VTT).unwrap();
+        println!("{:?}", links);
+        let timing = Some("00:00:05.000 --> 00:00:07.000");
+        assert_eq!(
+            summary(&links),
+            vec![
+                ("https://translator.test.com", 0, None, 4),
+                (
+                    "https://cue.test.com/home",
+                    0,
+                    Some("00:00:01.000 --> 00:00:04.000"),
+                    11
+                ),
+                ("https://karaoke.test.com/a", 1, timing, 14),
+                ("https://karaoke.test.com/b", 1, timing, 14),
+            ]
+        );
+    }
+
+    #[test]
+    fn strip_tags_test() {
+        assert_eq!(
+            strip_tags("<v.loud Bob>Hi</v> <https://a.test.com>!"),
+            "Hi  https://a.test.com !"
+        );
+        assert_eq!(strip_tags("a &lt; b"), "a < b");
+        assert_eq!(strip_tags("unclosed <b"), "unclosed <b");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_VTT).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<SubtitleLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::location::SourceLocation;
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter, Write};
+use std::io::Read;
+use thiserror::Error;
+use toml::de::{DeTable, DeValue};
+use toml::Spanned;
+
+/// Scrapes links from all string values of a toml-document, including the ones inside inline tables
+/// and arrays of tables.
+///
+/// Keys are not scraped. The links are ordered by their position inside the document.
+/// # Example
+/// ```
+/// use link_scraper::formats::toml::scrape;
+/// let toml = "[dependencies]\nfoo = { git = \"https://git.example.com/foo\" }\n";
+/// let links = scrape(toml.as_bytes()).unwrap();
+/// assert_eq!(links[0].key_path, "dependencies.foo.git");
+/// assert_eq!(links[0].location, 2);
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<TomlLink>, TomlScrapingError>
+where
+    R: Read,
+{
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+
+    let table = DeTable::parse(&content)?;
+    // The tables are ordered by their keys, so the links are sorted by their position afterward
+    let mut links: Vec<(usize, TomlLink)> = vec![];
+    scrape_table(table.get_ref(), &mut String::new(), &content, &mut links);
+    links.sort_by_key(|(position, _)| *position);
+    Ok(links.into_iter().map(|(_, link)| link).collect())
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<TomlLink>, TomlScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<TomlLink>, TomlScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<TomlLink>, TomlScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum TomlScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    TomlError(#[from] toml::de::Error),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TomlLink {
+    pub url: String,
+    /// The dotted keys and indices leading to the value containing the link, like `dependencies.foo.git`
+    /// or `bin[0].path`. Keys that are not bare keys are quoted, like `dependencies."my.crate".git`.
+    pub key_path: String,
+    /// The line (starting at 1) of the value containing the link
+    pub location: usize,
+}
+
+impl Link for TomlLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.location)
+    }
+
+    fn source_location(&self) -> SourceLocation {
+        SourceLocation::Pointer(self.key_path.clone())
+    }
+}
+
+impl Display for TomlLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+fn scrape_table(
+    table: &DeTable,
+    path: &mut String,
+    content: &str,
+    links: &mut Vec<(usize, TomlLink)>,
+) {
+    for (key, value) in table {
+        let path_length = path.len();
+        if !path.is_empty() {
+            path.push('.');
+        }
+        push_key(path, key.get_ref());
+        scrape_value(value, path, content, links);
+        path.truncate(path_length);
+    }
+}
+
+fn scrape_value(
+    value: &Spanned<DeValue>,
+    path: &mut String,
+    content: &str,
+    links: &mut Vec<(usize, TomlLink)>,
+) {
+    match value.get_ref() {
+        DeValue::String(string) => {
+            let start = value.span().start;
+            let line = content[..start].matches('\n').count() + 1;
+            links.extend(find_urls(string).iter().map(|url| {
+                (
+                    start,
+                    TomlLink {
+                        url: url.as_str().to_string(),
+                        key_path: path.clone(),
+                        location: line,
+                    },
+                )
+            }));
+        }
+        DeValue::Array(array) => {
+            for (index, item) in array.iter().enumerate() {
+                let path_length = path.len();
+                let _ = write!(path, "[{index}]");
+                scrape_value(item, path, content, links);
+                path.truncate(path_length);
+            }
+        }
+        DeValue::Table(table) => scrape_table(table, path, content, links),
+        _ => {}
+    }
+}
+
+/// Appends `key` to `path`, quoted if it is not a bare key
+fn push_key(path: &mut String, key: &str) {
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_bare {
+        path.push_str(key);
+    } else {
+        let _ = write!(path, "{:?}", key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_TOML: &[u8] = include_bytes!("../../../test_files/toml/toml_test.toml");
+
+    #[test]
+    fn scrape_toml_test() {
+        let links = scrape(TEST_TOML).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, &str, usize)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.key_path.as_str(), it.location))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://homepage.test.com", "package.homepage", 4),
+                ("https://repository.test.com/crate", "package.repository", 5),
+                ("https://docs.test.com/a", "package.description", 6),
+                ("https://docs.test.com/b", "package.description", 6),
+                ("https://keyword.test.com", "package.keywords[1]", 7),
+                ("https://git.test.com/foo", "dependencies.foo.git", 10),
+                (
+                    "https://git.test.com/dotted",
+                    r#"dependencies."my.crate".git"#,
+                    11
+                ),
+                ("https://bin.test.com/first", "bin[0].doc", 15),
+                ("https://bin.test.com/second", "bin[1].doc", 19),
+            ]
+        );
+    }
+
+    #[test]
+    fn fail_on_invalid_toml_test() {
+        let result = scrape("a = \"https://a.test.com".as_bytes());
+        assert!(matches!(result, Err(TomlScrapingError::TomlError(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_TOML).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<TomlLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
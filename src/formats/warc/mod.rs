@@ -0,0 +1,318 @@
+use crate::dispatch::{scrape_reader, Format, ScrapeError, ScrapedLink};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use thiserror::Error;
+
+/// Scrapes the links from the http-responses stored in a WARC-file (Web ARChive, ISO 28500).
+///
+/// The body of every `response`-record is scraped with the scraper of the format given by its
+/// `Content-Type`-header, see [`Format::from_mime_type`]. Chunked bodies are decoded first.
+/// All other records, bodies without a known format and bodies with a `Content-Encoding` (like gzip) are skipped.
+/// Textual bodies (`text/*`), whose format is not enabled by the features, are scraped as plaintext.
+///
+/// Compressed archives (`.warc.gz`) have to be decompressed before, e.g. by passing a gzip-decoder as `reader`.
+/// # Example
+/// ```
+/// use link_scraper::formats::warc::scrape;
+/// let body = "<a href=\"https://example.com/next\">Next</a>";
+/// let http = format!("HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n{body}");
+/// let warc = format!(
+///     "WARC/1.1\r\nWARC-Type: response\r\nWARC-Target-URI: https://example.com/\r\nContent-Length: {}\r\n\r\n{http}\r\n\r\n",
+///     http.len()
+/// );
+/// let links = scrape(warc.as_bytes()).unwrap();
+/// assert_eq!(links[0].url, "https://example.com/next");
+/// assert_eq!(links[0].record_uri, "https://example.com/");
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<WarcLink>, WarcScrapingError>
+where
+    R: Read,
+{
+    let mut content = vec![];
+    reader.read_to_end(&mut content)?;
+
+    let mut links = vec![];
+    let mut offset = 0;
+    loop {
+        // The records are separated by two line breaks
+        offset += content[offset..]
+            .iter()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count();
+        if offset == content.len() {
+            break;
+        }
+        let Some(head) =
+            Head::parse(&content[offset..]).filter(|head| head.first_line.starts_with("WARC/"))
+        else {
+            return Err(WarcScrapingError::InvalidRecordError(offset));
+        };
+        let block_start = offset + head.length;
+        let block_end = head
+            .value("Content-Length")
+            .and_then(|length| length.parse::<usize>().ok())
+            .and_then(|length| block_start.checked_add(length))
+            .filter(|end| *end <= content.len())
+            .ok_or(WarcScrapingError::InvalidRecordError(offset))?;
+
+        let is_response = head
+            .value("WARC-Type")
+            .is_some_and(|kind| kind.eq_ignore_ascii_case("response"));
+        if is_response {
+            let record_uri = head
+                .value("WARC-Target-URI")
+                .map(|uri| uri.trim_start_matches('<').trim_end_matches('>'))
+                .unwrap_or_default();
+            links.extend(
+                scrape_http_response(&content[block_start..block_end])?
+                    .into_iter()
+                    .map(|inner| WarcLink {
+                        url: inner.url().to_string(),
+                        record_uri: record_uri.to_string(),
+                        inner,
+                    }),
+            );
+        }
+        offset = block_end;
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<WarcLink>, WarcScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<WarcLink>, WarcScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<WarcLink>, WarcScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum WarcScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    /// The body of a response could not be scraped, e.g. because it is malformed xml
+    /// or the feature of its format is not enabled
+    #[error(transparent)]
+    ScrapeError(#[from] ScrapeError),
+    /// The record starting at this byte-offset has no WARC-version or no valid `Content-Length`
+    #[error("Invalid WARC-record at byte {0}.")]
+    InvalidRecordError(usize),
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WarcLink {
+    pub url: String,
+    /// The `WARC-Target-URI` of the record, i.e. the url the response was received from.
+    /// Empty if the record has none.
+    pub record_uri: String,
+    /// The link as found by the scraper of the body's format
+    pub inner: ScrapedLink,
+}
+
+impl Link for WarcLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Also sets the url of [`WarcLink::inner`]
+    fn set_url(&mut self, url: String) {
+        self.inner.set_url(url.clone());
+        self.url = url;
+    }
+
+    /// The location inside the body of the response
+    fn location(&self) -> Location {
+        self.inner.location()
+    }
+}
+
+impl Display for WarcLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Scrapes the body of the http-response `block` with the scraper of its `Content-Type`
+fn scrape_http_response(block: &[u8]) -> Result<Vec<ScrapedLink>, ScrapeError> {
+    let Some(head) = Head::parse(block) else {
+        return Ok(vec![]);
+    };
+    let content_type = head.value("Content-Type");
+    let format = content_type.and_then(Format::from_mime_type);
+    let encoded = head
+        .value("Content-Encoding")
+        .is_some_and(|encoding| !encoding.eq_ignore_ascii_case("identity"));
+    let Some(format) = format.filter(|_| !encoded) else {
+        return Ok(vec![]);
+    };
+
+    let body = &block[head.length..];
+    let chunked = head
+        .value("Transfer-Encoding")
+        .is_some_and(|encoding| encoding.to_ascii_lowercase().contains("chunked"));
+    let decoded;
+    let body = if chunked {
+        decoded = decode_chunked(body);
+        &decoded[..]
+    } else {
+        body
+    };
+    let is_text =
+        content_type.is_some_and(|it| it.trim_start().to_ascii_lowercase().starts_with("text/"));
+    match scrape_reader(body, format) {
+        Err(ScrapeError::FeatureNotEnabledError(_)) if is_text => {
+            scrape_reader(body, Format::PlainText)
+        }
+        result => result,
+    }
+}
+
+/// The head of a WARC-record or http-message
+struct Head {
+    /// The version of a WARC-record or the status-line of a http-response
+    first_line: String,
+    headers: Vec<(String, String)>,
+    /// The length of the head including the empty line behind it, i.e. the offset of the content
+    length: usize,
+}
+
+impl Head {
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        // The head ends at the first empty line, which is either terminated by CRLF or only by LF
+        let (head_length, separator_length) =
+            bytes
+                .windows(2)
+                .enumerate()
+                .find_map(|(position, window)| match window {
+                    b"\n\n" => Some((position, 2)),
+                    b"\r\n" if bytes[position + 2..].starts_with(b"\r\n") => Some((position, 4)),
+                    _ => None,
+                })?;
+        let head = String::from_utf8_lossy(&bytes[..head_length]);
+        let mut lines = head.lines();
+        let first_line = lines.next()?.trim().to_string();
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        Some(Head {
+            first_line,
+            headers,
+            length: head_length + separator_length,
+        })
+    }
+
+    /// The value of the first header called `name` (case-insensitive)
+    fn value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Joins the chunks of a body with `Transfer-Encoding: chunked`, up to the last complete chunk
+fn decode_chunked(mut body: &[u8]) -> Vec<u8> {
+    let mut decoded = vec![];
+    while let Some(line_end) = body.iter().position(|b| *b == b'\n') {
+        // The size may be followed by extensions like `;name=value`
+        let size = String::from_utf8_lossy(&body[..line_end]);
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let Ok(size) = usize::from_str_radix(size, 16) else {
+            break;
+        };
+        let data = &body[line_end + 1..];
+        if size == 0 || size > data.len() {
+            break;
+        }
+        decoded.extend_from_slice(&data[..size]);
+        body = &data[size..];
+        body = body.strip_prefix(b"\r\n").unwrap_or(body);
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_WARC: &[u8] = include_bytes!("../../../test_files/warc/warc_test.warc");
+
+    #[test]
+    fn scrape_html_response_test() {
+        let links = scrape(TEST_WARC).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, &str)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.record_uri.as_str()))
+            .collect();
+        // The warcinfo- and request-records and the image-response are skipped
+        assert_eq!(
+            found,
+            vec![
+                ("https://www.test.com/style.css", "https://www.test.com/"),
+                ("https://www.test.com/about", "https://www.test.com/"),
+                ("https://other.test.com/", "https://www.test.com/"),
+                (
+                    "https://chunked.test.com/page",
+                    "https://www.test.com/chunked"
+                ),
+            ]
+        );
+        assert!(links
+            .iter()
+            .all(|it| matches!(it.inner, ScrapedLink::Html(_) | ScrapedLink::Text(_))));
+        assert!(matches!(links[0].inner, ScrapedLink::Html(_)));
+        assert!(matches!(links[3].inner, ScrapedLink::Text(_)));
+    }
+
+    #[test]
+    fn scrape_css_response_test() {
+        let http = "HTTP/1.1 200 OK\r\nContent-Type: text/css\r\n\r\nbody { background: url(https://www.test.com/bg.png) }";
+        let warc = format!(
+            "WARC/1.1\r\nWARC-Type: response\r\nContent-Length: {}\r\n\r\n{http}\r\n\r\n",
+            http.len()
+        );
+        let links = scrape(warc.as_bytes()).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://www.test.com/bg.png");
+        // Without the css-feature the stylesheet is scraped as plaintext
+        #[cfg(feature = "css")]
+        assert!(matches!(links[0].inner, ScrapedLink::Css(_)));
+        #[cfg(not(feature = "css"))]
+        assert!(matches!(links[0].inner, ScrapedLink::Text(_)));
+    }
+
+    #[test]
+    fn decode_chunked_test() {
+        assert_eq!(
+            decode_chunked(b"5\r\nHello\r\n7;ext=1\r\n, World\r\n0\r\n\r\n"),
+            b"Hello, World"
+        );
+        // A truncated chunk is dropped
+        assert_eq!(decode_chunked(b"5\r\nHello\r\nff\r\nabc"), b"Hello");
+    }
+
+    #[test]
+    fn fail_on_invalid_record_test() {
+        assert!(scrape(&b""[..]).unwrap().is_empty());
+        let result = scrape(&b"\r\nnot a warc-file\r\n\r\n"[..]);
+        assert!(matches!(
+            result,
+            Err(WarcScrapingError::InvalidRecordError(2))
+        ));
+        let result =
+            scrape(&b"WARC/1.1\r\nWARC-Type: response\r\nContent-Length: 100\r\n\r\nshort"[..]);
+        assert!(matches!(
+            result,
+            Err(WarcScrapingError::InvalidRecordError(0))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_WARC).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<WarcLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
@@ -1,109 +1,897 @@
-use crate::helpers::find_urls;
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use crate::helpers::{find_css_urls, find_srcset_urls, find_urls, scheme_of, UrlMatch};
+use crate::link::{Limited, Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::io::Read;
+use std::ops::ControlFlow;
+use std::rc::Rc;
 use thiserror::Error;
 use xml::attribute::OwnedAttribute;
 use xml::common::{Position, TextPosition};
 use xml::name::OwnedName;
 use xml::namespace::Namespace;
 use xml::reader::XmlEvent;
-use xml::EventReader;
+use xml::{EventReader, ParserConfig};
 
 /// Scrapes links from any file with a xml-schema
+///
+/// Returns an error as soon as the document turns out to be malformed.
+/// Use [`scrape_lenient`] if you want to keep the links found up to that point instead.
+///
+/// The candidates of a `srcset`-attribute (like `small.png 480w, big.png 2x`) are taken as is,
+/// even if they are relative links.
 pub fn scrape<R>(reader: R) -> Result<Vec<XmlLink>, XmlScrapingError>
 where
     R: Read,
 {
-    let mut collector: Vec<XmlLink> = vec![];
-    let mut namespaces: Vec<NamespaceOccurrence> = vec![];
+    let mut links = vec![];
+    let mut namespaces = vec![];
+    scrape_with(reader, |link| {
+        if matches!(link.kind, XmlLinkKind::NameSpace(_)) {
+            namespaces.push(link);
+        } else {
+            links.push(link);
+        }
+        ControlFlow::Continue(())
+    })?;
+    links.extend(namespaces);
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<XmlLink>, XmlScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<XmlLink>, XmlScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<XmlLink>, XmlScrapingError>);
+
+/// Like [`scrape`], but passes every link to `f` while the document is parsed instead of collecting them.
+///
+/// Parsing stops as soon as `f` returns [`ControlFlow::Break`], so the rest of the document is not read.
+/// Like [`scrape_iter`], links to namespaces are passed as soon as the namespace is declared for the first time.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::scrape_with;
+/// use std::ops::ControlFlow;
+/// let mut first = None;
+/// scrape_with(r#"<a href="https://a.com">https://b.com</a>"#.as_bytes(), |link| {
+///     first = Some(link.url);
+///     ControlFlow::Break(())
+/// }).unwrap();
+/// assert_eq!(first.as_deref(), Some("https://a.com"));
+/// ```
+pub fn scrape_with<R, F>(reader: R, mut f: F) -> Result<(), XmlScrapingError>
+where
+    R: Read,
+    F: FnMut(XmlLink) -> ControlFlow<()>,
+{
+    for link in ScrapedLinks::new(reader, &ScrapeOptions::default()) {
+        if let Some(link) = into_xml_link(link?) {
+            if f(link).is_break() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first link [`scrape`] would return, e.g. to check if a document contains any link.
+///
+/// Parsing stops at the first link, that is not a link to a namespace. Since [`scrape`] moves the links
+/// to namespaces to the end, the whole document is only read if it contains no other link.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::find_first_link;
+/// let xml = r#"<root xmlns:a="https://ns.example.com"><a href="https://a.com">https://b.com</a></root>"#;
+/// let link = find_first_link(xml.as_bytes()).unwrap();
+/// assert_eq!(link.unwrap().url, "https://a.com");
+/// assert!(find_first_link("<root/>".as_bytes()).unwrap().is_some_and(|it| it.url.starts_with("http://www.w3.org/")));
+/// ```
+pub fn find_first_link<R>(reader: R) -> Result<Option<XmlLink>, XmlScrapingError>
+where
+    R: Read,
+{
+    let mut first = None;
+    let mut first_namespace = None;
+    scrape_with(reader, |link| {
+        if matches!(link.kind, XmlLinkKind::NameSpace(_)) {
+            first_namespace.get_or_insert(link);
+            return ControlFlow::Continue(());
+        }
+        first = Some(link);
+        ControlFlow::Break(())
+    })?;
+    Ok(first.or(first_namespace))
+}
+
+/// Like [`scrape`], but stops parsing as soon as more than `max_links` links were found,
+/// so untrusted documents cannot make it collect an unbounded number of links.
+///
+/// The links are in the order of [`scrape_with`], so links to namespaces are not moved to the end.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::scrape_limited;
+/// let limited = scrape_limited(br#"<a href="https://a.com">https://b.com</a>"#.as_slice(), 1).unwrap();
+/// assert_eq!(limited.links.len(), 1);
+/// assert!(limited.truncated);
+/// ```
+pub fn scrape_limited<R>(reader: R, max_links: usize) -> Result<Limited<XmlLink>, XmlScrapingError>
+where
+    R: Read,
+{
+    let mut limited = Limited {
+        links: vec![],
+        truncated: false,
+    };
+    scrape_with(reader, |link| {
+        if limited.links.len() >= max_links {
+            limited.truncated = true;
+            return ControlFlow::Break(());
+        }
+        limited.links.push(link);
+        ControlFlow::Continue(())
+    })?;
+    Ok(limited)
+}
+
+/// Counts the links [`scrape`] would return, without collecting them or converting them to [`XmlLink`]s.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::count_links;
+/// // Including the implicit xml- and xmlns-namespaces
+/// assert_eq!(count_links(r#"<a href="https://a.com">https://b.com</a>"#.as_bytes()).unwrap(), 4);
+/// ```
+pub fn count_links<R>(reader: R) -> Result<usize, XmlScrapingError>
+where
+    R: Read,
+{
+    let mut count = 0;
+    for link in ScrapedLinks::new(reader, &ScrapeOptions::default()) {
+        if matches!(link?.kind, ScrapedLinkKind::Xml(_)) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Like [`scrape`], but only collects the links inside of the elements matched by `matcher`,
+/// including the attributes of the matched elements themselves.
+///
+/// Links outside of them, like in the boilerplate header of a document or the namespaces
+/// declared by the root-element, are skipped. The whole document is still parsed.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::scrape_within;
+/// let xml = r#"<html><head><link href="https://style.example.com"/></head><body>https://example.com</body></html>"#;
+/// let links = scrape_within(xml.as_bytes(), |name| name.local_name == "body").unwrap();
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].url, "https://example.com");
+/// ```
+pub fn scrape_within<R, F>(reader: R, matcher: F) -> Result<Vec<XmlLink>, XmlScrapingError>
+where
+    R: Read,
+    F: Fn(&OwnedName) -> bool,
+{
+    let links = ScrapedLinks::new(reader, &ScrapeOptions::default()).within(&matcher);
+    collect_links(links).map(into_xml_links)
+}
+
+/// Like [`scrape`], but only scans the attributes allowed by `config` for urls.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::{scrape_with_config, XmlScrapeConfig};
+/// let config = XmlScrapeConfig {
+///     attribute_denylist: ["data-analytics".to_string()].into(),
+///     ..Default::default()
+/// };
+/// let links = scrape_with_config(
+///     r#"<a href="https://example.com" data-analytics="https://tracker.example.com"/>"#.as_bytes(),
+///     &config,
+/// ).unwrap();
+/// assert!(links.iter().any(|it| it.url == "https://example.com"));
+/// assert!(links.iter().all(|it| it.url != "https://tracker.example.com"));
+/// ```
+pub fn scrape_with_config<R>(
+    reader: R,
+    config: &XmlScrapeConfig,
+) -> Result<Vec<XmlLink>, XmlScrapingError>
+where
+    R: Read,
+{
+    let options = ScrapeOptions {
+        config: Some(config),
+        ..Default::default()
+    };
+    scrape_internal(reader, &options).map(into_xml_links)
+}
+
+/// Chooses which attributes are scanned for urls and which namespaces are reported by [`scrape_with_config`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmlScrapeConfig {
+    /// If set, only the attributes with one of these local names (e.g. `href` for `xlink:href`) are scanned
+    pub attribute_allowlist: Option<HashSet<String>>,
+    /// The attributes with one of these local names are never scanned, even if they are allowed
+    pub attribute_denylist: HashSet<String>,
+    /// Reports every declared namespace as [`XmlLinkKind::NameSpace`], even if its uri is not an url
+    /// (e.g. a relative uri like `legacy/schema` or a custom scheme like `urn:example`).
+    /// Otherwise only the namespaces with an url as uri are reported.
+    pub include_all_namespaces: bool,
+    /// Which declarations of a namespace are reported as separate links
+    pub namespace_dedup: NamespaceDedup,
+    /// Reports every attribute-value that is a `javascript:`- or `data:`-uri as [`XmlLinkKind::DangerousScheme`],
+    /// e.g. to audit a document for script injection. Otherwise these values are not recognized as urls.
+    pub detect_dangerous_schemes: bool,
+    /// Truncates the `data:`-uris reported because of [`XmlScrapeConfig::detect_dangerous_schemes`]
+    /// to this many characters, so large embedded files (like base64-encoded images) do not end up in the results
+    pub max_data_uri_length: Option<usize>,
+    /// Attribute-values longer than this many bytes are not scanned for urls, so large embedded files
+    /// (like base64-encoded images) are neither searched nor produce spurious matches.
+    /// Such a value that is a `data:`-uri is still reported once as [`XmlLinkKind::DangerousScheme`],
+    /// shortened to [`XmlScrapeConfig::max_data_uri_length`]. Values taken as a whole, like the
+    /// references of svg, are not affected.
+    pub max_scanned_attribute_length: Option<usize>,
+    /// The local names of the attributes (e.g. `href`), whose fragment-only (`#top`) and query-only (`?page=2`)
+    /// values are reported as [`XmlLinkKind::RelativeReference`]. Otherwise these values are not recognized as urls.
+    pub relative_reference_attributes: HashSet<String>,
+    /// Sets [`XmlLink::raw`] of the links inside attributes to their source, e.g. to find them in the document.
+    /// Slows down the scraping, because the source of every start-tag has to be recorded.
+    pub report_raw: bool,
+    /// Also scrapes the texts and CData-sections containing html-elements, like the (escaped) descriptions
+    /// of feeds, with [`crate::formats::html::scrape`]. The links it finds in addition to the ones
+    /// inside the text itself (e.g. the `href` of an `<a>`) are reported as [`XmlLinkKind::EmbeddedHtml`].
+    #[cfg(feature = "html")]
+    pub parse_embedded_html: bool,
+}
+
+/// The form of a [`XmlLinkKind::RelativeReference`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RelativeReferenceKind {
+    /// Only a fragment, referencing a part of the same document.<br/>
+    /// Example: `#top`
+    Fragment,
+    /// Only a query, replacing the query of the document's url.<br/>
+    /// Example: `?page=2`
+    Query,
+}
+
+impl RelativeReferenceKind {
+    /// The kind of `value`, if it is a fragment-only or query-only reference
+    pub fn of(value: &str) -> Option<Self> {
+        match value.trim_start().as_bytes().first()? {
+            b'#' => Some(Self::Fragment),
+            b'?' => Some(Self::Query),
+            _ => None,
+        }
+    }
+}
+
+/// Chooses when the declaration of a namespace is a duplicate of an earlier one and therefore not reported again
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NamespaceDedup {
+    /// Each pair of prefix and uri is reported once,
+    /// so an uri bound to two prefixes (like `xmlns:a` and `xmlns:b`) is reported twice
+    #[default]
+    ByPrefix,
+    /// Each uri is reported once, under the prefix it is declared with first
+    ByUri,
+}
+
+impl XmlScrapeConfig {
+    /// Whether the attribute with the local name `name` is scanned for urls
+    pub fn is_attribute_scanned(&self, name: &str) -> bool {
+        self.attribute_allowlist
+            .as_ref()
+            .is_none_or(|allowlist| allowlist.contains(name))
+            && !self.attribute_denylist.contains(name)
+    }
+}
+
+/// The scheme of an uri, that may execute code or embed content when it is opened
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DangerousScheme {
+    /// Example: `javascript:alert(document.cookie)`
+    JavaScript,
+    /// Example: `data:image/svg+xml;base64,PHN2Zz4=`
+    Data,
+}
+
+impl DangerousScheme {
+    /// The dangerous scheme of `value`, if it is a `javascript:`- or `data:`-uri
+    pub fn of(value: &str) -> Option<Self> {
+        match scheme_of(value)?.as_ref() {
+            "javascript" => Some(Self::JavaScript),
+            "data" => Some(Self::Data),
+            _ => None,
+        }
+    }
+}
+
+/// Like [`scrape`], but yields the links lazily while the document is parsed,
+/// so huge documents can be processed without keeping all of their links in memory.
+///
+/// Unlike [`scrape`], links to namespaces are yielded as soon as the namespace is declared for the first time,
+/// instead of after all other links. The iterator ends after the first error.
+pub fn scrape_iter<R>(reader: R) -> impl Iterator<Item = Result<XmlLink, XmlScrapingError>>
+where
+    R: Read,
+{
+    ScrapedLinks::new(reader, &ScrapeOptions::default())
+        .filter_map(|result| result.map(into_xml_link).transpose())
+}
+
+/// Like [`scrape`], but stops at the first malformed token and returns
+/// all links found up to that point instead of an error.
+pub fn scrape_lenient<R>(reader: R) -> Result<Vec<XmlLink>, XmlScrapingError>
+where
+    R: Read,
+{
+    let options = ScrapeOptions {
+        lenient: true,
+        ..Default::default()
+    };
+    scrape_internal(reader, &options).map(into_xml_links)
+}
+
+/// Like [`scrape_lenient`], but also reports the malformed token it stopped at as a [`ScrapeWarning`],
+/// so documents with recoverable issues (like unclosed tags) can be told apart from well-formed ones.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::{scrape_with_diagnostics, ScrapeWarningKind};
+/// let xml = br#"<root><a href="https://a.example.com"></root>"#;
+/// let (links, warnings) = scrape_with_diagnostics(&xml[..]).unwrap();
+/// assert_eq!(links[0].url, "https://a.example.com");
+/// assert!(matches!(warnings[0].kind, ScrapeWarningKind::MalformedXml(_)));
+/// ```
+pub fn scrape_with_diagnostics<R>(
+    reader: R,
+) -> Result<(Vec<XmlLink>, Vec<ScrapeWarning>), XmlScrapingError>
+where
+    R: Read,
+{
+    let mut warnings = vec![];
+    let mut links = vec![];
+    for result in ScrapedLinks::new(reader, &ScrapeOptions::default()) {
+        match result {
+            Ok(link) => links.push(link),
+            Err(XmlScrapingError::XmlReaderError(err)) => {
+                warnings.push(ScrapeWarning::malformed_xml(&err));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    let (namespaces, mut links): (Vec<_>, Vec<_>) = links
+        .into_iter()
+        .partition(|link| matches!(link.kind, ScrapedLinkKind::Xml(XmlLinkKind::NameSpace(_))));
+    links.extend(namespaces);
+    Ok((into_xml_links(links), warnings))
+}
+
+/// Like [`scrape`], but also collects the values of all `id`- and `xml:id`-attributes and additionally reports
+/// the fragment-only references of `href`- and `xlink:href`-attributes (like `#section-3`) as
+/// [`XmlLinkKind::Attribute`], so they can be checked with [`find_dangling_fragments`].
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::{find_dangling_fragments, scrape_with_ids};
+/// let xml = br##"<doc><a href="#intro"/><a href="#missing"/><section id="intro"/></doc>"##;
+/// let result = scrape_with_ids(&xml[..]).unwrap();
+/// assert!(result.ids.contains("intro"));
+/// let dangling = find_dangling_fragments(&result);
+/// assert_eq!(dangling.len(), 1);
+/// assert_eq!(dangling[0].url, "#missing");
+/// ```
+pub fn scrape_with_ids<R>(reader: R) -> Result<XmlScrapeResult, XmlScrapingError>
+where
+    R: Read,
+{
+    let options = ScrapeOptions {
+        collect_ids: true,
+        ..Default::default()
+    };
+    let mut scraped = ScrapedLinks::new(reader, &options);
+    let (namespaces, mut links): (Vec<_>, Vec<_>) = scraped
+        .by_ref()
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .partition(|link| matches!(link.kind, ScrapedLinkKind::Xml(XmlLinkKind::NameSpace(_))));
+    links.extend(namespaces);
+    Ok(XmlScrapeResult {
+        links: into_xml_links(links),
+        ids: scraped.ids,
+    })
+}
+
+/// The result of [`scrape_with_ids`]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XmlScrapeResult {
+    pub links: Vec<XmlLink>,
+    /// The values of all `id`- and `xml:id`-attributes of the document
+    pub ids: HashSet<String>,
+}
+
+/// Returns the intra-document references (links starting with `#`) of `result`,
+/// whose fragment is not the id of any element of the document.
+pub fn find_dangling_fragments(result: &XmlScrapeResult) -> Vec<&XmlLink> {
+    result
+        .links
+        .iter()
+        .filter(|link| {
+            link.url
+                .strip_prefix('#')
+                .is_some_and(|fragment| !result.ids.contains(fragment))
+        })
+        .collect()
+}
+
+/// A recoverable issue of a document, that did not stop it from being scraped
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrapeWarning {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
+    pub location: TextPosition,
+    pub kind: ScrapeWarningKind,
+}
+
+impl ScrapeWarning {
+    pub(crate) fn malformed_xml(err: &xml::reader::Error) -> Self {
+        Self {
+            location: err.position(),
+            kind: ScrapeWarningKind::MalformedXml(err.msg().to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ScrapeWarningKind {
+    /// The document is malformed from here on (e.g. a tag is not closed), so the rest of it was not scraped.
+    /// Contains the message of the parser.
+    MalformedXml(String),
+    /// A locator-element outside of an extended element was skipped
+    #[cfg(feature = "xlink")]
+    LocatorOutsideOfExtended,
+    /// An arc-element outside of an extended element was skipped
+    #[cfg(feature = "xlink")]
+    ArcOutsideOfExtended,
+    /// A resource-element outside of an extended element was skipped
+    #[cfg(feature = "xlink")]
+    ResourceOutsideOfExtended,
+}
+
+impl Display for ScrapeWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ScrapeWarningKind::MalformedXml(message) => {
+                write!(f, "{}: malformed xml: {}", self.location, message)
+            }
+            #[cfg(feature = "xlink")]
+            ScrapeWarningKind::LocatorOutsideOfExtended => {
+                write!(
+                    f,
+                    "{}: skipped a locator-element outside of an extended element",
+                    self.location
+                )
+            }
+            #[cfg(feature = "xlink")]
+            ScrapeWarningKind::ArcOutsideOfExtended => {
+                write!(
+                    f,
+                    "{}: skipped an arc-element outside of an extended element",
+                    self.location
+                )
+            }
+            #[cfg(feature = "xlink")]
+            ScrapeWarningKind::ResourceOutsideOfExtended => {
+                write!(
+                    f,
+                    "{}: skipped a resource-element outside of an extended element",
+                    self.location
+                )
+            }
+        }
+    }
+}
+
+/// Configures [`scrape_internal`] for the formats based on xml
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct ScrapeOptions<'a> {
+    /// Stop at the first malformed token and keep the links found up to that point instead of returning an error
+    pub lenient: bool,
+    /// The attributes containing css (e.g. `style`), which are scraped for css-references instead of plain urls
+    pub css_attributes: &'a [&'a str],
+    /// The elements containing css (e.g. `<style>`), whose text is scraped for css-references instead of plain urls
+    pub css_elements: &'a [&'a str],
+    /// The attributes to scan and the namespaces to report, all attributes are scanned if not set
+    pub config: Option<&'a XmlScrapeConfig>,
+    /// The elements (e.g. `<use>`), whose `href` or `xlink:href` references another resource.
+    /// Their value is taken as is, even if it is a relative link or only a fragment.
+    pub reference_elements: &'a [&'a str],
+    /// Collect the values of all `id`- and `xml:id`-attributes into [`ScrapedLinks::ids`]
+    /// and report the fragment-only references of `href`-attributes
+    pub collect_ids: bool,
+}
+
+/// A link found by [`scrape_internal`]
+pub(crate) struct ScrapedLink {
+    pub url: String,
+    pub location: TextPosition,
+    /// See [`XmlLink::offset_in_value`]
+    pub offset_in_value: usize,
+    pub kind: ScrapedLinkKind,
+    pub xml_base: Vec<String>,
+    /// See [`XmlLink::raw`]
+    pub raw: Option<String>,
+}
+
+pub(crate) enum ScrapedLinkKind {
+    Xml(XmlLinkKind),
+    /// A css-reference inside one of the [`ScrapeOptions::css_attributes`] or [`ScrapeOptions::css_elements`].
+    /// Fragment-only references like `url(#gradient)` are skipped.
+    Css {
+        element: OwnedName,
+    },
+    /// The `href` or `xlink:href` of one of the [`ScrapeOptions::reference_elements`]
+    Reference {
+        element: OwnedName,
+    },
+}
+
+/// Css-references and references are only found, if the [`ScrapeOptions`] name attributes or elements containing them.
+fn into_xml_link(link: ScrapedLink) -> Option<XmlLink> {
+    match link.kind {
+        ScrapedLinkKind::Xml(kind) => Some(XmlLink {
+            url: link.url,
+            location: link.location,
+            offset_in_value: link.offset_in_value,
+            kind,
+            xml_base: link.xml_base,
+            raw: link.raw,
+        }),
+        ScrapedLinkKind::Css { .. } | ScrapedLinkKind::Reference { .. } => None,
+    }
+}
+
+fn into_xml_links(links: Vec<ScrapedLink>) -> Vec<XmlLink> {
+    links.into_iter().filter_map(into_xml_link).collect()
+}
+
+/// The scraper shared by all formats based on xml.
+///
+/// Collects the links of [`ScrapedLinks`], but moves the links to namespaces after all other links.
+pub(crate) fn scrape_internal<R>(
+    reader: R,
+    options: &ScrapeOptions,
+) -> Result<Vec<ScrapedLink>, XmlScrapingError>
+where
+    R: Read,
+{
+    collect_links(ScrapedLinks::new(reader, options))
+}
+
+/// Collects the links of `links`, but moves the links to namespaces after all other links
+fn collect_links<R: Read>(
+    links: ScrapedLinks<'_, R>,
+) -> Result<Vec<ScrapedLink>, XmlScrapingError> {
+    let (namespaces, mut links): (Vec<_>, Vec<_>) = links
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .partition(|link| matches!(link.kind, ScrapedLinkKind::Xml(XmlLinkKind::NameSpace(_))));
+    links.extend(namespaces);
+    Ok(links)
+}
+
+/// Yields the links of a xml-document while it is parsed.
+///
+/// Links to namespaces are yielded when the namespace is declared for the first time.
+/// Ends after the first error.
+pub(crate) struct ScrapedLinks<'a, R: Read> {
+    parser: EventReader<TagRecordingReader<R>>,
+    /// The source of the last start-tag read by the parser if [`XmlScrapeConfig::report_raw`] is set,
+    /// see [`TagRecordingReader`]
+    tag: Option<Rc<RefCell<RecordedTag>>>,
+    options: ScrapeOptions<'a>,
+    ancestors: Vec<OwnedName>,
+    /// The xml:base declared by each of the ancestors
+    bases: Vec<Option<String>>,
+    /// The prefixes and uris of all namespaces declared so far
+    namespaces: Vec<(String, String)>,
+    /// The namespaces in scope of each of the ancestors, to tell the declarations of an element
+    /// apart from the ones it inherits
+    namespace_scopes: Vec<Namespace>,
+    /// The links found in the last event, that have not been yielded yet
+    pending: VecDeque<ScrapedLink>,
+    finished: bool,
+    /// The ids found so far, if [`ScrapeOptions::collect_ids`] is set
+    pub ids: HashSet<String>,
+    /// Only the links inside of the elements matched by it are yielded, see [`ScrapedLinks::within`]
+    matcher: Option<&'a dyn Fn(&OwnedName) -> bool>,
+    /// The number of ancestors of the outermost matched element the parser is inside of
+    matched_depth: Option<usize>,
+}
+
+impl<'a, R: Read> ScrapedLinks<'a, R> {
+    pub(crate) fn new(reader: R, options: &ScrapeOptions<'a>) -> Self {
+        let tag = options
+            .config
+            .is_some_and(|config| config.report_raw)
+            .then(|| Rc::new(RefCell::new(RecordedTag::default())));
+        Self {
+            parser: EventReader::new_with_config(
+                TagRecordingReader {
+                    inner: reader,
+                    tag: tag.clone(),
+                },
+                ParserConfig::new().ignore_comments(false),
+            ),
+            tag,
+            options: *options,
+            ancestors: vec![],
+            bases: vec![],
+            namespaces: vec![],
+            namespace_scopes: vec![],
+            pending: VecDeque::new(),
+            finished: false,
+            ids: HashSet::new(),
+            matcher: None,
+            matched_depth: None,
+        }
+    }
+
+    /// Only yields the links inside of the elements matched by `matcher`, including their own attributes
+    pub(crate) fn within(self, matcher: &'a dyn Fn(&OwnedName) -> bool) -> Self {
+        Self {
+            matcher: Some(matcher),
+            ..self
+        }
+    }
 
-    let mut current_parent: Option<OwnedName> = None;
-    let mut parser = EventReader::new(reader);
-    while let Ok(xml_event) = &parser.next() {
+    /// Reads the next event and adds its links to the pending ones.
+    fn scrape_next_event(&mut self) -> Result<(), XmlScrapingError> {
+        let xml_event = match self.parser.next() {
+            Ok(xml_event) => xml_event,
+            Err(_) if self.options.lenient => {
+                self.finished = true;
+                return Ok(());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let position = self.parser.position();
+        let mut found: Vec<ScrapedLink> = vec![];
+        let mut found_namespaces: Vec<ScrapedLink> = vec![];
         match xml_event {
             XmlEvent::StartElement {
                 name,
                 attributes,
                 namespace,
             } => {
-                namespace.0.iter().for_each(|(ns_name, ns_ref)| {
-                    let ns_occurence = NamespaceOccurrence {
-                        namespace: ns_name.to_string(),
-                        namespace_uri: ns_ref.to_string(),
-                        first_occurrence: parser.position(),
+                if self.ancestors.is_empty() {
+                    // The doctype is only known to the parser once the root-element starts
+                    found.extend(scrape_from_doctype(self.parser.doctype(), position));
+                }
+                self.bases.push(find_xml_base(&attributes));
+                if self.matched_depth.is_none()
+                    && self.matcher.is_some_and(|matcher| matcher(&name))
+                {
+                    self.matched_depth = Some(self.ancestors.len());
+                }
+                for (ns_name, ns_ref) in
+                    declared_namespaces(&namespace, self.namespace_scopes.last())
+                {
+                    let declaration = (ns_name.to_string(), ns_ref.to_string());
+                    let namespace_dedup = self
+                        .options
+                        .config
+                        .map(|config| config.namespace_dedup)
+                        .unwrap_or_default();
+                    let is_duplicate = match namespace_dedup {
+                        NamespaceDedup::ByPrefix => self.namespaces.contains(&declaration),
+                        NamespaceDedup::ByUri => {
+                            self.namespaces.iter().any(|(_, uri)| uri == ns_ref)
+                        }
                     };
-                    if !&namespaces.contains(&ns_occurence) {
-                        namespaces.push(ns_occurence);
+                    if is_duplicate {
+                        continue;
                     }
-                });
-                current_parent = Some(name.clone());
-                collector.append(&mut scrape_from_xml_start_element_attributes(
-                    &attributes,
-                    &parser,
-                )?)
+                    let include_all = self
+                        .options
+                        .config
+                        .is_some_and(|config| config.include_all_namespaces);
+                    // An empty uri is not a declaration, but the absence of a default namespace
+                    if (include_all && !ns_ref.is_empty()) || !find_urls(ns_ref).is_empty() {
+                        found_namespaces.push(ScrapedLink {
+                            url: ns_ref.to_string(),
+                            location: position,
+                            offset_in_value: 0,
+                            xml_base: vec![],
+                            raw: None,
+                            kind: ScrapedLinkKind::Xml(XmlLinkKind::NameSpace(ns_name.to_string())),
+                        });
+                    }
+                    self.namespaces.push(declaration);
+                }
+                self.namespace_scopes.push(namespace);
+                self.ancestors.push(name.clone());
+                if self.options.collect_ids {
+                    self.ids.extend(
+                        attributes
+                            .iter()
+                            .filter(|attribute| is_id(&attribute.name))
+                            .map(|attribute| attribute.value.clone()),
+                    );
+                }
+                let attributes_start = found.len();
+                scrape_from_xml_start_element_attributes(
+                    &name,
+                    attributes,
+                    position,
+                    &self.options,
+                    &mut found,
+                );
+                let tag = self.tag.as_ref().map(|tag| tag.borrow());
+                if let Some(tag) = tag.as_ref().and_then(|tag| tag.text()) {
+                    let raw_values = raw_attribute_values(&tag);
+                    found[attributes_start..]
+                        .iter_mut()
+                        .chain(found_namespaces.iter_mut())
+                        .for_each(|link| link.raw = find_raw(link, &raw_values));
+                }
             }
-            XmlEvent::Comment(comment) => collector.append(
-                &mut find_urls(comment)
+            XmlEvent::Comment(comment) => {
+                found = find_urls(&comment)
                     .iter()
-                    .map(|link| XmlLink {
+                    .map(|link| ScrapedLink {
                         url: link.as_str().to_string(),
-                        location: parser.position(),
-                        kind: XmlLinkKind::Comment,
+                        location: position,
+                        offset_in_value: char_offset(&comment, link.start()),
+                        xml_base: vec![],
+                        raw: None,
+                        kind: ScrapedLinkKind::Xml(XmlLinkKind::Comment),
                     })
-                    .collect(),
-            ),
-            XmlEvent::Characters(chars) => collector.append(
-                &mut find_urls(chars)
+                    .collect()
+            }
+            XmlEvent::Characters(chars) | XmlEvent::CData(chars)
+                if self.ancestors.last().is_some_and(|parent| {
+                    self.options
+                        .css_elements
+                        .contains(&parent.local_name.as_str())
+                }) =>
+            {
+                found = scrape_style_urls(&chars, self.ancestors.last().unwrap(), position)
+            }
+            XmlEvent::Characters(chars) => {
+                found = find_urls(&chars)
                     .iter()
-                    .map(|link| XmlLink {
+                    .map(|link| ScrapedLink {
                         url: link.as_str().to_string(),
-                        location: parser.position(),
-                        kind: XmlLinkKind::PlainText(ParentInformation {
-                            parent_tag_name: current_parent.clone(),
-                        }),
+                        location: position,
+                        offset_in_value: char_offset(&chars, link.start()),
+                        xml_base: vec![],
+                        raw: None,
+                        kind: ScrapedLinkKind::Xml(XmlLinkKind::PlainText(ParentInformation {
+                            ancestors: self.ancestors.clone(),
+                        })),
                     })
-                    .collect(),
-            ),
-            XmlEvent::CData(chars) => collector.append(
-                &mut find_urls(chars)
+                    .collect();
+                #[cfg(feature = "html")]
+                self.scrape_embedded_html(&chars, position, &mut found);
+            }
+            XmlEvent::CData(chars) => {
+                found = find_urls(&chars)
                     .iter()
-                    .map(|link| XmlLink {
+                    .map(|link| ScrapedLink {
                         url: link.as_str().to_string(),
-                        location: parser.position(),
-                        kind: XmlLinkKind::CData(ParentInformation {
-                            parent_tag_name: current_parent.clone(),
-                        }),
+                        location: position,
+                        offset_in_value: char_offset(&chars, link.start()),
+                        xml_base: vec![],
+                        raw: None,
+                        kind: ScrapedLinkKind::Xml(XmlLinkKind::CData(ParentInformation {
+                            ancestors: self.ancestors.clone(),
+                        })),
                     })
-                    .collect(),
-            ),
-            XmlEvent::EndDocument => break,
+                    .collect();
+                #[cfg(feature = "html")]
+                self.scrape_embedded_html(&chars, position, &mut found);
+            }
+            XmlEvent::EndElement { .. } => {
+                self.ancestors.pop();
+                self.bases.pop();
+                self.namespace_scopes.pop();
+                if self
+                    .matched_depth
+                    .is_some_and(|depth| self.ancestors.len() <= depth)
+                {
+                    self.matched_depth = None;
+                }
+            }
+            XmlEvent::ProcessingInstruction { name, data } => {
+                found = scrape_from_processing_instruction(&name, data.as_deref(), position)
+            }
+            XmlEvent::EndDocument => self.finished = true,
             _ => {}
         }
+
+        if self.bases.iter().any(Option::is_some) {
+            let xml_base: Vec<String> = self.bases.iter().flatten().cloned().collect();
+            found
+                .iter_mut()
+                .for_each(|link| link.xml_base.clone_from(&xml_base));
+        }
+        if self.matcher.is_some() && self.matched_depth.is_none() {
+            return Ok(());
+        }
+        self.pending.extend(found);
+        self.pending.extend(found_namespaces);
+        Ok(())
     }
+}
 
-    namespaces.into_iter().for_each(
-        |NamespaceOccurrence {
-             namespace,
-             namespace_uri,
-             first_occurrence,
-         }| {
-            if find_urls(&namespace_uri).len() == 0 {
-                return;
+impl<R: Read> ScrapedLinks<'_, R> {
+    /// Adds the links of the html inside `text` to the links `found` inside the text itself,
+    /// if [`XmlScrapeConfig::parse_embedded_html`] is set
+    #[cfg(feature = "html")]
+    fn scrape_embedded_html(
+        &self,
+        text: &str,
+        position: TextPosition,
+        found: &mut Vec<ScrapedLink>,
+    ) {
+        if !self
+            .options
+            .config
+            .is_some_and(|config| config.parse_embedded_html)
+            || !text.contains('<')
+        {
+            return;
+        }
+        // Reading from a slice cannot fail
+        let links = crate::formats::html::scrape(text.as_bytes()).unwrap_or_default();
+        for link in links {
+            if found.iter().any(|it| it.url == link.url) {
+                continue;
             }
+            found.push(ScrapedLink {
+                location: position,
+                offset_in_value: text
+                    .find(&link.url)
+                    .map_or(0, |start| char_offset(text, start)),
+                xml_base: vec![],
+                raw: None,
+                kind: ScrapedLinkKind::Xml(XmlLinkKind::EmbeddedHtml {
+                    parent: ParentInformation {
+                        ancestors: self.ancestors.clone(),
+                    },
+                    kind: link.kind,
+                }),
+                url: link.url,
+            });
+        }
+    }
+}
 
-            collector.push(XmlLink {
-                url: namespace_uri,
-                location: first_occurrence,
-                kind: XmlLinkKind::NameSpace(namespace),
-            })
-        },
-    );
+impl<R: Read> Iterator for ScrapedLinks<'_, R> {
+    type Item = Result<ScrapedLink, XmlScrapingError>;
 
-    Ok(collector)
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(link) = self.pending.pop_front() {
+                return Some(Ok(link));
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(err) = self.scrape_next_event() {
+                self.finished = true;
+                return Some(Err(err));
+            }
+        }
+    }
 }
-gen_scrape_from_file!(scrape(Read) -> Result<Vec<XmlLink>, XmlScrapingError>);
-gen_scrape_from_slice!(scrape(Read) -> Result<Vec<XmlLink>, XmlScrapingError>);
 
 #[derive(Error, Debug)]
 pub enum XmlScrapingError {
@@ -118,10 +906,22 @@ pub mod svg;
 pub mod xlink;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum XmlLinkKind {
     /// The link is inside a xml-attribute <br/>
     /// Example: `<a href="https://link.example.com">`
-    Attribute(OwnedAttribute),
+    Attribute {
+        /// The attribute containing the link
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serde_helpers::OwnedAttributeDef")
+        )]
+        attribute: OwnedAttribute,
+        /// The element the attribute belongs to
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+    },
 
     /// The link is inside a xml-comment <br/>
     /// Example: `<!--Just a comment with a link to https://link.example.com-->`
@@ -145,18 +945,148 @@ pub enum XmlLinkKind {
     /// This link is a reference to a xml-namespace<br/>
     /// Example: `<root xmlns="https://link.example.com">`
     NameSpace(String),
+
+    /// The link is inside a processing instruction.
+    /// The `href` pseudo-attribute is taken as is, even if it is a relative link.<br/>
+    /// Example: `<?xml-stylesheet type="text/xsl" href="https://link.example.com/style.xsl"?>`
+    ProcessingInstruction { target: String },
+
+    /// The link is the system identifier of the external DTD, or inside its public identifier.
+    /// The system identifier is taken as is, even if it is a relative link.
+    /// The location is the one of the root-element, as the parser does not report the position of the doctype.<br/>
+    /// Example: `<!DOCTYPE note SYSTEM "https://link.example.com/note.dtd">`
+    Doctype,
+
+    /// The value of a xml-attribute is a `javascript:`- or `data:`-uri,
+    /// only reported if [`XmlScrapeConfig::detect_dangerous_schemes`] is set
+    /// or a `data:`-uri is longer than [`XmlScrapeConfig::max_scanned_attribute_length`].
+    /// Unlike [`XmlLinkKind::Attribute`] only the name of the attribute is kept, as its value may be huge.<br/>
+    /// Example: `<a onclick="javascript:alert(1)">`
+    DangerousScheme {
+        scheme: DangerousScheme,
+        /// The name of the attribute containing the uri
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        attribute: OwnedName,
+        /// The element the attribute belongs to
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+        /// The uri was shortened to [`XmlScrapeConfig::max_data_uri_length`]
+        truncated: bool,
+    },
+
+    /// The value of a xml-attribute is a fragment-only or query-only reference, taken as a whole.
+    /// Only reported for the [`XmlScrapeConfig::relative_reference_attributes`].<br/>
+    /// Example: `<a href="#top">` or `<a href="?page=2">`
+    RelativeReference {
+        kind: RelativeReferenceKind,
+        /// The name of the attribute containing the reference
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        attribute: OwnedName,
+        /// The element the attribute belongs to
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+    },
+
+    /// The link is part of the html inside a plaintext portion or CData portion,
+    /// only reported if [`XmlScrapeConfig::parse_embedded_html`] is set.
+    /// The offset of the link is the one of its first occurrence in the text, or 0 if it is not written
+    /// the same way there (e.g. because it contains an html-entity).<br/>
+    /// Example: `<description><![CDATA[<a href="/article">Read more</a>]]></description>`
+    #[cfg(feature = "html")]
+    EmbeddedHtml {
+        /// The elements surrounding the text containing the html
+        parent: ParentInformation,
+        /// Where the link was found inside the html
+        kind: crate::formats::html::HtmlLinkKind,
+    },
 }
 
+/// Information about the elements surrounding a text-node
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParentInformation {
-    pub parent_tag_name: Option<OwnedName>,
+    /// All elements containing the text-node, from the root-element to its direct parent
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::vec_owned_name")
+    )]
+    pub ancestors: Vec<OwnedName>,
 }
 
-#[derive(Debug, Clone)]
+impl ParentInformation {
+    /// The element directly containing the text-node
+    pub fn parent_tag_name(&self) -> Option<&OwnedName> {
+        self.ancestors.last()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XmlLink {
     pub url: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
     pub location: TextPosition,
+    /// The position of the link inside the value it was found in (e.g. the decoded attribute-value,
+    /// text-node or comment), counted in characters and starting at 0.
+    ///
+    /// [`XmlLink::location`] is the position of the event containing the value,
+    /// so this tells apart several links inside the same value. Links to namespaces and values taken as a whole
+    /// (like the system identifier of a doctype) have the offset 0.
+    pub offset_in_value: usize,
     pub kind: XmlLinkKind,
+    /// The `xml:base`-attributes of the element containing the link and its ancestors,
+    /// from the outermost to the innermost one
+    pub xml_base: Vec<String>,
+    /// The link exactly as it is written in the document, e.g. with `&amp;` where [`XmlLink::url`] has a `&`.
+    ///
+    /// Only set if [`XmlScrapeConfig::report_raw`] is, and only for links inside attributes
+    /// (including the declarations of namespaces). `None` for all other links and if the document
+    /// is not encoded in UTF-8.
+    pub raw: Option<String>,
+}
+
+impl Link for XmlLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::TextPosition(self.location)
+    }
+
+    fn bases(&self) -> &[String] {
+        &self.xml_base
+    }
+}
+
+impl XmlLink {
+    /// The name of the attribute containing the link, if it was found inside an attribute
+    pub fn attribute_name(&self) -> Option<&OwnedName> {
+        match &self.kind {
+            XmlLinkKind::Attribute { attribute, .. } => Some(&attribute.name),
+            XmlLinkKind::DangerousScheme { attribute, .. }
+            | XmlLinkKind::RelativeReference { attribute, .. } => Some(attribute),
+            _ => None,
+        }
+    }
+
+    /// The namespace-uri of the attribute containing the link (e.g. `http://www.w3.org/1999/xlink` for `xlink:href`).
+    /// `None` if the link is not inside an attribute or the attribute has no prefix.
+    pub fn attribute_namespace(&self) -> Option<&str> {
+        self.attribute_name()?.namespace.as_deref()
+    }
+
+    /// The prefix of the attribute containing the link as written in the document (e.g. `xlink` for `xlink:href`)
+    pub fn attribute_prefix(&self) -> Option<&str> {
+        self.attribute_name()?.prefix.as_deref()
+    }
 }
 
 impl Display for XmlLink {
@@ -165,80 +1095,640 @@ impl Display for XmlLink {
     }
 }
 
+/// A start-element as passed to the xlink-scraper
+#[cfg(feature = "xlink")]
 pub struct XmlStartElement<'a> {
-    name: &'a OwnedName,
+    _name: &'a OwnedName,
     attributes: &'a Vec<OwnedAttribute>,
     _namespace: &'a Namespace,
+    /// The position of the element, to report where a malformed xlink-element is
+    position: TextPosition,
+}
+
+/// Scrapes all links from href-attributes regardless of their namespace or tag-name.
+///
+/// Use [`scrape_from_href_tags_in_namespace`] to only get the `href`-attributes of one namespace.
+pub fn scrape_from_href_tags(bytes: &[u8]) -> Result<Vec<XmlLink>, XmlScrapingError> {
+    let config = XmlScrapeConfig {
+        attribute_allowlist: Some(["href".to_string()].into()),
+        ..Default::default()
+    };
+    Ok(scrape_with_config(bytes, &config)?
+        .into_iter()
+        .filter(|link| matches!(link.kind, XmlLinkKind::Attribute { .. }))
+        .collect())
+}
+
+/// Like [`scrape_from_href_tags`], but only scrapes the href-attributes with the namespace-uri `namespace`,
+/// or the ones without a prefix if `namespace` is `None`.
+///
+/// The namespace is compared by its uri, so `xlink:href` and `xl:href` match, as long as both prefixes
+/// are bound to the same uri.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::scrape_from_href_tags_in_namespace;
+/// let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:foo="https://foo.example.com/ns">
+///     <a xlink:href="https://xlink.example.com"/>
+///     <a foo:href="https://foo.example.com"/>
+/// </root>"#;
+/// let links = scrape_from_href_tags_in_namespace(xml, Some("http://www.w3.org/1999/xlink")).unwrap();
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].url, "https://xlink.example.com");
+/// assert_eq!(links[0].attribute_prefix(), Some("xlink"));
+/// ```
+pub fn scrape_from_href_tags_in_namespace(
+    bytes: &[u8],
+    namespace: Option<&str>,
+) -> Result<Vec<XmlLink>, XmlScrapingError> {
+    Ok(scrape_from_href_tags(bytes)?
+        .into_iter()
+        .filter(|link| link.attribute_namespace() == namespace)
+        .collect())
 }
 
-#[derive(Debug, Clone)]
-struct NamespaceOccurrence {
-    namespace: String,
-    namespace_uri: String,
-    first_occurrence: TextPosition,
+/// Scrapes the `href` pseudo-attribute and all other urls from a processing instruction
+fn scrape_from_processing_instruction(
+    target: &str,
+    data: Option<&str>,
+    position: TextPosition,
+) -> Vec<ScrapedLink> {
+    let Some(data) = data else { return vec![] };
+    let to_link = |url: &str, offset: usize| ScrapedLink {
+        url: url.to_string(),
+        location: position,
+        offset_in_value: char_offset(data, offset),
+        xml_base: vec![],
+        raw: None,
+        kind: ScrapedLinkKind::Xml(XmlLinkKind::ProcessingInstruction {
+            target: target.to_string(),
+        }),
+    };
+
+    let href = find_pseudo_attribute(data, "href");
+    // Unlike real attributes, the data of a processing instruction is not decoded by the parser
+    let mut links: Vec<ScrapedLink> = href
+        .iter()
+        // `href` is a slice of `data`
+        .map(|href| {
+            to_link(
+                &decode_references(href),
+                href.as_ptr() as usize - data.as_ptr() as usize,
+            )
+        })
+        .collect();
+    links.extend(
+        find_urls(data)
+            .iter()
+            .filter(|link| href != Some(link.as_str()))
+            .map(|link| to_link(link.as_str(), link.start())),
+    );
+    links
 }
 
-impl PartialEq for NamespaceOccurrence {
-    fn eq(&self, other: &Self) -> bool {
-        self.namespace_uri == other.namespace_uri && self.namespace == other.namespace
+/// Scrapes the system identifier and the urls of the public identifier from a doctype-declaration
+fn scrape_from_doctype(doctype: Option<&str>, position: TextPosition) -> Vec<ScrapedLink> {
+    let Some((public_id, system_id)) = doctype.and_then(find_external_id) else {
+        return vec![];
+    };
+    let to_link = |url: &str, offset_in_value: usize| ScrapedLink {
+        url: url.to_string(),
+        location: position,
+        offset_in_value,
+        xml_base: vec![],
+        raw: None,
+        kind: ScrapedLinkKind::Xml(XmlLinkKind::Doctype),
+    };
+
+    let public_id = public_id.unwrap_or_default();
+    let mut links: Vec<ScrapedLink> = find_urls(public_id)
+        .iter()
+        .map(|link| to_link(link.as_str(), char_offset(public_id, link.start())))
+        .collect();
+    links.extend(
+        system_id
+            .filter(|id| !id.is_empty())
+            .map(|id| to_link(id, 0)),
+    );
+    links
+}
+
+/// Finds the public and system identifier of the external DTD inside a doctype-declaration like
+/// `<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd">`
+fn find_external_id(doctype: &str) -> Option<(Option<&str>, Option<&str>)> {
+    let rest = doctype.strip_prefix("<!DOCTYPE")?.trim_start();
+    let rest = rest[rest.find(char::is_whitespace)?..].trim_start();
+    let (public_id, rest) = if let Some(rest) = rest.strip_prefix("PUBLIC") {
+        let (public_id, rest) = split_quoted_literal(rest.trim_start())?;
+        (Some(public_id), rest)
+    } else {
+        (None, rest.strip_prefix("SYSTEM")?)
+    };
+    let system_id = split_quoted_literal(rest.trim_start()).map(|(system_id, _)| system_id);
+    Some((public_id, system_id))
+}
+
+/// Splits a literal in single or double quotes from the start of `text`
+fn split_quoted_literal(text: &str) -> Option<(&str, &str)> {
+    let quote = text.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let end = text[1..].find(quote)? + 1;
+    Some((&text[1..end], &text[end + 1..]))
+}
+
+/// Reads from `inner` and keeps the bytes since the last `<` in `tag`, if there is one.
+///
+/// The parser reads its source byte by byte and no start-tag contains a `<` except the one it starts with,
+/// so `tag` contains exactly the source of a start-tag as soon as the parser returns it.
+struct TagRecordingReader<R> {
+    inner: R,
+    tag: Option<Rc<RefCell<RecordedTag>>>,
+}
+
+impl<R: Read> Read for TagRecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if let Some(tag) = &self.tag {
+            let mut tag = tag.borrow_mut();
+            buf[..read].iter().for_each(|byte| tag.push(*byte));
+        }
+        Ok(read)
     }
 }
 
-/// Scrapes all links from href-attributes regardless of their namespace or tag-name
-pub fn scrape_from_href_tags(bytes: &[u8]) -> Result<Vec<XmlLink>, XmlScrapingError> {
-    let mut collector: Vec<XmlLink> = vec![];
+/// The source of the last start-tag, see [`TagRecordingReader`]
+#[derive(Default)]
+struct RecordedTag {
+    bytes: Vec<u8>,
+    /// The number of bytes read so far
+    offset: usize,
+    /// The encoding chosen by the byte order mark of a document encoded in UTF-16, [None] for UTF-8
+    utf16: Option<Utf16>,
+}
 
-    let mut parser = EventReader::new(bytes);
-    while let Ok(xml_event) = &parser.next() {
-        match xml_event {
-            XmlEvent::StartElement {
-                name: _name,
-                attributes,
-                namespace: _namespace,
-            } => {
-                let mut list: Vec<XmlLink> =
-                    scrape_from_xml_start_element_attributes(attributes, &parser)?
-                        .into_iter()
-                        .filter(|link| {
-                            if let XmlLinkKind::Attribute(att) = &link.kind {
-                                if att.name.local_name == "href" {
-                                    return true;
-                                }
-                            }
-                            return false;
-                        })
-                        .collect();
-                collector.append(&mut list)
+#[derive(Clone, Copy, PartialEq)]
+enum Utf16 {
+    LittleEndian,
+    BigEndian,
+}
+
+impl RecordedTag {
+    fn push(&mut self, byte: u8) {
+        // The second byte of a code unit, the `<` only starts a tag if it is a whole code unit
+        let unit_end = self.offset % 2 == 1;
+        let previous = self.bytes.last().copied();
+        self.offset += 1;
+        match self.utf16 {
+            None if byte == b'<' => self.bytes.clear(),
+            Some(Utf16::LittleEndian) if unit_end && byte == 0 && previous == Some(b'<') => {
+                self.bytes.clear();
+                self.bytes.push(b'<');
+            }
+            Some(Utf16::BigEndian) if unit_end && byte == b'<' && previous == Some(0) => {
+                self.bytes.clear();
+                self.bytes.push(0);
             }
-            XmlEvent::EndDocument => break,
             _ => {}
         }
+        self.bytes.push(byte);
+
+        // The byte order mark comes before the first `<`, so it is still recorded
+        if self.offset == 2 {
+            self.utf16 = match self.bytes[..] {
+                [0xFF, 0xFE] => Some(Utf16::LittleEndian),
+                [0xFE, 0xFF] => Some(Utf16::BigEndian),
+                _ => None,
+            };
+        }
     }
 
-    Ok(collector)
+    /// The recorded source as text, [None] if it is not valid in the encoding of the document
+    fn text(&self) -> Option<Cow<'_, str>> {
+        let Some(utf16) = self.utf16 else {
+            return std::str::from_utf8(&self.bytes).ok().map(Cow::Borrowed);
+        };
+        let units = self.bytes.chunks_exact(2).map(|unit| match utf16 {
+            Utf16::LittleEndian => u16::from_le_bytes([unit[0], unit[1]]),
+            Utf16::BigEndian => u16::from_be_bytes([unit[0], unit[1]]),
+        });
+        char::decode_utf16(units)
+            .collect::<Result<String, _>>()
+            .ok()
+            .map(Cow::Owned)
+    }
 }
 
-fn scrape_from_xml_start_element_attributes<R>(
-    attributes: &Vec<OwnedAttribute>,
-    parser: &EventReader<R>,
-) -> Result<Vec<XmlLink>, XmlScrapingError>
-where
-    R: Read,
-{
-    let mut ret: Vec<XmlLink> = vec![];
-    for attribute in attributes {
-        let mut links = find_urls(&attribute.value)
+/// The qualified names and values of all attributes of a start-tag like `<a href="a.html?b=1&amp;c=2">`,
+/// as they are written in the source
+fn raw_attribute_values(tag: &str) -> Vec<(&str, &str)> {
+    let mut values = vec![];
+    let Some(name_end) = tag.find(|c: char| c.is_whitespace() || c == '/' || c == '>') else {
+        return values;
+    };
+    let mut rest = &tag[name_end..];
+    while let Some(equals) = rest.find('=') {
+        let name = rest[..equals].trim();
+        rest = rest[equals + 1..].trim_start();
+        let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(length) = rest[1..].find(quote) else {
+            break;
+        };
+        values.push((name, &rest[1..=length]));
+        rest = &rest[length + 2..];
+    }
+    values
+}
+
+/// The source of a link inside an attribute-value or a namespace-declaration of the current start-tag
+fn find_raw(link: &ScrapedLink, raw_values: &[(&str, &str)]) -> Option<String> {
+    let raw_value = |name: String| {
+        raw_values
             .iter()
-            .map(|link| XmlLink {
-                url: link.as_str().to_string(),
-                location: parser.position(),
-                kind: XmlLinkKind::Attribute(attribute.clone()),
+            .find(|(raw_name, _)| *raw_name == name)
+            .map(|(_, value)| *value)
+    };
+    let (raw, value) = match &link.kind {
+        ScrapedLinkKind::Xml(XmlLinkKind::Attribute { attribute, .. }) => {
+            let name = match &attribute.name.prefix {
+                Some(prefix) => format!("{prefix}:{}", attribute.name.local_name),
+                None => attribute.name.local_name.clone(),
+            };
+            (raw_value(name)?, attribute.value.as_str())
+        }
+        ScrapedLinkKind::Xml(XmlLinkKind::NameSpace(prefix)) => {
+            let name = match prefix.as_str() {
+                "" => "xmlns".to_string(),
+                prefix => format!("xmlns:{prefix}"),
+            };
+            (raw_value(name)?, link.url.as_str())
+        }
+        _ => return None,
+    };
+    let start = value
+        .char_indices()
+        .nth(link.offset_in_value)
+        .map_or(value.len(), |(start, _)| start);
+    if !value[start..].starts_with(&link.url) {
+        return None;
+    }
+    raw_range(raw, value, start..start + link.url.len()).map(str::to_string)
+}
+
+/// The part of the `raw` attribute-value, that the parser decoded to `value[range]`.
+///
+/// `None` if `raw` does not decode to `value`, e.g. because it contains an entity declared in the doctype.
+fn raw_range<'a>(raw: &'a str, value: &str, range: std::ops::Range<usize>) -> Option<&'a str> {
+    let mut decoded = String::with_capacity(value.len());
+    let (mut raw_start, mut raw_end) = (None, None);
+    let mut rest = raw;
+    loop {
+        let raw_position = raw.len() - rest.len();
+        if decoded.len() == range.start && raw_start.is_none() {
+            raw_start = Some(raw_position);
+        }
+        if decoded.len() == range.end && raw_end.is_none() {
+            raw_end = Some(raw_position);
+        }
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        let (character, length) = match c {
+            '&' => decode_reference(rest)?,
+            // Line-breaks and tabs are normalized to spaces, a `\r\n` to a single one
+            '\r' if rest.starts_with("\r\n") => (' ', 2),
+            '\t' | '\n' | '\r' => (' ', 1),
+            c => (c, c.len_utf8()),
+        };
+        decoded.push(character);
+        rest = &rest[length..];
+    }
+    (decoded == value).then(|| Some(&raw[raw_start?..raw_end?]))?
+}
+
+/// Replaces the predefined entities (like `&amp;`) and character references (like `&#38;`) in `value`
+///
+/// Unknown or malformed references are kept as they are.
+fn decode_references(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match decode_reference(rest) {
+            Some((character, length)) => {
+                decoded.push(character);
+                rest = &rest[length..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+/// Decodes the entity or character reference at the start of `text`,
+/// returns the character and the length of the reference
+fn decode_reference(text: &str) -> Option<(char, usize)> {
+    let end = text.find(';')?;
+    let character = match &text[1..end] {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        reference => reference
+            .strip_prefix("#x")
+            .map(|hex| u32::from_str_radix(hex, 16))
+            .or_else(|| reference.strip_prefix('#').map(str::parse))
+            .and_then(Result::ok)
+            .and_then(char::from_u32),
+    };
+    character.map(|character| (character, end + 1))
+}
+
+/// The value of the `xml:base`-attribute, if there is one
+pub(crate) fn find_xml_base(attributes: &[OwnedAttribute]) -> Option<String> {
+    attributes
+        .iter()
+        .find(|attribute| {
+            attribute.name.prefix.as_deref() == Some("xml") && attribute.name.local_name == "base"
+        })
+        .map(|attribute| attribute.value.clone())
+}
+
+/// Finds the value of a pseudo-attribute like `href="style.xsl"` inside the data of a processing instruction
+fn find_pseudo_attribute<'a>(data: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = data;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)? + 1;
+        if key == name {
+            return Some(&value[1..end]);
+        }
+        rest = &value[end + 1..];
+    }
+    None
+}
+
+/// Elements with at least this many attributes are scraped in parallel, if the feature `rayon` is enabled
+#[cfg(feature = "rayon")]
+const PARALLEL_ATTRIBUTES_THRESHOLD: usize = 64;
+
+/// Adds the links inside the `attributes` of the element `name` to `found`.
+///
+/// Takes the attributes by value, so the last link found inside an attribute can take it instead of a clone.
+/// The attributes of elements with many of them are scraped in parallel with the feature `rayon`
+/// (see [`PARALLEL_ATTRIBUTES_THRESHOLD`]), the links are in the order of the attributes either way.
+fn scrape_from_xml_start_element_attributes(
+    name: &OwnedName,
+    attributes: Vec<OwnedAttribute>,
+    position: TextPosition,
+    options: &ScrapeOptions,
+    found: &mut Vec<ScrapedLink>,
+) {
+    #[cfg(feature = "rayon")]
+    if attributes.len() >= PARALLEL_ATTRIBUTES_THRESHOLD {
+        use rayon::prelude::*;
+        let links: Vec<Vec<ScrapedLink>> = attributes
+            .into_par_iter()
+            .map(|attribute| {
+                let mut links = vec![];
+                scrape_from_xml_attribute(name, attribute, position, options, &mut links);
+                links
             })
             .collect();
+        found.extend(links.into_iter().flatten());
+        return;
+    }
+    for attribute in attributes {
+        scrape_from_xml_attribute(name, attribute, position, options, found);
+    }
+}
+
+/// Adds the links inside `attribute` of the element `name` to `found`
+fn scrape_from_xml_attribute(
+    name: &OwnedName,
+    attribute: OwnedAttribute,
+    position: TextPosition,
+    options: &ScrapeOptions,
+    found: &mut Vec<ScrapedLink>,
+) {
+    let local_name = attribute.name.local_name.as_str();
+    if options
+        .config
+        .is_some_and(|config| !config.is_attribute_scanned(local_name))
+    {
+        return;
+    }
+
+    if let Some(config) = options.config.filter(|it| it.detect_dangerous_schemes) {
+        if let Some(link) = scrape_dangerous_scheme(
+            name,
+            attribute.name.clone(),
+            &attribute.value,
+            position,
+            config,
+        ) {
+            found.push(link);
+            return;
+        }
+    }
+
+    if options.css_attributes.contains(&local_name) {
+        found.extend(scrape_style_urls(&attribute.value, name, position));
+        return;
+    }
+
+    if options
+        .reference_elements
+        .contains(&name.local_name.as_str())
+        && is_href(&attribute.name)
+    {
+        found.push(ScrapedLink {
+            url: attribute.value,
+            location: position,
+            offset_in_value: 0,
+            xml_base: vec![],
+            raw: None,
+            kind: ScrapedLinkKind::Reference {
+                element: name.clone(),
+            },
+        });
+        return;
+    }
 
-        ret.append(&mut links);
+    if let Some(config) = options.config {
+        if config.relative_reference_attributes.contains(local_name) {
+            if let Some(kind) = RelativeReferenceKind::of(&attribute.value) {
+                let trimmed = attribute.value.trim();
+                found.push(ScrapedLink {
+                    url: trimmed.to_string(),
+                    location: position,
+                    offset_in_value: char_offset(
+                        &attribute.value,
+                        attribute.value.len() - attribute.value.trim_start().len(),
+                    ),
+                    xml_base: vec![],
+                    raw: None,
+                    kind: ScrapedLinkKind::Xml(XmlLinkKind::RelativeReference {
+                        kind,
+                        attribute: attribute.name,
+                        element: name.clone(),
+                    }),
+                });
+                return;
+            }
+        }
     }
-    Ok(ret)
+
+    if options.collect_ids && is_href(&attribute.name) && attribute.value.starts_with('#') {
+        found.push(ScrapedLink {
+            url: attribute.value.clone(),
+            location: position,
+            offset_in_value: 0,
+            xml_base: vec![],
+            raw: None,
+            kind: ScrapedLinkKind::Xml(XmlLinkKind::Attribute {
+                attribute,
+                element: name.clone(),
+            }),
+        });
+        return;
+    }
+
+    if let Some(config) = options.config {
+        if config
+            .max_scanned_attribute_length
+            .is_some_and(|max_length| attribute.value.len() > max_length)
+        {
+            if DangerousScheme::of(&attribute.value) == Some(DangerousScheme::Data) {
+                found.extend(scrape_dangerous_scheme(
+                    name,
+                    attribute.name,
+                    &attribute.value,
+                    position,
+                    config,
+                ));
+            }
+            return;
+        }
+    }
+
+    // The candidates of a srcset are taken as is, so relative ones are found and no descriptor is glued on
+    let mut urls = if local_name == "srcset" {
+        find_srcset_urls(&attribute.value)
+    } else {
+        find_urls(&attribute.value)
+    };
+    let Some(last) = urls.pop() else {
+        return;
+    };
+    let attribute_link = |url: UrlMatch, attribute: OwnedAttribute| ScrapedLink {
+        url: url.as_str().to_string(),
+        location: position,
+        offset_in_value: char_offset(&attribute.value, url.start()),
+        xml_base: vec![],
+        raw: None,
+        kind: ScrapedLinkKind::Xml(XmlLinkKind::Attribute {
+            attribute,
+            element: name.clone(),
+        }),
+    };
+    found.extend(
+        urls.into_iter()
+            .map(|url| attribute_link(url, attribute.clone())),
+    );
+    found.push(attribute_link(last, attribute));
+}
+
+/// Reports `value` as a whole, if it is a `javascript:`- or `data:`-uri
+fn scrape_dangerous_scheme(
+    element: &OwnedName,
+    attribute: OwnedName,
+    value: &str,
+    position: TextPosition,
+    config: &XmlScrapeConfig,
+) -> Option<ScrapedLink> {
+    let trimmed = value.trim();
+    let scheme = DangerousScheme::of(trimmed)?;
+    let offset_in_value = char_offset(value, value.len() - value.trim_start().len());
+    let value = trimmed;
+    let end = match (scheme, config.max_data_uri_length) {
+        (DangerousScheme::Data, Some(max_length)) => {
+            value.char_indices().nth(max_length).map(|(end, _)| end)
+        }
+        _ => None,
+    };
+    Some(ScrapedLink {
+        url: value[..end.unwrap_or(value.len())].to_string(),
+        location: position,
+        offset_in_value,
+        xml_base: vec![],
+        raw: None,
+        kind: ScrapedLinkKind::Xml(XmlLinkKind::DangerousScheme {
+            scheme,
+            attribute,
+            element: element.clone(),
+            truncated: end.is_some(),
+        }),
+    })
+}
+
+/// The namespaces declared by an element, that are not bound the same way in the scope of its `parent`.
+/// A prefix rebound to another uri is a new declaration, the predefined namespaces are declared by the root-element.
+fn declared_namespaces<'n>(
+    namespace: &'n Namespace,
+    parent: Option<&'n Namespace>,
+) -> impl Iterator<Item = (&'n String, &'n String)> {
+    namespace.0.iter().filter(move |(prefix, uri)| {
+        parent.and_then(|parent| parent.get(prefix.as_str())) != Some(uri.as_str())
+    })
+}
+
+/// Converts a byte-offset inside `value` to the number of characters before it
+fn char_offset(value: &str, byte_offset: usize) -> usize {
+    value[..byte_offset].chars().count()
+}
+
+/// Checks if `name` is `href` without a namespace or `xlink:href`
+fn is_href(name: &OwnedName) -> bool {
+    name.local_name == "href"
+        && name
+            .namespace
+            .as_deref()
+            .is_none_or(|namespace| namespace == "http://www.w3.org/1999/xlink")
+}
+
+/// Checks if `name` is `id` without a namespace or `xml:id`
+fn is_id(name: &OwnedName) -> bool {
+    name.local_name == "id" && (name.namespace.is_none() || name.prefix.as_deref() == Some("xml"))
+}
+
+/// Scrapes all css-references except fragment-only ones from `css`
+fn scrape_style_urls(css: &str, element: &OwnedName, position: TextPosition) -> Vec<ScrapedLink> {
+    find_css_urls(css)
+        .iter()
+        .filter(|link| !link.as_str().starts_with('#'))
+        .map(|link| ScrapedLink {
+            url: link.as_str().to_string(),
+            location: position,
+            offset_in_value: char_offset(css, link.start()),
+            xml_base: vec![],
+            raw: None,
+            kind: ScrapedLinkKind::Css {
+                element: element.clone(),
+            },
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -246,13 +1736,779 @@ mod tests {
     use super::*;
 
     const TEST_XML: &[u8] = include_bytes!("../../../test_files/xml/xml_test.xml");
+    const TEST_XML_STYLESHEET: &[u8] =
+        include_bytes!("../../../test_files/xml/xml_stylesheet_test.xml");
+    const TEST_XML_TRUNCATED: &[u8] =
+        include_bytes!("../../../test_files/xml/xml_truncated_test.xml");
+    const TEST_XML_BOM: &[u8] = include_bytes!("../../../test_files/xml/xml_bom_test.xml");
 
     #[test]
     fn scrape_hrefs_test() {
         let links = scrape_from_href_tags(TEST_XML).unwrap();
         println!("{:?}", links);
         assert!(links.iter().any(|it| it.url == "https://attribute.test.com"
-            && matches!(it.kind, XmlLinkKind::Attribute(_))));
+            && matches!(it.kind, XmlLinkKind::Attribute { .. })));
+    }
+
+    #[test]
+    fn scrape_hrefs_in_namespace_test() {
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:foo="https://foo.test.com/ns" xmlns:xl="http://www.w3.org/1999/xlink">
+            <a xlink:href="https://xlink.test.com"/>
+            <a foo:href="https://foo.test.com"/>
+            <a href="https://plain.test.com"/>
+            <a xl:href="https://xl.test.com"/>
+        </root>"#;
+        let urls = |namespace| -> Vec<String> {
+            scrape_from_href_tags_in_namespace(&xml[..], namespace)
+                .unwrap()
+                .into_iter()
+                .map(|it| it.url)
+                .collect()
+        };
+        assert_eq!(
+            urls(Some("http://www.w3.org/1999/xlink")),
+            vec!["https://xlink.test.com", "https://xl.test.com"]
+        );
+        assert_eq!(
+            urls(Some("https://foo.test.com/ns")),
+            vec!["https://foo.test.com"]
+        );
+        assert_eq!(urls(None), vec!["https://plain.test.com"]);
+        assert_eq!(scrape_from_href_tags(&xml[..]).unwrap().len(), 4);
+
+        let links = scrape_from_href_tags(&xml[..]).unwrap();
+        assert_eq!(
+            links[1].attribute_namespace(),
+            Some("https://foo.test.com/ns")
+        );
+        assert_eq!(links[1].attribute_prefix(), Some("foo"));
+        assert_eq!(links[2].attribute_namespace(), None);
+        assert_eq!(links[2].attribute_name().unwrap().local_name, "href");
+    }
+
+    #[test]
+    fn scrape_attribute_element_test() {
+        let xml = br#"<page><img src="https://img.test.com/a.png"/><link href="https://link.test.com/a.css"/></page>"#;
+        let elements: Vec<(String, String, String)> = scrape_from_href_tags(&xml[..])
+            .unwrap()
+            .into_iter()
+            .chain(scrape(&xml[..]).unwrap())
+            .filter_map(|it| match it.kind {
+                XmlLinkKind::Attribute { attribute, element } => {
+                    Some((it.url, element.local_name, attribute.name.local_name))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            elements,
+            vec![
+                (
+                    "https://link.test.com/a.css".to_string(),
+                    "link".to_string(),
+                    "href".to_string()
+                ),
+                (
+                    "https://img.test.com/a.png".to_string(),
+                    "img".to_string(),
+                    "src".to_string()
+                ),
+                (
+                    "https://link.test.com/a.css".to_string(),
+                    "link".to_string(),
+                    "href".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn scrape_from_file_async_test() {
+        let links = scrape_from_file_async("test_files/xml/xml_test.xml")
+            .await
+            .unwrap();
+        assert_eq!(links, scrape(TEST_XML).unwrap());
+
+        let result = scrape_from_file_async("test_files/xml/xml_truncated_test.xml").await;
+        assert!(matches!(result, Err(XmlScrapingError::XmlReaderError(_))));
+    }
+
+    /// An endless document, that fails the test if it is read too far
+    struct EndlessXml {
+        written: usize,
+        pending: Vec<u8>,
+    }
+
+    impl Read for EndlessXml {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.pending.is_empty() {
+                assert!(self.written < 1000, "the document was read too far");
+                self.pending = if self.written == 0 {
+                    br#"<root xmlns="https://ns.test.com">"#.to_vec()
+                } else {
+                    format!(r#"<a href="https://{}.test.com"/>"#, self.written).into_bytes()
+                };
+                self.written += 1;
+            }
+            let length = buf.len().min(self.pending.len());
+            buf[..length].copy_from_slice(&self.pending[..length]);
+            self.pending.drain(..length);
+            Ok(length)
+        }
+    }
+
+    #[test]
+    fn scrape_iter_is_lazy_test() {
+        let reader = EndlessXml {
+            written: 0,
+            pending: vec![],
+        };
+        let urls: Vec<String> = scrape_iter(reader)
+            .take(6)
+            .map(|it| it.unwrap().url)
+            .collect();
+        // The namespaces are yielded first, including the implicit xml and xmlns namespaces
+        assert_eq!(urls[0], "https://ns.test.com");
+        assert_eq!(
+            urls[3..],
+            [
+                "https://1.test.com",
+                "https://2.test.com",
+                "https://3.test.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_iter_test() {
+        let mut iterated: Vec<XmlLink> = scrape_iter(TEST_XML).map(Result::unwrap).collect();
+        let mut collected = scrape(TEST_XML).unwrap();
+        assert_eq!(iterated.len(), collected.len());
+        let key = |link: &XmlLink| (link.location.row, link.location.column, link.url.clone());
+        iterated.sort_by_key(key);
+        collected.sort_by_key(key);
+        assert_eq!(iterated, collected);
+
+        let mut truncated = scrape_iter(TEST_XML_TRUNCATED);
+        assert!(truncated.any(|it| matches!(it, Err(XmlScrapingError::XmlReaderError(_)))));
+        assert!(truncated.next().is_none());
+    }
+
+    #[test]
+    fn scrape_with_test() {
+        let mut visited = vec![];
+        scrape_with(TEST_XML, |link| {
+            visited.push(link);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        let iterated: Vec<XmlLink> = scrape_iter(TEST_XML).map(Result::unwrap).collect();
+        assert_eq!(visited, iterated);
+    }
+
+    #[test]
+    fn find_first_link_test() {
+        for xml in [TEST_XML, TEST_XML_STYLESHEET] {
+            assert_eq!(
+                find_first_link(xml).unwrap(),
+                scrape(xml).unwrap().into_iter().next()
+            );
+        }
+        // The document is malformed after the first links, but it is never read that far
+        let first = find_first_link(TEST_XML_TRUNCATED).unwrap().unwrap();
+        assert_eq!(first.url, "https://attribute.test.com");
+        assert!(scrape(TEST_XML_TRUNCATED).is_err());
+    }
+
+    #[test]
+    fn scrape_with_break_test() {
+        let mut calls = 0;
+        let mut first = None;
+        // The document is malformed after the first links, but it is never read that far
+        scrape_with(TEST_XML_TRUNCATED, |link| {
+            calls += 1;
+            if link.url == "https://attribute.test.com" {
+                first = Some(link);
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+        assert!(matches!(first.unwrap().kind, XmlLinkKind::Attribute { .. }));
+        // Only the implicit xml- and xmlns-namespaces come before the attribute
+        assert_eq!(calls, 3);
+
+        let result = scrape_with(TEST_XML_TRUNCATED, |_| ControlFlow::Continue(()));
+        assert!(matches!(result, Err(XmlScrapingError::XmlReaderError(_))));
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn parse_embedded_html_test() {
+        use crate::formats::html::HtmlLinkKind;
+        let xml = br#"<rss><item>
+            <description><![CDATA[<p>Read <a href="/articles/1">https://text.test.com</a></p>]]></description>
+            <summary>&lt;img src="images/cover.png"&gt; on https://summary.test.com</summary>
+        </item></rss>"#;
+        let config = XmlScrapeConfig {
+            parse_embedded_html: true,
+            ..Default::default()
+        };
+        let summary = |links: Vec<XmlLink>| -> Vec<(String, Option<HtmlLinkKind>)> {
+            links
+                .into_iter()
+                .filter(|it| !matches!(it.kind, XmlLinkKind::NameSpace(_)))
+                .map(|it| match it.kind {
+                    XmlLinkKind::EmbeddedHtml { kind, .. } => (it.url, Some(kind)),
+                    _ => (it.url, None),
+                })
+                .collect()
+        };
+        let attribute = |element: &str, attribute: &str| {
+            Some(HtmlLinkKind::Attribute {
+                element: element.to_string(),
+                attribute: attribute.to_string(),
+            })
+        };
+        assert_eq!(
+            summary(scrape_with_config(&xml[..], &config).unwrap()),
+            vec![
+                ("https://text.test.com".to_string(), None),
+                ("/articles/1".to_string(), attribute("a", "href")),
+                ("https://summary.test.com".to_string(), None),
+                ("images/cover.png".to_string(), attribute("img", "src")),
+            ]
+        );
+        assert_eq!(
+            summary(scrape(&xml[..]).unwrap()),
+            vec![
+                ("https://text.test.com".to_string(), None),
+                ("https://summary.test.com".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn count_links_test() {
+        let files: [&[u8]; 4] = [
+            TEST_XML,
+            TEST_XML_STYLESHEET,
+            include_bytes!("../../../test_files/xml/svg_test.svg"),
+            include_bytes!("../../../test_files/xml/xlink_test.xml"),
+        ];
+        for xml in files {
+            assert_eq!(count_links(xml).unwrap(), scrape(xml).unwrap().len());
+        }
+        assert!(count_links(TEST_XML_TRUNCATED).is_err());
+    }
+
+    #[test]
+    fn scrape_limited_test() {
+        let xml = format!(
+            "<root>{}</root>",
+            (1..=100)
+                .map(|it| format!(r#"<a href="https://{it}.test.com"/>"#))
+                .collect::<String>()
+        );
+        let limited = scrape_limited(xml.as_bytes(), 10).unwrap();
+        assert!(limited.truncated);
+        assert_eq!(limited.links.len(), 10);
+        // The implicit xml- and xmlns-namespaces come first
+        assert_eq!(limited.links[2].url, "https://1.test.com");
+        assert_eq!(limited.links[9].url, "https://8.test.com");
+
+        let limited = scrape_limited(xml.as_bytes(), 102).unwrap();
+        assert!(!limited.truncated);
+        assert_eq!(limited.links.len(), 102);
+    }
+
+    #[test]
+    fn scrape_with_options_test() {
+        use crate::options::ScrapeOptions;
+        let xml = br#"<root xmlns="http://ns.test.com"><a href="ftp://files.test.com">https://a.test.com</a><!-- http://comment.test.com https://b.test.com --><b>https://c.test.com</b></root>"#;
+        let options = ScrapeOptions::new().allowed_schemes(["https"]).max_links(2);
+        let urls: Vec<String> = scrape_with_options(&xml[..], &options)
+            .unwrap()
+            .into_iter()
+            .map(|it| it.url)
+            .collect();
+        // The cap counts only the links kept by the scheme filter
+        assert_eq!(urls, vec!["https://a.test.com", "https://b.test.com"]);
+    }
+
+    #[test]
+    fn scrape_with_url_transform_test() {
+        use crate::options::ScrapeOptions;
+        let xml = br#"<root><a href="http://a.test.com">See http://b.test.com and https://c.test.com</a><!-- http://comment.test.com --></root>"#;
+        let options =
+            ScrapeOptions::new().transform_urls(|url| match url.strip_prefix("http://") {
+                Some(rest) => format!("https://{rest}"),
+                None => url.to_string(),
+            });
+        let transformed = scrape_with_options(&xml[..], &options).unwrap();
+        let expected: Vec<XmlLink> = scrape(&xml[..])
+            .unwrap()
+            .into_iter()
+            .map(|mut it| {
+                it.url = it.url.replacen("http://", "https://", 1);
+                it
+            })
+            .collect();
+        // Only the urls are changed, the positions and kinds are the same
+        assert_eq!(transformed, expected);
+        assert!(transformed.iter().all(|it| it.url.starts_with("https://")));
+    }
+
+    #[test]
+    fn scrape_srcset_test() {
+        let xml = br#"<html xmlns="http://www.w3.org/1999/xhtml"><img srcset="https://cdn.test.com/small.jpg 480w, https://cdn.test.com/medium,crop.jpg 800w,images/large.jpg 1200w"/></html>"#;
+        let links: Vec<(String, usize)> = scrape(&xml[..])
+            .unwrap()
+            .into_iter()
+            .filter(|it| it.attribute_name().is_some())
+            .map(|it| (it.url, it.offset_in_value))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                ("https://cdn.test.com/small.jpg".to_string(), 0),
+                ("https://cdn.test.com/medium,crop.jpg".to_string(), 37),
+                ("images/large.jpg".to_string(), 79),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_raw_test() {
+        let xml = b"<root xmlns:t=\"https://ns.test.com/?a=1&amp;b=2\">\n<a href='https://a.test.com/?a=1&amp;b=&#x32;'\n   title=\"https://b.test.com/&lt;x&gt; and https://c.test.com\">https://text.test.com/?a=1&amp;b=2</a></root>";
+        let config = XmlScrapeConfig {
+            report_raw: true,
+            ..Default::default()
+        };
+        assert!(scrape(&xml[..]).unwrap().iter().all(|it| it.raw.is_none()));
+        let links: Vec<(String, Option<String>)> = scrape_with_config(&xml[..], &config)
+            .unwrap()
+            .into_iter()
+            .map(|it| (it.url, it.raw))
+            .collect();
+        let link = |url: &str, raw: Option<&str>| (url.to_string(), raw.map(str::to_string));
+        assert_eq!(
+            links,
+            vec![
+                link(
+                    "https://a.test.com/?a=1&b=2",
+                    Some("https://a.test.com/?a=1&amp;b=&#x32;")
+                ),
+                link("https://b.test.com/", Some("https://b.test.com/")),
+                link("https://c.test.com", Some("https://c.test.com")),
+                link("https://text.test.com/?a=1&b=2", None),
+                link(
+                    "https://ns.test.com/?a=1&b=2",
+                    Some("https://ns.test.com/?a=1&amp;b=2")
+                ),
+                link("http://www.w3.org/XML/1998/namespace", None),
+                link("http://www.w3.org/2000/xmlns/", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_with_byte_order_mark_test() {
+        assert!(TEST_XML_BOM.starts_with(&[0xEF, 0xBB, 0xBF]));
+        let summary = |links: Vec<XmlLink>| -> Vec<(String, u64, u64, Option<String>)> {
+            links
+                .into_iter()
+                .map(|it| (it.url, it.location.row, it.location.column, it.raw))
+                .collect()
+        };
+        let config = XmlScrapeConfig {
+            report_raw: true,
+            ..Default::default()
+        };
+        let links = summary(scrape_with_config(TEST_XML_BOM, &config).unwrap());
+        let link = |url: &str, row: u64, column: u64, raw: Option<&str>| {
+            (url.to_string(), row, column, raw.map(str::to_string))
+        };
+        assert_eq!(
+            links,
+            vec![
+                link(
+                    "https://item.test.com/?a=1&b=2",
+                    2,
+                    2,
+                    Some("https://item.test.com/?a=1&amp;b=2")
+                ),
+                link("https://export.test.com", 2, 50, None),
+                link("https://comment.test.com", 3, 2, None),
+                link(
+                    "https://ns.test.com/catalog",
+                    1,
+                    0,
+                    Some("https://ns.test.com/catalog")
+                ),
+                link("http://www.w3.org/XML/1998/namespace", 1, 0, None),
+                link("http://www.w3.org/2000/xmlns/", 1, 0, None),
+            ]
+        );
+
+        // The positions are the same as in the document without the byte order mark
+        let without_bom = summary(scrape_with_config(&TEST_XML_BOM[3..], &config).unwrap());
+        assert_eq!(links, without_bom);
+
+        let text = std::str::from_utf8(&TEST_XML_BOM[3..])
+            .unwrap()
+            .replace("encoding=\"UTF-8\"", "encoding=\"UTF-16\"");
+        let mut little_endian = vec![0xFF, 0xFE];
+        let mut big_endian = vec![0xFE, 0xFF];
+        for unit in text.encode_utf16() {
+            little_endian.extend_from_slice(&unit.to_le_bytes());
+            big_endian.extend_from_slice(&unit.to_be_bytes());
+        }
+        assert_eq!(
+            summary(scrape_with_config(&little_endian[..], &config).unwrap()),
+            links
+        );
+        assert_eq!(
+            summary(scrape_with_config(&big_endian[..], &config).unwrap()),
+            links
+        );
+    }
+
+    #[test]
+    fn scrape_many_attributes_in_order_test() {
+        // More than PARALLEL_ATTRIBUTES_THRESHOLD, so the parallel path is taken with the feature `rayon`
+        let mut xml = String::from("<config><entry");
+        for i in 0..500 {
+            xml.push_str(&format!(
+                r#" option-{i}="https://{i}.test.com/a https://{i}.test.com/b" plain-{i}="no link""#
+            ));
+        }
+        xml.push_str(r#" style="https://last.test.com"/></config>"#);
+
+        let links = scrape(xml.as_bytes()).unwrap();
+        let attribute_links: Vec<(String, String)> = links
+            .into_iter()
+            .filter_map(|it| match it.kind {
+                XmlLinkKind::Attribute { attribute, .. } => {
+                    Some((attribute.name.local_name, it.url))
+                }
+                _ => None,
+            })
+            .collect();
+        let mut expected: Vec<(String, String)> = (0..500)
+            .flat_map(|i| {
+                ["a", "b"].map(|path| {
+                    (
+                        format!("option-{i}"),
+                        format!("https://{i}.test.com/{path}"),
+                    )
+                })
+            })
+            .collect();
+        expected.push(("style".to_string(), "https://last.test.com".to_string()));
+        assert_eq!(attribute_links, expected);
+    }
+
+    #[test]
+    fn scrape_within_test() {
+        let xml = br#"<doc xmlns:dc="https://ns.test.com/dc">
+            <header><link href="https://header.test.com"/>https://boilerplate.test.com</header>
+            <metadata source="https://metadata.test.com">
+                <dc:source>https://source.test.com</dc:source>
+                <metadata><!-- https://nested.test.com --></metadata>
+                https://after-nested.test.com
+            </metadata>
+            <footer>https://footer.test.com</footer>
+            <metadata xmlns:x="https://ns.test.com/x">https://second.test.com</metadata>
+        </doc>"#;
+        let urls: Vec<String> = scrape_within(&xml[..], |name| name.local_name == "metadata")
+            .unwrap()
+            .into_iter()
+            .map(|it| it.url)
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://metadata.test.com",
+                "https://source.test.com",
+                "https://nested.test.com",
+                "https://after-nested.test.com",
+                "https://second.test.com",
+                "https://ns.test.com/x",
+            ]
+        );
+        assert!(scrape_within(&xml[..], |name| name.local_name == "missing")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn scrape_with_allowlist_test() {
+        let config = XmlScrapeConfig {
+            attribute_allowlist: Some(["href".to_string(), "src".to_string()].into()),
+            ..Default::default()
+        };
+        let xml = br#"<root><a href="https://href.test.com" data-analytics="https://tracker.test.com"/><img src="https://src.test.com"/>https://text.test.com</root>"#;
+        let urls: Vec<String> = scrape_with_config(&xml[..], &config)
+            .unwrap()
+            .into_iter()
+            .filter(|it| !matches!(it.kind, XmlLinkKind::NameSpace(_)))
+            .map(|it| it.url)
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://href.test.com",
+                "https://src.test.com",
+                "https://text.test.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_all_namespaces_test() {
+        let xml = br#"<root xmlns="legacy/schema" xmlns:ex="urn:example" xmlns:web="https://ns.test.com"/>"#;
+        let namespaces = |config: &XmlScrapeConfig| -> Vec<(String, String)> {
+            scrape_with_config(&xml[..], config)
+                .unwrap()
+                .into_iter()
+                .filter_map(|it| match it.kind {
+                    XmlLinkKind::NameSpace(prefix) if prefix != "xml" && prefix != "xmlns" => {
+                        Some((prefix, it.url))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            namespaces(&XmlScrapeConfig::default()),
+            vec![("web".to_string(), "https://ns.test.com".to_string())]
+        );
+        let mut all = namespaces(&XmlScrapeConfig {
+            include_all_namespaces: true,
+            ..Default::default()
+        });
+        all.sort();
+        assert_eq!(
+            all,
+            vec![
+                ("".to_string(), "legacy/schema".to_string()),
+                ("ex".to_string(), "urn:example".to_string()),
+                ("web".to_string(), "https://ns.test.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrape_rebound_namespace_prefix_test() {
+        let xml = br#"<root xmlns:a="https://one.test.com">
+    <a:x/>
+    <child xmlns:a="https://two.test.com" href="https://child.test.com"><a:y/></child>
+    <a:z/>
+    <other xmlns:a="https://one.test.com"/>
+</root>"#;
+        let links = scrape(&xml[..]).unwrap();
+        let namespaces: Vec<(&str, &str, TextPosition)> = links
+            .iter()
+            .filter_map(|it| match &it.kind {
+                XmlLinkKind::NameSpace(prefix) if prefix == "a" => {
+                    Some((prefix.as_str(), it.url.as_str(), it.location))
+                }
+                _ => None,
+            })
+            .collect();
+        let child = links
+            .iter()
+            .find(|it| it.url == "https://child.test.com")
+            .unwrap();
+        // The rebinding is reported at the child, the siblings after it are bound to the first uri again
+        assert_eq!(
+            namespaces,
+            vec![
+                (
+                    "a",
+                    "https://one.test.com",
+                    TextPosition { row: 0, column: 36 }
+                ),
+                ("a", "https://two.test.com", child.location),
+            ]
+        );
+        assert_eq!(child.location.row, 2);
+    }
+
+    #[test]
+    fn namespace_dedup_test() {
+        let xml =
+            br#"<root xmlns:a="https://ns.test.com"><child xmlns:b="https://ns.test.com"/></root>"#;
+        let prefixes = |namespace_dedup: NamespaceDedup| -> Vec<String> {
+            let config = XmlScrapeConfig {
+                namespace_dedup,
+                ..Default::default()
+            };
+            scrape_with_config(&xml[..], &config)
+                .unwrap()
+                .into_iter()
+                .filter_map(|it| match it.kind {
+                    XmlLinkKind::NameSpace(prefix) if it.url == "https://ns.test.com" => {
+                        Some(prefix)
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(prefixes(NamespaceDedup::ByPrefix), vec!["a", "b"]);
+        assert_eq!(prefixes(NamespaceDedup::ByUri), vec!["a"]);
+    }
+
+    #[test]
+    fn detect_dangerous_schemes_test() {
+        let xml = br#"<root><button onclick=" JavaScript:window.open('https://popup.test.com')">Open</button><a href="data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciLz4=">Logo</a></root>"#;
+        let dangerous = |config: &XmlScrapeConfig| -> Vec<(String, DangerousScheme, String, bool)> {
+            scrape_with_config(&xml[..], config)
+                .unwrap()
+                .into_iter()
+                .filter_map(|it| {
+                    let attribute = it.attribute_name()?.local_name.clone();
+                    match it.kind {
+                        XmlLinkKind::DangerousScheme {
+                            scheme, truncated, ..
+                        } => Some((it.url, scheme, attribute, truncated)),
+                        _ => None,
+                    }
+                })
+                .collect()
+        };
+        assert!(dangerous(&XmlScrapeConfig::default()).is_empty());
+
+        let mut config = XmlScrapeConfig {
+            detect_dangerous_schemes: true,
+            ..Default::default()
+        };
+        let found = dangerous(&config);
+        println!("{:?}", found);
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "JavaScript:window.open('https://popup.test.com')".to_string(),
+                    DangerousScheme::JavaScript,
+                    "onclick".to_string(),
+                    false
+                ),
+                (
+                    "data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciLz4=".to_string(),
+                    DangerousScheme::Data,
+                    "href".to_string(),
+                    false
+                ),
+            ]
+        );
+
+        config.max_data_uri_length = Some(26);
+        let found = dangerous(&config);
+        assert!(!found[0].3);
+        assert_eq!(
+            (found[1].0.as_str(), found[1].3),
+            ("data:image/svg+xml;base64,", true)
+        );
+    }
+
+    #[test]
+    fn scrape_relative_references_test() {
+        let xml = br##"<root><a href="#top">Back to #top</a><a href=" ?page=2"/><a href="https://test.com/page" title="#title"/></root>"##;
+        let relative = |config: &XmlScrapeConfig| -> Vec<(String, RelativeReferenceKind, usize)> {
+            scrape_with_config(&xml[..], config)
+                .unwrap()
+                .into_iter()
+                .filter_map(|it| match it.kind {
+                    XmlLinkKind::RelativeReference {
+                        kind, attribute, ..
+                    } => {
+                        assert_eq!(attribute.local_name, "href");
+                        Some((it.url, kind, it.offset_in_value))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        assert!(relative(&XmlScrapeConfig::default()).is_empty());
+
+        let config = XmlScrapeConfig {
+            relative_reference_attributes: ["href".to_string()].into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            relative(&config),
+            vec![
+                ("#top".to_string(), RelativeReferenceKind::Fragment, 0),
+                ("?page=2".to_string(), RelativeReferenceKind::Query, 1),
+            ]
+        );
+        // Text-nodes and the other attributes are scanned as before
+        let urls: Vec<String> = scrape_with_config(&xml[..], &config)
+            .unwrap()
+            .into_iter()
+            .filter(|it| !matches!(it.kind, XmlLinkKind::NameSpace(_)))
+            .map(|it| it.url)
+            .collect();
+        assert_eq!(urls, vec!["#top", "?page=2", "https://test.com/page"]);
+    }
+
+    #[test]
+    fn skip_scanning_long_attributes_test() {
+        // A 1MB data-uri, its content looks like it contains urls
+        let mut data_uri = "data:text/plain,".to_string();
+        while data_uri.len() < 1024 * 1024 {
+            data_uri.push_str("see https://inner.test.com or www.inner.test.com ");
+        }
+        let xml = format!(
+            r#"<root><a href="{data_uri}"/><a title="{}"/><a href="https://short.test.com"/></root>"#,
+            data_uri.replace("data:", "text:")
+        );
+        let config = XmlScrapeConfig {
+            max_scanned_attribute_length: Some(1024),
+            max_data_uri_length: Some(15),
+            ..Default::default()
+        };
+        let links: Vec<XmlLink> = scrape_with_config(xml.as_bytes(), &config)
+            .unwrap()
+            .into_iter()
+            .filter(|it| !matches!(it.kind, XmlLinkKind::NameSpace(_)))
+            .collect();
+        println!("{:?}", links);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].url, "data:text/plain");
+        assert!(matches!(
+            links[0].kind,
+            XmlLinkKind::DangerousScheme {
+                scheme: DangerousScheme::Data,
+                truncated: true,
+                ..
+            }
+        ));
+        assert_eq!(links[1].url, "https://short.test.com");
+    }
+
+    #[test]
+    fn scrape_with_denylist_test() {
+        let config = XmlScrapeConfig {
+            attribute_denylist: ["data-analytics".to_string()].into(),
+            ..Default::default()
+        };
+        let xml = br#"<root><a href="https://href.test.com" data-analytics="https://tracker.test.com"/><img src="https://src.test.com"/></root>"#;
+        let urls: Vec<String> = scrape_with_config(&xml[..], &config)
+            .unwrap()
+            .into_iter()
+            .filter(|it| !matches!(it.kind, XmlLinkKind::NameSpace(_)))
+            .map(|it| it.url)
+            .collect();
+        assert_eq!(urls, vec!["https://href.test.com", "https://src.test.com"]);
+        assert!(scrape_with_config(&xml[..], &Default::default())
+            .unwrap()
+            .iter()
+            .any(|it| it.url == "https://tracker.test.com"));
     }
 
     #[test]
@@ -260,9 +2516,12 @@ mod tests {
         let links = scrape(TEST_XML).unwrap();
         println!("{:?}", links);
         assert!(links.iter().any(|it| it.url == "https://attribute.test.com"
-            && matches!(it.kind, XmlLinkKind::Attribute(_))));
+            && matches!(it.kind, XmlLinkKind::Attribute { .. })));
         assert!(links.iter().any(|it| it.url == "https://plaintext.test.com"
             && matches!(it.kind, XmlLinkKind::PlainText(_))));
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://comment.test.com" && it.kind == XmlLinkKind::Comment));
         assert!(links.iter().any(
             |it| it.url == "https://cdata.test.com" && matches!(it.kind, XmlLinkKind::CData(_))
         ));
@@ -271,4 +2530,220 @@ mod tests {
             .any(|it| it.url == "http://www.w3.org/XML/1998/namespace"
                 && matches!(it.kind, XmlLinkKind::NameSpace(_))));
     }
+
+    #[test]
+    fn scrape_entity_references_test() {
+        let xml = br#"<?xml-stylesheet href="https://pi.test.com/style.xsl?a=1&amp;b=2"?><a href="https://x.test.com/a?b=1&amp;c=2&#38;d=3&#x26;e=4">see https://y.test.com/?f=5&amp;g=6</a>"#;
+        let links = scrape(&xml[..]).unwrap();
+        println!("{:?}", links);
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://x.test.com/a?b=1&c=2&d=3&e=4"
+                && matches!(it.kind, XmlLinkKind::Attribute { .. })));
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://y.test.com/?f=5&g=6"
+                && matches!(it.kind, XmlLinkKind::PlainText(_))));
+        let processing_instruction_urls: Vec<&str> = links
+            .iter()
+            .filter(|it| matches!(it.kind, XmlLinkKind::ProcessingInstruction { .. }))
+            .map(|it| it.url.as_str())
+            .collect();
+        assert_eq!(
+            processing_instruction_urls,
+            vec!["https://pi.test.com/style.xsl?a=1&b=2"]
+        );
+    }
+
+    #[test]
+    fn fail_on_truncated_xml_test() {
+        let result = scrape(TEST_XML_TRUNCATED);
+        assert!(matches!(result, Err(XmlScrapingError::XmlReaderError(_))));
+
+        let result = scrape_from_href_tags(TEST_XML_TRUNCATED);
+        assert!(matches!(result, Err(XmlScrapingError::XmlReaderError(_))));
+    }
+
+    #[test]
+    fn scrape_lenient_truncated_xml_test() {
+        let links = scrape_lenient(TEST_XML_TRUNCATED).unwrap();
+        println!("{:?}", links);
+        assert!(links.iter().any(|it| it.url == "https://attribute.test.com"
+            && matches!(it.kind, XmlLinkKind::Attribute { .. })));
+        assert!(links.iter().any(|it| it.url == "https://plaintext.test.com"
+            && matches!(it.kind, XmlLinkKind::PlainText(_))));
+    }
+
+    #[test]
+    fn find_dangling_fragments_test() {
+        let xml = br##"<doc xmlns:xlink="http://www.w3.org/1999/xlink">
+            <toc>
+                <a href="#section-1">First</a>
+                <a xlink:href="#section-3">Third</a>
+                <a href="https://external.test.com#section-3">External</a>
+            </toc>
+            <section xml:id="section-1"/>
+            <section id="section-2"/>
+        </doc>"##;
+        let result = scrape_with_ids(&xml[..]).unwrap();
+        assert_eq!(
+            result.ids,
+            HashSet::from(["section-1".to_string(), "section-2".to_string()])
+        );
+        assert!(result.links.iter().any(|it| it.url == "#section-1"));
+        let dangling = find_dangling_fragments(&result);
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].url, "#section-3");
+        assert_eq!(dangling[0].location.row, 3);
+
+        // The fragment-only references are only reported, if the ids are collected
+        assert!(scrape(&xml[..])
+            .unwrap()
+            .iter()
+            .all(|it| !it.url.starts_with('#')));
+    }
+
+    #[test]
+    fn scrape_with_diagnostics_test() {
+        let (links, warnings) = scrape_with_diagnostics(TEST_XML_TRUNCATED).unwrap();
+        assert_eq!(links, scrape_lenient(TEST_XML_TRUNCATED).unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].kind,
+            ScrapeWarningKind::MalformedXml(_)
+        ));
+
+        let (links, warnings) = scrape_with_diagnostics(TEST_XML).unwrap();
+        assert_eq!(links, scrape(TEST_XML).unwrap());
+        assert!(warnings.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_XML).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<XmlLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+
+    #[test]
+    fn scrape_processing_instruction_test() {
+        let links = scrape(TEST_XML_STYLESHEET).unwrap();
+        println!("{:?}", links);
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://stylesheet.test.com/style.xsl"
+                && it.kind
+                    == XmlLinkKind::ProcessingInstruction {
+                        target: "xml-stylesheet".to_string()
+                    }));
+        assert!(links.iter().any(|it| it.url == "relative.css"
+            && matches!(&it.kind, XmlLinkKind::ProcessingInstruction { target } if target == "xml-stylesheet")));
+        assert!(links.iter().any(|it| it.url == "https://pi.test.com"
+            && matches!(&it.kind, XmlLinkKind::ProcessingInstruction { target } if target == "custom-pi")));
+    }
+
+    #[test]
+    fn decode_references_test() {
+        assert_eq!(
+            decode_references("a?b=1&amp;c=2&#38;d=3&#x26;e=4"),
+            "a?b=1&c=2&d=3&e=4"
+        );
+        assert_eq!(
+            decode_references("&lt;&unknown;&#xZZ;&"),
+            "<&unknown;&#xZZ;&"
+        );
+        assert!(matches!(decode_references("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn scrape_doctype_test() {
+        let xml = br#"<?xml version="1.0"?>
+<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd">
+<html xmlns="http://www.w3.org/1999/xhtml"><body>https://body.test.com</body></html>"#;
+        let links = scrape(&xml[..]).unwrap();
+        println!("{:?}", links);
+        let doctype_urls: Vec<&str> = links
+            .iter()
+            .filter(|it| it.kind == XmlLinkKind::Doctype)
+            .map(|it| it.url.as_str())
+            .collect();
+        assert_eq!(
+            doctype_urls,
+            vec!["http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd"]
+        );
+
+        let xml =
+            br#"<!DOCTYPE note SYSTEM 'note.dtd' [<!ENTITY e "https://entity.test.com">]><note/>"#;
+        let links = scrape(&xml[..]).unwrap();
+        let doctype_urls: Vec<&str> = links
+            .iter()
+            .filter(|it| it.kind == XmlLinkKind::Doctype)
+            .map(|it| it.url.as_str())
+            .collect();
+        assert_eq!(doctype_urls, vec!["note.dtd"]);
+    }
+
+    #[test]
+    fn find_external_id_test() {
+        assert_eq!(
+            find_external_id(r#"<!DOCTYPE a PUBLIC "-//https://public.test.com//EN" "b.dtd">"#),
+            Some((Some("-//https://public.test.com//EN"), Some("b.dtd")))
+        );
+        assert_eq!(
+            find_external_id(r#"<!DOCTYPE a SYSTEM "b.dtd">"#),
+            Some((None, Some("b.dtd")))
+        );
+        assert_eq!(find_external_id("<!DOCTYPE a [<!ELEMENT a ANY>]>"), None);
+        assert_eq!(find_external_id("<!DOCTYPE a>"), None);
+    }
+
+    #[test]
+    fn find_pseudo_attribute_test() {
+        let data = r#"type="text/xsl" href='style.xsl' title="x""#;
+        assert_eq!(find_pseudo_attribute(data, "href"), Some("style.xsl"));
+        assert_eq!(find_pseudo_attribute(data, "type"), Some("text/xsl"));
+        assert_eq!(find_pseudo_attribute(data, "media"), None);
+    }
+
+    #[test]
+    fn offset_in_value_test() {
+        let xml = "<a data-links=\"mirrors: https://one.test.com and https://two.test.com\">ü https://text.test.com</a>";
+        let links = scrape(xml.as_bytes()).unwrap();
+        let found: Vec<(&str, usize)> = links
+            .iter()
+            .filter(|it| !matches!(it.kind, XmlLinkKind::NameSpace(_)))
+            .map(|it| (it.url.as_str(), it.offset_in_value))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://one.test.com", 9),
+                ("https://two.test.com", 34),
+                ("https://text.test.com", 2),
+            ]
+        );
+        assert_eq!(links[0].location, links[1].location);
+    }
+
+    #[test]
+    fn scrape_ancestors_test() {
+        let xml = b"<a><b>text with https://x.com</b><c/>after https://y.com</a>";
+        let links = scrape(&xml[..]).unwrap();
+        println!("{:?}", links);
+
+        let ancestor_names = |url: &str| -> Vec<String> {
+            let link = links.iter().find(|it| it.url == url).unwrap();
+            let XmlLinkKind::PlainText(info) = &link.kind else {
+                panic!("Expected a plaintext link")
+            };
+            info.ancestors
+                .iter()
+                .map(|name| name.local_name.clone())
+                .collect()
+        };
+        assert_eq!(ancestor_names("https://x.com"), vec!["a", "b"]);
+        assert_eq!(ancestor_names("https://y.com"), vec!["a"]);
+    }
 }
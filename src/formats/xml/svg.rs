@@ -1,33 +1,139 @@
-use crate::formats::xml::svg::SvgLinkKind::{Attribute, Comment, NameSpace, Script, Text};
-use crate::formats::xml::XmlLinkKind;
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use crate::formats::xml::svg::SvgLinkKind::{
+    Attribute, Comment, Doctype, Href, Image, NameSpace, ProcessingInstruction, Script, StyleUrl,
+    Text, Use,
+};
+use crate::formats::xml::{
+    DangerousScheme, RelativeReferenceKind, ScrapeOptions, ScrapedLinkKind, XmlLinkKind,
+    XmlScrapeConfig,
+};
+use crate::link::{Link, Location};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
 use std::fmt::{Display, Formatter};
 use std::io::Read;
 use thiserror::Error;
 use xml::attribute::OwnedAttribute;
 use xml::common::TextPosition;
+use xml::name::OwnedName;
 
+/// Scrapes links from a svg-file.
+///
+/// In addition to [`crate::formats::xml::scrape`], this finds css-references in `style`-attributes,
+/// presentation-attributes like `fill` and `<style>`-elements, even if they are relative links.
+/// The `href` or `xlink:href` of elements like `<use>`, `<image>`, `<a>` or `<linearGradient>`
+/// is taken as is as well, see [`SvgLinkKind::Use`], [`SvgLinkKind::Image`] and [`SvgLinkKind::Href`].
 pub fn scrape<R>(reader: R) -> Result<Vec<SvgLink>, SvgScrapingError>
 where
     R: Read,
 {
-    Ok(crate::formats::xml::scrape(reader)?
+    scrape_internal(reader, None)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<SvgLink>, SvgScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<SvgLink>, SvgScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<SvgLink>, SvgScrapingError>);
+
+/// Like [`scrape`], but only scans the attributes allowed by `config` for urls,
+/// see [`crate::formats::xml::scrape_with_config`].
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::svg::{scrape_with_config, SvgLinkKind};
+/// use link_scraper::formats::xml::{DangerousScheme, XmlScrapeConfig};
+/// let config = XmlScrapeConfig {
+///     detect_dangerous_schemes: true,
+///     ..Default::default()
+/// };
+/// let svg = r#"<svg><a href="javascript:alert(1)"><text>Click</text></a></svg>"#;
+/// let links = scrape_with_config(svg.as_bytes(), &config).unwrap();
+/// assert_eq!(links[0].url, "javascript:alert(1)");
+/// assert!(matches!(links[0].kind, SvgLinkKind::DangerousScheme { scheme: DangerousScheme::JavaScript, .. }));
+/// ```
+pub fn scrape_with_config<R>(
+    reader: R,
+    config: &XmlScrapeConfig,
+) -> Result<Vec<SvgLink>, SvgScrapingError>
+where
+    R: Read,
+{
+    scrape_internal(reader, Some(config))
+}
+
+fn scrape_internal<R>(
+    reader: R,
+    config: Option<&XmlScrapeConfig>,
+) -> Result<Vec<SvgLink>, SvgScrapingError>
+where
+    R: Read,
+{
+    let options = ScrapeOptions {
+        css_attributes: &CSS_ATTRIBUTES,
+        css_elements: &["style"],
+        reference_elements: &HREF_ELEMENTS,
+        config,
+        ..Default::default()
+    };
+    Ok(crate::formats::xml::scrape_internal(reader, &options)?
         .into_iter()
         .map(|link| SvgLink {
-            url: link.url,
             location: link.location,
+            offset_in_value: link.offset_in_value,
             kind: match link.kind {
-                XmlLinkKind::Attribute(attribute) => Attribute(attribute),
-                XmlLinkKind::Comment => Comment,
-                XmlLinkKind::PlainText(_) => Text,
-                XmlLinkKind::CData(_) => Script,
-                XmlLinkKind::NameSpace(ns) => NameSpace(ns),
+                ScrapedLinkKind::Xml(kind) => kind.into(),
+                ScrapedLinkKind::Css { element } => StyleUrl { element },
+                ScrapedLinkKind::Reference { element } if element.local_name == "use" => Use {
+                    fragment: link
+                        .url
+                        .split_once('#')
+                        .map(|(_, fragment)| fragment.to_string()),
+                    local: link.url.starts_with('#'),
+                },
+                ScrapedLinkKind::Reference { element } if element.local_name == "image" => Image,
+                ScrapedLinkKind::Reference { element } => Href { element },
             },
+            url: link.url,
+            xml_base: link.xml_base,
         })
         .collect())
 }
-gen_scrape_from_file!(scrape(Read) -> Result<Vec<SvgLink>, SvgScrapingError>);
-gen_scrape_from_slice!(scrape(Read) -> Result<Vec<SvgLink>, SvgScrapingError>);
+
+/// The `style`-attribute and the presentation-attributes, that may reference other files by a css `url()`
+const CSS_ATTRIBUTES: [&str; 10] = [
+    "style",
+    "fill",
+    "stroke",
+    "mask",
+    "clip-path",
+    "filter",
+    "marker-start",
+    "marker-mid",
+    "marker-end",
+    "cursor",
+];
+
+/// The elements of SVG 1.1 and SVG 2, whose `href` or `xlink:href` references another resource or element
+const HREF_ELEMENTS: [&str; 23] = [
+    "a",
+    "altGlyph",
+    "animate",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "color-profile",
+    "cursor",
+    "discard",
+    "feImage",
+    "filter",
+    "font-face-uri",
+    "glyphRef",
+    "image",
+    "linearGradient",
+    "mpath",
+    "pattern",
+    "radialGradient",
+    "script",
+    "set",
+    "textPath",
+    "tref",
+    "use",
+];
 
 #[derive(Error, Debug)]
 pub enum SvgScrapingError {
@@ -37,18 +143,39 @@ pub enum SvgScrapingError {
     XmlScrapingError(#[from] crate::formats::xml::XmlScrapingError),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SvgLink {
     pub url: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
     pub location: TextPosition,
+    /// The position of the link inside the value it was found in, see [`crate::formats::xml::XmlLink::offset_in_value`]
+    pub offset_in_value: usize,
     pub kind: SvgLinkKind,
+    /// The `xml:base`-attributes of the element containing the link and its ancestors,
+    /// from the outermost to the innermost one
+    pub xml_base: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SvgLinkKind {
     /// The link is inside a xml-attribute <br/>
     /// Example: `<a href="https://link.example.com">`
-    Attribute(OwnedAttribute),
+    Attribute {
+        /// The attribute containing the link
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serde_helpers::OwnedAttributeDef")
+        )]
+        attribute: OwnedAttribute,
+        /// The element the attribute belongs to
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+    },
     /// The link is inside a xml-comment <br/>
     /// Example: `<!--Just a comment with a link to https://link.example.com-->`
     Comment,
@@ -67,6 +194,121 @@ pub enum SvgLinkKind {
     /// This link is a reference to a xml-namespace<br/>
     /// Example: `<root xmlns="https://link.example.com">`
     NameSpace(String),
+    /// The link is referenced by a css `url()` or `@import` in a `style`-attribute,
+    /// a presentation-attribute (e.g. `fill`, `mask` or `marker-end`) or a `<style>`-element.
+    /// Fragment-only references like `url(#gradient)` are skipped.<br/>
+    /// Example: `<rect style="fill: url(https://link.example.com/pattern.svg#p)"/>`
+    StyleUrl {
+        /// The element with the attribute or the `<style>`-element
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+    },
+    /// The link is the `href` or `xlink:href` of a `<use>`-element, taken as is even if it is relative<br/>
+    /// Example: `<use xlink:href="sprite.svg#icon"/>`
+    Use {
+        /// The part after the `#`, like `icon` for `sprite.svg#icon`
+        fragment: Option<String>,
+        /// The link only consists of a fragment (like `#icon`), so it references an element of the same file
+        /// instead of an external resource
+        local: bool,
+    },
+    /// The link is the `href` or `xlink:href` of an `<image>`-element, taken as is even if it is relative<br/>
+    /// Example: `<image href="photo.png"/>`
+    Image,
+    /// The link is the `href` or `xlink:href` of any other element that references a resource or an element,
+    /// like `<a>`, `<linearGradient>`, `<textPath>` or `<script>`, taken as is even if it is relative<br/>
+    /// Example: `<a xlink:href="about.html">`
+    Href {
+        /// The element the attribute belongs to
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+    },
+    /// The link is inside a processing instruction<br/>
+    /// Example: `<?xml-stylesheet type="text/css" href="https://link.example.com/style.css"?>`
+    ProcessingInstruction { target: String },
+    /// The link is the system identifier of the external DTD, or inside its public identifier<br/>
+    /// Example: `<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">`
+    Doctype,
+    /// The value of an attribute is a `javascript:`- or `data:`-uri, see [`XmlLinkKind::DangerousScheme`]<br/>
+    /// Example: `<image href="data:image/png;base64,iVBORw0KGgo="/>`
+    DangerousScheme {
+        scheme: DangerousScheme,
+        /// The name of the attribute containing the uri
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        attribute: OwnedName,
+        /// The element the attribute belongs to
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+        /// The uri was shortened to [`XmlScrapeConfig::max_data_uri_length`]
+        truncated: bool,
+    },
+    /// The value of an attribute is a fragment-only or query-only reference, see [`XmlLinkKind::RelativeReference`]<br/>
+    /// Example: `<g data-target="#layer"/>`
+    RelativeReference {
+        kind: RelativeReferenceKind,
+        /// The name of the attribute containing the reference
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        attribute: OwnedName,
+        /// The element the attribute belongs to
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::OwnedNameDef"))]
+        element: OwnedName,
+    },
+}
+
+/// Text inside svg-files is only ever displayed text, and CData-sections are only used for scripts,
+/// so those kinds are renamed accordingly.
+impl From<XmlLinkKind> for SvgLinkKind {
+    fn from(kind: XmlLinkKind) -> Self {
+        match kind {
+            XmlLinkKind::Attribute { attribute, element } => Attribute { attribute, element },
+            XmlLinkKind::Comment => Comment,
+            XmlLinkKind::PlainText(_) => Text,
+            #[cfg(feature = "html")]
+            XmlLinkKind::EmbeddedHtml { .. } => Text,
+            XmlLinkKind::CData(_) => Script,
+            XmlLinkKind::NameSpace(ns) => NameSpace(ns),
+            XmlLinkKind::ProcessingInstruction { target } => ProcessingInstruction { target },
+            XmlLinkKind::Doctype => Doctype,
+            XmlLinkKind::DangerousScheme {
+                scheme,
+                attribute,
+                element,
+                truncated,
+            } => SvgLinkKind::DangerousScheme {
+                scheme,
+                attribute,
+                element,
+                truncated,
+            },
+            XmlLinkKind::RelativeReference {
+                kind,
+                attribute,
+                element,
+            } => SvgLinkKind::RelativeReference {
+                kind,
+                attribute,
+                element,
+            },
+        }
+    }
+}
+
+impl Link for SvgLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::TextPosition(self.location)
+    }
+
+    fn bases(&self) -> &[String] {
+        &self.xml_base
+    }
 }
 
 impl Display for SvgLink {
@@ -75,12 +317,6 @@ impl Display for SvgLink {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct SvgLinkLocation {
-    pub file: String,
-    pub position: TextPosition,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +334,209 @@ mod tests {
             .iter()
             .any(|it| it.url == "http://www.w3.org/2000/svg" && matches!(it.kind, NameSpace(_))));
     }
+
+    #[test]
+    fn scrape_svg_from_slice_test() {
+        let links = scrape_from_slice(TEST_SVG).unwrap();
+        println!("{:?}", links);
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://cdata.test.com/insideACodeSnippet"
+                && matches!(it.kind, Script)));
+    }
+
+    #[test]
+    fn svg_link_kind_mapping_test() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg">
+            <!-- https://comment.test.com -->
+            <a href="https://href.test.com" data-link="https://attribute.test.com"><text>https://text.test.com</text></a>
+            <script><![CDATA[ var link = "https://script.test.com"; ]]></script>
+        </svg>"#;
+        let links = scrape(&svg[..]).unwrap();
+        println!("{:?}", links);
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://comment.test.com" && matches!(it.kind, Comment)));
+        assert!(links.iter().any(
+            |it| it.url == "https://attribute.test.com" && matches!(it.kind, Attribute { .. })
+        ));
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://href.test.com" && matches!(it.kind, Href { .. })));
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://text.test.com" && matches!(it.kind, Text)));
+        assert!(links
+            .iter()
+            .any(|it| it.url == "https://script.test.com" && matches!(it.kind, Script)));
+    }
+
+    #[test]
+    fn detect_dangerous_schemes_test() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg">
+            <image href="data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciLz4="/>
+            <rect onclick="javascript:alert(document.domain)"/>
+        </svg>"#;
+        let config = XmlScrapeConfig {
+            detect_dangerous_schemes: true,
+            max_data_uri_length: Some(32),
+            ..Default::default()
+        };
+        let links = scrape_with_config(&svg[..], &config).unwrap();
+        println!("{:?}", links);
+        let dangerous: Vec<(&str, DangerousScheme, &str, bool)> = links
+            .iter()
+            .filter_map(|it| match &it.kind {
+                SvgLinkKind::DangerousScheme {
+                    scheme,
+                    element,
+                    truncated,
+                    ..
+                } => Some((
+                    it.url.as_str(),
+                    *scheme,
+                    element.local_name.as_str(),
+                    *truncated,
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            dangerous,
+            vec![
+                (
+                    "data:image/svg+xml;base64,PHN2Zy",
+                    DangerousScheme::Data,
+                    "image",
+                    true
+                ),
+                (
+                    "javascript:alert(document.domain)",
+                    DangerousScheme::JavaScript,
+                    "rect",
+                    false
+                ),
+            ]
+        );
+        // Without the flag the data-uri of the image is reported as a plain reference
+        assert!(scrape(&svg[..])
+            .unwrap()
+            .iter()
+            .any(|it| it.url.starts_with("data:") && matches!(it.kind, Image)));
+    }
+
+    #[test]
+    fn scrape_style_urls_test() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg">
+            <style><![CDATA[ @import url('theme.css'); ]]></style>
+            <rect fill="url(#grad)" style="background:url(https://cdn.test.com/bg.png); fill: url(#grad)"/>
+            <path mask="url(masks.svg#m)" marker-end="url('https://cdn.test.com/markers.svg#arrow')"/>
+        </svg>"#;
+        let links = scrape(&svg[..]).unwrap();
+        println!("{:?}", links);
+        let style_urls: Vec<(&str, &str)> = links
+            .iter()
+            .filter_map(|it| match &it.kind {
+                StyleUrl { element } => Some((it.url.as_str(), element.local_name.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            style_urls,
+            vec![
+                ("theme.css", "style"),
+                ("https://cdn.test.com/bg.png", "rect"),
+                ("masks.svg#m", "path"),
+                ("https://cdn.test.com/markers.svg#arrow", "path"),
+            ]
+        );
+        assert_eq!(
+            links.iter().filter(|it| it.url.contains("bg.png")).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn scrape_use_and_image_test() {
+        let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+            <use xlink:href="sprite.svg#icon"/>
+            <use href="#grad"/>
+            <image href="photo.png"/>
+            <rect data-href="https://attribute.test.com"/>
+        </svg>"##;
+        let links = scrape(&svg[..]).unwrap();
+        println!("{:?}", links);
+        let references: Vec<(&str, &SvgLinkKind)> = links
+            .iter()
+            .filter(|it| matches!(it.kind, Use { .. } | Image))
+            .map(|it| (it.url.as_str(), &it.kind))
+            .collect();
+        assert_eq!(
+            references,
+            vec![
+                (
+                    "sprite.svg#icon",
+                    &Use {
+                        fragment: Some("icon".to_string()),
+                        local: false
+                    }
+                ),
+                (
+                    "#grad",
+                    &Use {
+                        fragment: Some("grad".to_string()),
+                        local: true
+                    }
+                ),
+                ("photo.png", &Image),
+            ]
+        );
+        assert!(links.iter().any(
+            |it| it.url == "https://attribute.test.com" && matches!(it.kind, Attribute { .. })
+        ));
+    }
+
+    #[test]
+    fn scrape_relative_xlink_href_test() {
+        let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+            <linearGradient id="fade" xlink:href="gradients.svg#base"/>
+            <a xlink:href="about.html"><text>About</text></a>
+            <use xlink:href="icon.svg"/>
+            <text><textPath href="#curve">Curved</textPath></text>
+        </svg>"##;
+        let links = scrape(&svg[..]).unwrap();
+        println!("{:?}", links);
+        let references: Vec<(&str, &str)> = links
+            .iter()
+            .filter_map(|it| match &it.kind {
+                Href { element } => Some((it.url.as_str(), element.local_name.as_str())),
+                Use { .. } => Some((it.url.as_str(), "use")),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            references,
+            vec![
+                ("gradients.svg#base", "linearGradient"),
+                ("about.html", "a"),
+                ("icon.svg", "use"),
+                ("#curve", "textPath"),
+            ]
+        );
+        assert!(links.iter().any(|it| it.url == "icon.svg"
+            && it.kind
+                == Use {
+                    fragment: None,
+                    local: false
+                }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_SVG).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<SvgLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
 }
@@ -1,5 +1,6 @@
 use crate::formats::xml::xlink::{get_xlink_attribute_value, XLinkFormatError};
 use crate::formats::xml::XmlStartElement;
+use xml::common::TextPosition;
 
 #[derive(Debug)]
 enum XLinkType {
@@ -11,10 +12,9 @@ enum XLinkType {
     Title,
 }
 
-impl TryFrom<&str> for XLinkType {
-    type Error = XLinkFormatError;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl XLinkType {
+    /// Parses the value of the `xlink:type`-attribute of the element at `position`
+    fn parse(value: &str, position: TextPosition) -> Result<Self, XLinkFormatError> {
         match value {
             "simple" => Ok(XLinkType::Simple),
             "extended" => Ok(XLinkType::Extended),
@@ -22,7 +22,10 @@ impl TryFrom<&str> for XLinkType {
             "arc" => Ok(XLinkType::Arc),
             "resource" => Ok(XLinkType::Resource),
             "title" => Ok(XLinkType::Title),
-            _ => Err(XLinkFormatError::UnknownTypeError(value.to_string())),
+            _ => Err(XLinkFormatError::UnknownTypeError(
+                value.to_string(),
+                position,
+            )),
         }
     }
 }
@@ -42,7 +45,7 @@ impl<'a> XlinkElement<'a> {
     ) -> Result<Option<Self>, XLinkFormatError> {
         let xlink_href = get_xlink_attribute_value("href", xml_start_element.attributes);
         let mut xlink_type_option = get_xlink_attribute_value("type", xml_start_element.attributes)
-            .map(|type_value| XLinkType::try_from(type_value.as_str()))
+            .map(|type_value| XLinkType::parse(&type_value, xml_start_element.position))
             .transpose()?;
         if xlink_href.is_some() && xlink_type_option.is_none() {
             xlink_type_option = Some(XLinkType::Simple);
@@ -124,7 +127,10 @@ impl<'a> TryFrom<XmlStartElement<'a>> for XlinkLocatorElement<'a> {
     fn try_from(xml_start_element: XmlStartElement<'a>) -> Result<Self, Self::Error> {
         Ok(XlinkLocatorElement {
             href: get_xlink_attribute_value("href", xml_start_element.attributes).ok_or(
-                XLinkFormatError::MissingRequiredAttributeError("href".to_string()),
+                XLinkFormatError::MissingRequiredAttributeError(
+                    "href".to_string(),
+                    xml_start_element.position,
+                ),
             )?,
             role: get_xlink_attribute_value("role", xml_start_element.attributes),
             title: get_xlink_attribute_value("title", xml_start_element.attributes),
@@ -7,74 +7,425 @@ use crate::formats::xml::xlink::XLinkFormatError::{
     ArcOutsideOfExtendedError, ExtendedInsideOfExtendedError, LocatorOutsideOfExtendedError,
     ResourceOutsideOfExtendedError, SimpleInsideOfExtendedError,
 };
-use crate::formats::xml::XmlStartElement;
-use crate::helpers::find_urls;
-use crate::{gen_scrape_from_file, gen_scrape_from_slice};
+use crate::formats::xml::{find_xml_base, ScrapeWarning, ScrapeWarningKind, XmlStartElement};
+use crate::helpers::{find_urls, scheme_of};
+use crate::link::{Limited, Link, Location};
+#[cfg(feature = "url")]
+use crate::processing::{resolve_links, Url};
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
 use itertools::Itertools;
 use std::io::Read;
+use std::ops::ControlFlow;
 use thiserror::Error;
 use xml::attribute::OwnedAttribute;
 use xml::common::{Position, TextPosition};
 use xml::reader::XmlEvent;
 use xml::EventReader;
 
+/// Scrapes links from xlink-elements.
+///
+/// Returns an error as soon as the document turns out to be malformed.
+/// Use [`scrape_lenient`] if you want to keep the links found up to that point instead.
 pub fn scrape<R>(reader: R) -> Result<Vec<XLinkLink>, XLinkFormatError>
 where
     R: Read,
 {
-    let mut collector: Vec<XLinkLink> = vec![];
+    scrape_internal(
+        reader,
+        &XLinkScrapeConfig::default(),
+        ScrapeOptions::new(ScrapeMode::Strict),
+    )
+    .map(|it| it.links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<XLinkLink>, XLinkFormatError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<XLinkLink>, XLinkFormatError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<XLinkLink>, XLinkFormatError>);
+
+/// Like [`scrape`], but stops at the first malformed xml-token and returns
+/// all links found up to that point instead of an error.
+///
+/// Violations of the xlink-format are still reported as errors.
+pub fn scrape_lenient<R>(reader: R) -> Result<Vec<XLinkLink>, XLinkFormatError>
+where
+    R: Read,
+{
+    scrape_internal(
+        reader,
+        &XLinkScrapeConfig::default(),
+        ScrapeOptions::new(ScrapeMode::Lenient),
+    )
+    .map(|it| it.links)
+}
+
+/// Like [`scrape_lenient`], but reports the recoverable issues of the document as [`ScrapeWarning`]s:
+/// the malformed xml-token it stopped at, and the locator-, arc- and resource-elements outside of an extended element,
+/// which are skipped instead of failing.
+///
+/// All other violations of the xlink-format are still reported as errors.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::xlink::scrape_with_diagnostics;
+/// use link_scraper::formats::xml::ScrapeWarningKind;
+/// let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+///     <loc xlink:type="locator" xlink:href="https://orphan.example.com"/>
+///     <a xlink:type="simple" xlink:href="https://a.example.com"/>
+/// </root>"#;
+/// let (links, warnings) = scrape_with_diagnostics(&xml[..]).unwrap();
+/// assert_eq!(links[0].url, "https://a.example.com");
+/// assert_eq!(warnings[0].kind, ScrapeWarningKind::LocatorOutsideOfExtended);
+/// ```
+pub fn scrape_with_diagnostics<R>(
+    reader: R,
+) -> Result<(Vec<XLinkLink>, Vec<ScrapeWarning>), XLinkFormatError>
+where
+    R: Read,
+{
+    let mut warnings = vec![];
+    let links = scrape_internal(
+        reader,
+        &XLinkScrapeConfig::default(),
+        ScrapeOptions::new(ScrapeMode::CollectWarnings(&mut warnings)),
+    )?
+    .links;
+    Ok((links, warnings))
+}
+
+/// Counts the links [`scrape`] would return, without collecting them.
+///
+/// The document is validated like by [`scrape`], so the links of each element are still built
+/// while its xlink-elements are checked, but they are only counted.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::xlink::count_links;
+/// let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+///     <a xlink:type="simple" xlink:href="https://a.example.com" xlink:role="https://role.example.com"/>
+/// </root>"#;
+/// assert_eq!(count_links(&xml[..]).unwrap(), 2);
+/// ```
+pub fn count_links<R>(reader: R) -> Result<usize, XLinkFormatError>
+where
+    R: Read,
+{
+    let mut count = 0;
+    scrape_each(
+        reader,
+        &XLinkScrapeConfig::default(),
+        ScrapeMode::Strict,
+        |links| {
+            count += links.len();
+            ControlFlow::Continue(())
+        },
+    )?;
+    Ok(count)
+}
+
+/// Returns the first link [`scrape`] would return, e.g. to check if a document contains any link.
+///
+/// Parsing stops at the first element with a link, like [`scrape_limited`] stops once its limit is exceeded.
+/// Violations of the xlink-format behind that element are therefore not reported.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::xlink::find_first_link;
+/// let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+///     <a xlink:type="simple" xlink:href="https://a.example.com"/>
+///     <a xlink:type="simple" xlink:href="https://b.example.com"/>
+/// </root>"#;
+/// assert_eq!(find_first_link(&xml[..]).unwrap().unwrap().url, "https://a.example.com");
+/// assert_eq!(find_first_link(&b"<root/>"[..]).unwrap(), None);
+/// ```
+pub fn find_first_link<R>(reader: R) -> Result<Option<XLinkLink>, XLinkFormatError>
+where
+    R: Read,
+{
+    let mut first = None;
+    scrape_each(
+        reader,
+        &XLinkScrapeConfig::default(),
+        ScrapeMode::Strict,
+        |links| match links.into_iter().next() {
+            Some(link) => {
+                first = Some(link);
+                ControlFlow::Break(())
+            }
+            None => ControlFlow::Continue(()),
+        },
+    )?;
+    Ok(first)
+}
+
+/// Like [`scrape`], but limits the nesting-depth and chooses how links nested inside an extended link are handled.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::xlink::{scrape_with_config, NestedLinks, XLinkScrapeConfig};
+/// let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+///     <links xlink:type="extended">
+///         <loc xlink:type="locator" xlink:href="https://a.example.com"/>
+///         <a xlink:type="simple" xlink:href="https://nested.example.com"/>
+///     </links>
+/// </root>"#;
+/// let config = XLinkScrapeConfig {
+///     nested_links: NestedLinks::Skip,
+///     ..Default::default()
+/// };
+/// let links = scrape_with_config(&xml[..], &config).unwrap();
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].url, "https://a.example.com");
+/// ```
+pub fn scrape_with_config<R>(
+    reader: R,
+    config: &XLinkScrapeConfig,
+) -> Result<Vec<XLinkLink>, XLinkFormatError>
+where
+    R: Read,
+{
+    scrape_internal(reader, config, ScrapeOptions::new(ScrapeMode::Strict)).map(|it| it.links)
+}
+
+/// Like [`scrape`], but stops parsing as soon as more than `max_links` links were found,
+/// so untrusted documents cannot make it collect an unbounded number of links.
+/// # Example
+/// ```
+/// use link_scraper::formats::xml::xlink::scrape_limited;
+/// let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+///     <a xlink:type="simple" xlink:href="https://a.example.com"/>
+///     <a xlink:type="simple" xlink:href="https://b.example.com"/>
+/// </root>"#;
+/// let limited = scrape_limited(&xml[..], 1).unwrap();
+/// assert_eq!(limited.links[0].url, "https://a.example.com");
+/// assert!(limited.truncated);
+/// ```
+pub fn scrape_limited<R>(
+    reader: R,
+    max_links: usize,
+) -> Result<Limited<XLinkLink>, XLinkFormatError>
+where
+    R: Read,
+{
+    scrape_internal(
+        reader,
+        &XLinkScrapeConfig::default(),
+        ScrapeOptions {
+            max_links: Some(max_links),
+            ..ScrapeOptions::new(ScrapeMode::Strict)
+        },
+    )
+}
+
+/// Configures [`scrape_with_config`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XLinkScrapeConfig {
+    /// Documents with elements nested deeper than this are rejected with a [`XLinkFormatError::MaxDepthExceededError`].
+    /// `None` means there is no limit.
+    pub max_depth: Option<usize>,
+    /// What happens to simple- and extended-elements inside an extended element
+    pub nested_links: NestedLinks,
+    /// The uri of the document, against which relative links (like the `xlink:href` of a locator) are resolved,
+    /// taking the `xml:base`-attributes of their elements into account.
+    /// Links that cannot be resolved keep their url and are flagged with [`XLinkLink::relative`].
+    #[cfg(feature = "url")]
+    pub base_uri: Option<Url>,
+}
+
+/// How simple- and extended-elements inside an extended element are handled.
+///
+/// The xlink-specification gives them no meaning inside an extended element.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NestedLinks {
+    /// Fail with a [`XLinkFormatError::SimpleInsideOfExtendedError`] or [`XLinkFormatError::ExtendedInsideOfExtendedError`]
+    #[default]
+    Error,
+    /// Ignore the nested element and everything inside of it
+    Skip,
+}
+
+/// How [`scrape_internal`] handles malformed documents and when it stops
+struct ScrapeOptions<'a> {
+    mode: ScrapeMode<'a>,
+    /// Stops after more than this many links were found
+    max_links: Option<usize>,
+}
+
+impl<'a> ScrapeOptions<'a> {
+    fn new(mode: ScrapeMode<'a>) -> Self {
+        Self {
+            mode,
+            max_links: None,
+        }
+    }
+}
+
+/// How the malformed xml and the misplaced xlink-elements of a document are handled
+enum ScrapeMode<'a> {
+    /// Fails at the first malformed xml-token or misplaced element
+    Strict,
+    /// Stops at the first malformed xml-token and keeps the links found up to it,
+    /// still fails at a misplaced element
+    Lenient,
+    /// Like [`ScrapeMode::Lenient`], but collects the recoverable issues here
+    /// and skips the misplaced elements instead of failing
+    CollectWarnings(&'a mut Vec<ScrapeWarning>),
+}
+
+impl ScrapeMode<'_> {
+    fn is_lenient(&self) -> bool {
+        !matches!(self, ScrapeMode::Strict)
+    }
+
+    /// Adds `warning` to the warnings, if they are collected
+    fn warn(&mut self, warning: ScrapeWarning) {
+        if let ScrapeMode::CollectWarnings(warnings) = self {
+            warnings.push(warning);
+        }
+    }
+}
+
+fn scrape_internal<R>(
+    reader: R,
+    config: &XLinkScrapeConfig,
+    options: ScrapeOptions,
+) -> Result<Limited<XLinkLink>, XLinkFormatError>
+where
+    R: Read,
+{
+    let mut limited = Limited {
+        links: vec![],
+        truncated: false,
+    };
+    scrape_each(reader, config, options.mode, |mut links| {
+        limited.links.append(&mut links);
+        match options.max_links {
+            Some(max_links) if limited.links.len() > max_links => {
+                limited.links.truncate(max_links);
+                limited.truncated = true;
+                ControlFlow::Break(())
+            }
+            _ => ControlFlow::Continue(()),
+        }
+    })?;
+    Ok(limited)
+}
+
+/// Validates the document and passes the links of each element to `f`, until it returns [`ControlFlow::Break`]
+fn scrape_each<R, F>(
+    reader: R,
+    config: &XLinkScrapeConfig,
+    mut mode: ScrapeMode,
+    mut f: F,
+) -> Result<(), XLinkFormatError>
+where
+    R: Read,
+    F: FnMut(Vec<XLinkLink>) -> ControlFlow<()>,
+{
+    // The xml:base declared by each of the ancestors
+    let mut bases: Vec<Option<String>> = vec![];
+    // The simple-elements whose text is being collected, from the outermost to the innermost one
+    let mut simple_texts: Vec<SimpleText> = vec![];
 
     let mut parser = EventReader::new(reader);
-    while let Ok(xml_event) = &parser.next() {
-        match xml_event {
+    loop {
+        let xml_event = match parser.next() {
+            Ok(xml_event) => xml_event,
+            Err(err) if mode.is_lenient() => {
+                mode.warn(ScrapeWarning::malformed_xml(&err));
+                break;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let list = match &xml_event {
             XmlEvent::StartElement {
                 name,
                 attributes,
                 namespace,
             } => {
-                let mut list = scrape_from_start_element(
+                bases.push(find_xml_base(attributes));
+                check_depth(&bases, config)?;
+                scrape_from_start_element(
                     XmlStartElement {
-                        name,
+                        _name: name,
                         attributes,
                         _namespace: namespace,
+                        position: parser.position(),
                     },
                     &mut parser,
-                )?;
-                collector.append(&mut list)
+                    &mut bases,
+                    &mut simple_texts,
+                    config,
+                    &mut mode,
+                )?
+            }
+            XmlEvent::Characters(chars) | XmlEvent::CData(chars) => {
+                if let Some(simple_text) = simple_texts.last_mut() {
+                    simple_text.text.push_str(chars);
+                }
+                continue;
+            }
+            XmlEvent::EndElement { .. } => {
+                let list = match simple_texts.last() {
+                    Some(simple_text) if simple_text.depth == bases.len() => {
+                        let simple_text = simple_texts.pop().unwrap();
+                        with_xml_base(
+                            scrape_from_option_string(
+                                Some(simple_text.text),
+                                XLinkLinkKind::Text,
+                                simple_text.position,
+                            ),
+                            &bases,
+                        )
+                    }
+                    _ => vec![],
+                };
+                bases.pop();
+                list
             }
             XmlEvent::EndDocument => break,
-            _ => {}
+            _ => continue,
+        };
+        #[cfg(feature = "url")]
+        let list = match &config.base_uri {
+            Some(base_uri) => {
+                let mut list = list;
+                resolve_links(&mut list, base_uri);
+                for link in &mut list {
+                    link.relative = is_relative(&link.url);
+                }
+                list
+            }
+            None => list,
+        };
+        if f(list).is_break() {
+            break;
         }
     }
-
-    Ok(collector)
+    Ok(())
 }
-gen_scrape_from_file!(scrape(Read) -> Result<Vec<XLinkLink>, XLinkFormatError>);
-gen_scrape_from_slice!(scrape(Read) -> Result<Vec<XLinkLink>, XLinkFormatError>);
 
 #[derive(Error, Debug)]
 pub enum XLinkFormatError {
     #[error(transparent)]
     IoError(#[from] std::io::Error),
-    #[error("Unknown xlink:type value.")]
-    UnknownTypeError(String),
-    #[error("Xlink-element is missing a required attribute.")]
-    MissingRequiredAttributeError(String),
-    #[error("Found a locator-element outside of an extended element.")]
-    LocatorOutsideOfExtendedError,
-    #[error("Found an arc-element outside of an extended element.")]
-    ArcOutsideOfExtendedError,
-    #[error("Found a resource-element outside of an extended element.")]
-    ResourceOutsideOfExtendedError,
-    #[error("Found a simple-element inside of an extended element.")]
-    SimpleInsideOfExtendedError,
-    #[error("Found a extended-element inside of an extended element.")]
-    ExtendedInsideOfExtendedError,
+    /// The value of the `xlink:type`-attribute and the position of its element
+    #[error("Unknown xlink:type value {0:?} at {1}.")]
+    UnknownTypeError(String, TextPosition),
+    /// The name of the missing attribute and the position of its element
+    #[error("Xlink-element at {1} is missing the required attribute {0:?}.")]
+    MissingRequiredAttributeError(String, TextPosition),
+    #[error("Found a locator-element outside of an extended element at {0}.")]
+    LocatorOutsideOfExtendedError(TextPosition),
+    #[error("Found an arc-element outside of an extended element at {0}.")]
+    ArcOutsideOfExtendedError(TextPosition),
+    #[error("Found a resource-element outside of an extended element at {0}.")]
+    ResourceOutsideOfExtendedError(TextPosition),
+    #[error("Found a simple-element inside of an extended element at {0}.")]
+    SimpleInsideOfExtendedError(TextPosition),
+    #[error("Found a extended-element inside of an extended element at {0}.")]
+    ExtendedInsideOfExtendedError(TextPosition),
+    #[error("The elements are nested deeper than the allowed {0} levels.")]
+    MaxDepthExceededError(usize),
     #[error(transparent)]
     XmlReaderError(#[from] xml::reader::Error),
 }
 
-fn get_xlink_attribute_value(key: &str, attributes: &Vec<OwnedAttribute>) -> Option<String> {
+fn get_xlink_attribute_value(key: &str, attributes: &[OwnedAttribute]) -> Option<String> {
     attributes
         .iter()
         .find(|attribute| {
@@ -84,26 +435,98 @@ fn get_xlink_attribute_value(key: &str, attributes: &Vec<OwnedAttribute>) -> Opt
         .map(|href_attribute| href_attribute.value.to_string())
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XLinkLink {
     pub url: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::TextPositionDef")
+    )]
     pub location: TextPosition,
     pub kind: XLinkLinkKind,
+    /// The `xlink:show`-attribute of the element, e.g. `embed` or `new`
+    pub show: Option<String>,
+    /// The `xlink:actuate`-attribute of the element, e.g. `onLoad` or `onRequest`
+    pub actuate: Option<String>,
+    /// The `xml:base`-attributes of the element containing the link and its ancestors,
+    /// from the outermost to the innermost one
+    pub xml_base: Vec<String>,
+    /// The url is a relative reference without a scheme, like the `xlink:href` of a locator pointing
+    /// to `chapter2.xml`. It was not resolved, because no `base_uri` is configured or it cannot be resolved against it.
+    pub relative: bool,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+impl Link for XLinkLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::TextPosition(self.location)
+    }
+
+    fn bases(&self) -> &[String] {
+        &self.xml_base
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum XLinkLinkKind {
     Simple,
     Extended,
     Role,
     ArcRole,
+    /// The `xlink:arcrole` of an arc-element, with the labels of the locators or resources it connects.
+    /// Arcs without an `xlink:arcrole` have no url and are therefore not reported.
+    Arc {
+        /// The `xlink:from`-attribute of the arc
+        from: Option<String>,
+        /// The `xlink:to`-attribute of the arc
+        to: Option<String>,
+    },
+    /// An url in the text of a title-element
+    Title,
+    /// An url in the text of a simple-element, i.e. the url displayed to the reader.
+    /// It can differ from the [`XLinkLinkKind::Simple`] url the element actually links to.
+    Text,
 }
 
 static XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
 
+/// Fails if the open elements, each with an entry in `bases`, are nested deeper than allowed by `config`
+fn check_depth(
+    bases: &[Option<String>],
+    config: &XLinkScrapeConfig,
+) -> Result<(), XLinkFormatError> {
+    match config.max_depth {
+        Some(max_depth) if bases.len() > max_depth => {
+            Err(XLinkFormatError::MaxDepthExceededError(max_depth))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A simple-element, whose text is scanned for urls once it ends
+struct SimpleText {
+    /// The number of open elements including the simple-element, i.e. its entries in `bases`
+    depth: usize,
+    position: TextPosition,
+    text: String,
+}
+
 fn scrape_from_start_element<R>(
     xml_start_element: XmlStartElement,
-    mut parser: &mut EventReader<R>,
+    parser: &mut EventReader<R>,
+    bases: &mut Vec<Option<String>>,
+    simple_texts: &mut Vec<SimpleText>,
+    config: &XLinkScrapeConfig,
+    mode: &mut ScrapeMode,
 ) -> Result<Vec<XLinkLink>, XLinkFormatError>
 where
     R: Read,
@@ -113,15 +536,62 @@ where
     };
 
     match xlink_element {
-        XlinkElement::Simple(element) => Ok(scrape_from_xlink_simple(element, &parser)),
-        XlinkElement::Extended(element) => scrape_from_xlink_extended(element, &mut parser),
-        XlinkElement::Locator(_) => Err(LocatorOutsideOfExtendedError),
-        XlinkElement::Arc(_) => Err(ArcOutsideOfExtendedError),
-        XlinkElement::Resource(_) => Err(ResourceOutsideOfExtendedError),
-        XlinkElement::Title(_) => Ok(vec![]),
+        XlinkElement::Simple(element) => {
+            simple_texts.push(SimpleText {
+                depth: bases.len(),
+                position: parser.position(),
+                text: String::new(),
+            });
+            Ok(with_xml_base(
+                scrape_from_xlink_simple(element, parser),
+                bases,
+            ))
+        }
+        XlinkElement::Extended(element) => {
+            scrape_from_xlink_extended(element, parser, bases, config, mode)
+        }
+        XlinkElement::Title(_) => Ok(scrape_from_xlink_title(parser, bases)),
+        XlinkElement::Locator(_) => skip_misplaced_element(
+            LocatorOutsideOfExtendedError(parser.position()),
+            ScrapeWarningKind::LocatorOutsideOfExtended,
+            parser,
+            mode,
+        ),
+        XlinkElement::Arc(_) => skip_misplaced_element(
+            ArcOutsideOfExtendedError(parser.position()),
+            ScrapeWarningKind::ArcOutsideOfExtended,
+            parser,
+            mode,
+        ),
+        XlinkElement::Resource(_) => skip_misplaced_element(
+            ResourceOutsideOfExtendedError(parser.position()),
+            ScrapeWarningKind::ResourceOutsideOfExtended,
+            parser,
+            mode,
+        ),
     }
 }
 
+/// Fails with `error`, or adds a warning of `kind` instead if the warnings are collected
+fn skip_misplaced_element<R>(
+    error: XLinkFormatError,
+    kind: ScrapeWarningKind,
+    parser: &EventReader<R>,
+    mode: &mut ScrapeMode,
+) -> Result<Vec<XLinkLink>, XLinkFormatError>
+where
+    R: Read,
+{
+    let ScrapeMode::CollectWarnings(warnings) = mode else {
+        return Err(error);
+    };
+    warnings.push(ScrapeWarning {
+        location: parser.position(),
+        kind,
+    });
+    Ok(vec![])
+}
+
 fn scrape_from_option_string(
     role: Option<String>,
     link_type: XLinkLinkKind,
@@ -133,52 +603,119 @@ fn scrape_from_option_string(
         .map(|link| XLinkLink {
             url: link.as_str().to_string(),
             location: position,
-            kind: link_type,
+            kind: link_type.clone(),
+            show: None,
+            actuate: None,
+            xml_base: vec![],
+            relative: is_relative(link.as_str()),
         })
         .collect_vec();
     links
 }
 
+/// Checks if `url` is a relative reference, i.e. has no scheme
+fn is_relative(url: &str) -> bool {
+    scheme_of(url).is_none()
+}
+
+/// Sets the behavior-attributes of the element the `links` were found in.
+fn with_behavior(
+    mut links: Vec<XLinkLink>,
+    show: &Option<String>,
+    actuate: &Option<String>,
+) -> Vec<XLinkLink> {
+    links.iter_mut().for_each(|link| {
+        link.show.clone_from(show);
+        link.actuate.clone_from(actuate);
+    });
+    links
+}
+
+/// Sets the `xml:base`-attributes, that apply to the element the `links` were found in.
+fn with_xml_base(mut links: Vec<XLinkLink>, bases: &[Option<String>]) -> Vec<XLinkLink> {
+    let xml_base: Vec<String> = bases.iter().flatten().cloned().collect();
+    links
+        .iter_mut()
+        .for_each(|link| link.xml_base.clone_from(&xml_base));
+    links
+}
+
 fn scrape_from_xlink_extended<R>(
     xlink_extended_element: XlinkExtendedElement,
     parser: &mut EventReader<R>,
+    bases: &mut Vec<Option<String>>,
+    config: &XLinkScrapeConfig,
+    mode: &mut ScrapeMode,
 ) -> Result<Vec<XLinkLink>, XLinkFormatError>
 where
     R: Read,
 {
-    let mut ret: Vec<XLinkLink> = scrape_from_option_string(
-        xlink_extended_element.role,
-        XLinkLinkKind::Role,
-        parser.position(),
+    // The extended element is already on the stack, so it ends once the stack gets shorter than this
+    let depth = bases.len();
+    // The depth of a nested link, that is skipped together with its children
+    let mut skipped_depth: Option<usize> = None;
+    let mut ret: Vec<XLinkLink> = with_xml_base(
+        scrape_from_option_string(
+            xlink_extended_element.role,
+            XLinkLinkKind::Role,
+            parser.position(),
+        ),
+        bases,
     );
 
-    while let Ok(xml_event) = &parser.next() {
-        let mut links = match xml_event {
+    loop {
+        let xml_event = match parser.next() {
+            Ok(xml_event) => xml_event,
+            Err(err) if mode.is_lenient() => {
+                mode.warn(ScrapeWarning::malformed_xml(&err));
+                break;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let links = match &xml_event {
             XmlEvent::StartElement {
                 name,
                 attributes,
                 namespace,
             } => {
+                bases.push(find_xml_base(attributes));
+                check_depth(bases, config)?;
+                if skipped_depth.is_some() {
+                    continue;
+                }
                 let Some(xlink_element) =
                     XlinkElement::try_from_xml_start_element(XmlStartElement {
-                        name,
+                        _name: name,
                         attributes,
                         _namespace: namespace,
+                        position: parser.position(),
                     })?
                 else {
                     continue;
                 };
 
                 match xlink_element {
-                    XlinkElement::Simple(_) => Err(SimpleInsideOfExtendedError),
-                    XlinkElement::Extended(_) => Err(ExtendedInsideOfExtendedError),
+                    XlinkElement::Simple(_) | XlinkElement::Extended(_)
+                        if config.nested_links == NestedLinks::Skip =>
+                    {
+                        skipped_depth = Some(bases.len());
+                        continue;
+                    }
+                    XlinkElement::Simple(_) => Err(SimpleInsideOfExtendedError(parser.position())),
+                    XlinkElement::Extended(_) => {
+                        Err(ExtendedInsideOfExtendedError(parser.position()))
+                    }
                     XlinkElement::Locator(element) => {
                         let mut locator_links = vec![];
 
                         locator_links.push(XLinkLink {
+                            relative: is_relative(&element.href),
                             url: element.href,
                             location: parser.position(),
                             kind: XLinkLinkKind::Extended,
+                            show: None,
+                            actuate: None,
+                            xml_base: vec![],
                         });
                         locator_links.append(&mut scrape_from_option_string(
                             element.role,
@@ -188,34 +725,83 @@ where
 
                         Ok(locator_links)
                     }
-                    XlinkElement::Arc(element) => Ok(scrape_from_option_string(
-                        element.arcrole,
-                        XLinkLinkKind::ArcRole,
-                        parser.position(),
+                    XlinkElement::Arc(element) => Ok(with_behavior(
+                        scrape_from_option_string(
+                            element.arcrole,
+                            XLinkLinkKind::Arc {
+                                from: element.from,
+                                to: element.to,
+                            },
+                            parser.position(),
+                        ),
+                        &element.show,
+                        &element.actuate,
                     )),
                     XlinkElement::Resource(element) => Ok(scrape_from_option_string(
                         element.role,
                         XLinkLinkKind::Role,
                         parser.position(),
                     )),
-                    XlinkElement::Title(_) => Ok(vec![]),
+                    XlinkElement::Title(_) => {
+                        // Already has the bases of the title, which ended inside of it
+                        ret.append(&mut scrape_from_xlink_title(parser, bases));
+                        continue;
+                    }
                 }?
             }
-            XmlEvent::EndElement { name } => {
-                if name.eq(xlink_extended_element.xml.name) {
+            XmlEvent::EndElement { .. } => {
+                if skipped_depth == Some(bases.len()) {
+                    skipped_depth = None;
+                }
+                bases.pop();
+                if bases.len() < depth {
                     break;
-                } else {
-                    vec![]
                 }
+                vec![]
             }
+            XmlEvent::EndDocument => break,
             _ => vec![],
         };
-        ret.append(&mut links);
+        ret.append(&mut with_xml_base(links, bases));
     }
 
     Ok(ret)
 }
 
+/// Scans the text of a title-element for urls and reads up to the end of the element,
+/// which already has an entry in `bases`.
+///
+/// Stops early at a malformed xml-token, the parser reports it again to the caller.
+fn scrape_from_xlink_title<R>(
+    parser: &mut EventReader<R>,
+    bases: &mut Vec<Option<String>>,
+) -> Vec<XLinkLink>
+where
+    R: Read,
+{
+    let depth = bases.len();
+    let position = parser.position();
+    let mut text = String::new();
+    while let Ok(xml_event) = parser.next() {
+        match xml_event {
+            XmlEvent::StartElement { attributes, .. } => bases.push(find_xml_base(&attributes)),
+            XmlEvent::Characters(chars) | XmlEvent::CData(chars) => text.push_str(&chars),
+            XmlEvent::EndElement { .. } if bases.len() == depth => break,
+            XmlEvent::EndElement { .. } => {
+                bases.pop();
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    let links = with_xml_base(
+        scrape_from_option_string(Some(text), XLinkLinkKind::Title, position),
+        bases,
+    );
+    bases.truncate(depth - 1);
+    links
+}
+
 fn scrape_from_xlink_simple<R>(
     xlink_element: XlinkSimpleElement,
     parser: &EventReader<R>,
@@ -235,7 +821,7 @@ where
         XLinkLinkKind::Role,
         parser.position(),
     ));
-    ret
+    with_behavior(ret, &xlink_element.show, &xlink_element.actuate)
 }
 
 #[cfg(test)]
@@ -243,6 +829,8 @@ mod tests {
     use super::*;
 
     const TEST_XLINK: &[u8] = include_bytes!("../../../../test_files/xml/xlink_test.xml");
+    const TEST_XML_TRUNCATED: &[u8] =
+        include_bytes!("../../../../test_files/xml/xml_truncated_test.xml");
 
     #[test]
     fn scrape_xlink_test() {
@@ -261,4 +849,361 @@ mod tests {
             .iter()
             .any(|it| it.url == "https://role.test.com/" && it.kind == XLinkLinkKind::Role));
     }
+
+    #[test]
+    fn count_links_test() {
+        for xml in [TEST_XLINK, NESTED_XLINK] {
+            assert_eq!(
+                count_links(xml).map_err(|it| it.to_string()),
+                scrape(xml).map(|it| it.len()).map_err(|it| it.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn find_first_link_test() {
+        assert_eq!(
+            find_first_link(TEST_XLINK).unwrap(),
+            scrape(TEST_XLINK).unwrap().into_iter().next()
+        );
+        // Stops before the misplaced locator, which fails the whole document in scrape
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+            <a xlink:type="simple" xlink:href="https://first.test.com"/>
+            <loc xlink:type="locator" xlink:href="https://orphan.test.com"/>
+        </root>"#;
+        assert!(scrape(&xml[..]).is_err());
+        let link = find_first_link(&xml[..]).unwrap().unwrap();
+        assert_eq!(link.url, "https://first.test.com");
+        assert_eq!(find_first_link(&b"<root/>"[..]).unwrap(), None);
+    }
+
+    #[test]
+    fn scrape_show_and_actuate_test() {
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+            <image xlink:type="simple" xlink:href="https://embed.test.com/image.png" xlink:show="embed" xlink:actuate="onLoad"/>
+            <a xlink:href="https://plain.test.com"/>
+        </root>"#;
+        let links = scrape_from_slice(xml).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].show.as_deref(), Some("embed"));
+        assert_eq!(links[0].actuate.as_deref(), Some("onLoad"));
+        assert_eq!(links[1].show, None);
+        assert_eq!(links[1].actuate, None);
+    }
+
+    #[test]
+    fn scrape_text_of_simple_element_test() {
+        let xml =
+            br#"<root xmlns:xlink="http://www.w3.org/1999/xlink" xml:base="https://base.test.com/">
+            <a xlink:href="https://target.test.com">Visit <b>https://visible.test.com</b></a>
+            <p>https://outside.test.com</p>
+        </root>"#;
+        let links = scrape_from_slice(xml).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, &XLinkLinkKind)> =
+            links.iter().map(|it| (it.url.as_str(), &it.kind)).collect();
+        // The text outside of simple-elements is not scanned
+        assert_eq!(
+            found,
+            vec![
+                ("https://target.test.com", &XLinkLinkKind::Simple),
+                ("https://visible.test.com", &XLinkLinkKind::Text),
+            ]
+        );
+        assert_eq!(links[1].location, links[0].location);
+        assert_eq!(links[1].xml_base, vec!["https://base.test.com/"]);
+        assert_ne!(links[0].url, links[1].url);
+    }
+
+    #[test]
+    fn scrape_xml_base_test() {
+        let xml =
+            br#"<root xmlns:xlink="http://www.w3.org/1999/xlink" xml:base="https://base.test.com/">
+            <a xlink:href="simple.html" xml:base="docs/"/>
+            <links xlink:type="extended">
+                <loc xlink:type="locator" xlink:href="https://a.test.com" xml:base="nested/"/>
+            </links>
+            <a xlink:href="https://b.test.com"/>
+        </root>"#;
+        let links = scrape_from_slice(xml).unwrap();
+        let bases: Vec<(&str, Vec<&str>)> = links
+            .iter()
+            .map(|it| {
+                (
+                    it.url.as_str(),
+                    it.bases().iter().map(|it| it.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            bases,
+            vec![
+                (
+                    "https://a.test.com",
+                    vec!["https://base.test.com/", "nested/"]
+                ),
+                ("https://b.test.com", vec!["https://base.test.com/"]),
+            ]
+        );
+    }
+
+    const RELATIVE_LOCATOR_XLINK: &[u8] = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+        <links xlink:type="extended" xml:base="https://base.test.com/docs/">
+            <loc xlink:type="locator" xlink:href="chapter1.xml"/>
+            <loc xlink:type="locator" xlink:href="chapter2.xml" xml:base="part2/"/>
+            <loc xlink:type="locator" xlink:href="https://absolute.test.com/index.xml"/>
+        </links>
+        <links xlink:type="extended">
+            <loc xlink:type="locator" xlink:href="../appendix.xml"/>
+        </links>
+    </root>"#;
+
+    #[test]
+    fn flag_relative_locator_test() {
+        let links = scrape_from_slice(RELATIVE_LOCATOR_XLINK).unwrap();
+        let found: Vec<(&str, bool)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.relative))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("chapter1.xml", true),
+                ("chapter2.xml", true),
+                ("https://absolute.test.com/index.xml", false),
+                ("../appendix.xml", true),
+            ]
+        );
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn resolve_relative_locator_test() {
+        let config = XLinkScrapeConfig {
+            base_uri: Some(Url::parse("https://document.test.com/a/index.xml").unwrap()),
+            ..Default::default()
+        };
+        let links = scrape_with_config(RELATIVE_LOCATOR_XLINK, &config).unwrap();
+        let found: Vec<(&str, bool)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.relative))
+            .collect();
+        // The xml:base of the elements take precedence over the uri of the document
+        assert_eq!(
+            found,
+            vec![
+                ("https://base.test.com/docs/chapter1.xml", false),
+                ("https://base.test.com/docs/part2/chapter2.xml", false),
+                ("https://absolute.test.com/index.xml", false),
+                ("https://document.test.com/appendix.xml", false),
+            ]
+        );
+
+        // Nothing can be resolved against an url, that is not hierarchical
+        let config = XLinkScrapeConfig {
+            base_uri: Some(Url::parse("urn:isbn:0451450523").unwrap()),
+            ..Default::default()
+        };
+        let links = scrape_with_config(RELATIVE_LOCATOR_XLINK, &config).unwrap();
+        assert_eq!(links[3].url, "../appendix.xml");
+        assert!(links[3].relative);
+    }
+
+    const NESTED_XLINK: &[u8] = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+        <links xlink:type="extended">
+            <links xlink:type="extended">
+                <loc xlink:type="locator" xlink:href="https://nested.test.com"/>
+            </links>
+            <a xlink:type="simple" xlink:href="https://simple.test.com"><b/></a>
+            <loc xlink:type="locator" xlink:href="https://outer.test.com"/>
+        </links>
+        <a xlink:href="https://after.test.com"/>
+    </root>"#;
+
+    #[test]
+    fn fail_on_nested_links_test() {
+        let result = scrape(NESTED_XLINK);
+        assert!(matches!(
+            result,
+            Err(XLinkFormatError::ExtendedInsideOfExtendedError(_))
+        ));
+    }
+
+    #[test]
+    fn skip_nested_links_test() {
+        let config = XLinkScrapeConfig {
+            nested_links: NestedLinks::Skip,
+            ..Default::default()
+        };
+        let links = scrape_with_config(NESTED_XLINK, &config).unwrap();
+        let urls: Vec<&str> = links.iter().map(|it| it.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://outer.test.com", "https://after.test.com"]
+        );
+    }
+
+    #[test]
+    fn extended_ends_with_its_own_element_test() {
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+            <links xlink:type="extended"><links/><loc xlink:type="locator" xlink:href="https://a.test.com"/></links>
+            <a xlink:href="https://b.test.com"/>
+        </root>"#;
+        let links = scrape(&xml[..]).unwrap();
+        assert_eq!(links[0].url, "https://a.test.com");
+        assert_eq!(links[0].kind, XLinkLinkKind::Extended);
+        assert_eq!(links[1].kind, XLinkLinkKind::Simple);
+    }
+
+    #[test]
+    fn scrape_arc_and_title_test() {
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+            <links xlink:type="extended">
+                <name xlink:type="title">Mirrors of <b>https://title.test.com</b></name>
+                <loc xlink:type="locator" xlink:href="https://a.test.com" xlink:label="a"/>
+                <loc xlink:type="locator" xlink:href="https://b.test.com" xlink:label="b"/>
+                <go xlink:type="arc" xlink:from="a" xlink:to="b" xlink:arcrole="https://mirror.test.com/role"/>
+            </links>
+            <a xlink:href="https://after.test.com"/>
+        </root>"#;
+        let links = scrape(&xml[..]).unwrap();
+        let found: Vec<(&str, &XLinkLinkKind)> =
+            links.iter().map(|it| (it.url.as_str(), &it.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://title.test.com", &XLinkLinkKind::Title),
+                ("https://a.test.com", &XLinkLinkKind::Extended),
+                ("https://b.test.com", &XLinkLinkKind::Extended),
+                (
+                    "https://mirror.test.com/role",
+                    &XLinkLinkKind::Arc {
+                        from: Some("a".to_string()),
+                        to: Some("b".to_string()),
+                    }
+                ),
+                ("https://after.test.com", &XLinkLinkKind::Simple),
+            ]
+        );
+    }
+
+    #[test]
+    fn max_depth_test() {
+        let config = XLinkScrapeConfig {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let result = scrape_with_config(NESTED_XLINK, &config);
+        assert!(matches!(
+            result,
+            Err(XLinkFormatError::MaxDepthExceededError(2))
+        ));
+
+        let deep = format!("{}{}", "<a>".repeat(100), "</a>".repeat(100));
+        assert!(scrape_with_config(deep.as_bytes(), &config).is_err());
+        assert!(scrape(deep.as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn scrape_limited_test() {
+        let xml = format!(
+            r#"<root xmlns:xlink="http://www.w3.org/1999/xlink">{}</root>"#,
+            (1..=100)
+                .map(|it| format!(r#"<a xlink:type="simple" xlink:href="https://{it}.test.com"/>"#))
+                .collect::<String>()
+        );
+        let limited = scrape_limited(xml.as_bytes(), 10).unwrap();
+        assert!(limited.truncated);
+        assert_eq!(limited.links.len(), 10);
+        assert_eq!(limited.links[9].url, "https://10.test.com");
+
+        let limited = scrape_limited(xml.as_bytes(), 100).unwrap();
+        assert!(!limited.truncated);
+        assert_eq!(limited.links.len(), 100);
+    }
+
+    #[test]
+    fn fail_on_truncated_xml_test() {
+        let result = scrape(TEST_XML_TRUNCATED);
+        assert!(matches!(result, Err(XLinkFormatError::XmlReaderError(_))));
+        assert!(scrape_lenient(TEST_XML_TRUNCATED).is_ok());
+    }
+
+    #[test]
+    fn warn_on_orphan_locator_test() {
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+            <loc xlink:type="locator" xlink:href="https://orphan.test.com"/>
+            <a xlink:type="simple" xlink:href="https://simple.test.com"/>
+            <links xlink:type="extended">
+                <loc xlink:type="locator" xlink:href="https://extended.test.com"/>
+            </links>
+        </root>"#;
+        assert!(matches!(
+            scrape(&xml[..]),
+            Err(XLinkFormatError::LocatorOutsideOfExtendedError(_))
+        ));
+
+        let (links, warnings) = scrape_with_diagnostics(&xml[..]).unwrap();
+        let urls: Vec<&str> = links.iter().map(|it| it.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://simple.test.com", "https://extended.test.com"]
+        );
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].kind,
+            ScrapeWarningKind::LocatorOutsideOfExtended
+        );
+        assert_eq!(warnings[0].location.row, 1);
+    }
+
+    #[test]
+    fn report_position_of_malformed_element_test() {
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+  <a xlink:type="bogus" xlink:href="https://test.com"/>
+</root>"#;
+        let error = scrape(&xml[..]).unwrap_err();
+        assert!(matches!(
+            &error,
+            XLinkFormatError::UnknownTypeError(value, TextPosition { row: 1, column: 2 })
+                if value == "bogus"
+        ));
+        assert_eq!(
+            error.to_string(),
+            r#"Unknown xlink:type value "bogus" at 2:3."#
+        );
+
+        let xml = br#"<root xmlns:xlink="http://www.w3.org/1999/xlink">
+  <links xlink:type="extended">
+      <loc xlink:type="locator"/>
+  </links>
+</root>"#;
+        assert!(matches!(
+            scrape(&xml[..]),
+            Err(XLinkFormatError::MissingRequiredAttributeError(
+                attribute,
+                TextPosition { row: 2, column: 6 }
+            )) if attribute == "href"
+        ));
+    }
+
+    #[test]
+    fn warn_on_truncated_xml_test() {
+        let (links, warnings) = scrape_with_diagnostics(TEST_XML_TRUNCATED).unwrap();
+        assert_eq!(links, scrape_lenient(TEST_XML_TRUNCATED).unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].kind,
+            ScrapeWarningKind::MalformedXml(_)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_XLINK).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<XLinkLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
 }
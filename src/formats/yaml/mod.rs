@@ -0,0 +1,279 @@
+use crate::helpers::find_urls;
+use crate::link::{Link, Location};
+use crate::location::SourceLocation;
+use crate::{gen_scrape_from_file, gen_scrape_from_slice, gen_scrape_with_options};
+use std::fmt::{Display, Formatter, Write};
+use std::io::Read;
+use thiserror::Error;
+use yaml_rust2::parser::Parser;
+use yaml_rust2::Event;
+
+/// Scrapes links from all scalar values of a yaml-stream, which may contain several documents.
+///
+/// Keys are not scraped. Aliases are not expanded, so the links of a node with an anchor (`&name`) are only
+/// reported once, at the path where the node is defined, no matter how often it is referenced (`*name`).
+/// # Example
+/// ```
+/// use link_scraper::formats::yaml::scrape;
+/// let yaml = "spec:\n  containers:\n    - image: https://registry.example.com/app\n";
+/// let links = scrape(yaml.as_bytes()).unwrap();
+/// assert_eq!(links[0].path, "spec.containers[0].image");
+/// assert_eq!(links[0].location, 3);
+/// ```
+pub fn scrape<R>(mut reader: R) -> Result<Vec<YamlLink>, YamlScrapingError>
+where
+    R: Read,
+{
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+
+    let mut parser = Parser::new_from_str(&content);
+    let mut links = vec![];
+    let mut ancestors: Vec<Node> = vec![];
+    let mut document = 0;
+    // The number of nested nodes inside a complex key (e.g. `? [a, b]`), which are not scraped
+    let mut key_depth = 0;
+    loop {
+        let (event, marker) = parser.next_token()?;
+        match event {
+            Event::StreamEnd => break,
+            Event::DocumentEnd => document += 1,
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                if key_depth > 0 || expects_key(&ancestors) {
+                    key_depth += 1;
+                    continue;
+                }
+                ancestors.push(match event {
+                    Event::MappingStart(..) => Node::Mapping { key: None },
+                    _ => Node::Sequence { index: 0 },
+                });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                if key_depth > 0 {
+                    key_depth -= 1;
+                    if key_depth == 0 {
+                        set_key(&mut ancestors, "?".to_string());
+                    }
+                    continue;
+                }
+                ancestors.pop();
+                finish_value(&mut ancestors);
+            }
+            Event::Scalar(value, ..) => {
+                if key_depth > 0 {
+                    continue;
+                }
+                if expects_key(&ancestors) {
+                    set_key(&mut ancestors, value);
+                    continue;
+                }
+                let urls = find_urls(&value);
+                if !urls.is_empty() {
+                    let path = format_path(&ancestors);
+                    links.extend(urls.iter().map(|url| YamlLink {
+                        url: url.as_str().to_string(),
+                        path: path.clone(),
+                        document,
+                        location: marker.line(),
+                    }));
+                }
+                finish_value(&mut ancestors);
+            }
+            // The links of the referenced node were already reported where it is defined
+            Event::Alias(_) => {
+                if key_depth > 0 {
+                    continue;
+                }
+                if expects_key(&ancestors) {
+                    set_key(&mut ancestors, "*".to_string());
+                } else {
+                    finish_value(&mut ancestors);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(links)
+}
+gen_scrape_from_file!(scrape(Read) -> Result<Vec<YamlLink>, YamlScrapingError>);
+gen_scrape_from_slice!(scrape(Read) -> Result<Vec<YamlLink>, YamlScrapingError>);
+gen_scrape_with_options!(scrape(Read) -> Result<Vec<YamlLink>, YamlScrapingError>);
+
+#[derive(Error, Debug)]
+pub enum YamlScrapingError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    YamlError(#[from] yaml_rust2::ScanError),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YamlLink {
+    pub url: String,
+    /// The keys and indices leading to the value containing the link, like `spec.containers[0].image`.
+    /// Keys that are not made of letters, digits, `_` and `-` are quoted, like `annotations["example.com/url"]`.
+    /// The path of a value at the root of a document is empty.
+    pub path: String,
+    /// The index of the document inside the stream containing the link, starting at 0
+    pub document: usize,
+    /// The line (starting at 1) of the value containing the link
+    pub location: usize,
+}
+
+impl Link for YamlLink {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    fn location(&self) -> Location {
+        Location::Line(self.location)
+    }
+
+    fn source_location(&self) -> SourceLocation {
+        SourceLocation::Pointer(self.path.clone())
+    }
+}
+
+impl Display for YamlLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// A collection containing the current value
+enum Node {
+    /// `key` is `None` while the next scalar is a key
+    Mapping {
+        key: Option<String>,
+    },
+    Sequence {
+        index: usize,
+    },
+}
+
+/// Checks if the next node is the key of a mapping
+fn expects_key(ancestors: &[Node]) -> bool {
+    matches!(ancestors.last(), Some(Node::Mapping { key: None }))
+}
+
+fn set_key(ancestors: &mut [Node], new_key: String) {
+    if let Some(Node::Mapping { key }) = ancestors.last_mut() {
+        *key = Some(new_key);
+    }
+}
+
+/// Moves the innermost collection on to its next entry, after its current value ended
+fn finish_value(ancestors: &mut [Node]) {
+    match ancestors.last_mut() {
+        Some(Node::Mapping { key }) => *key = None,
+        Some(Node::Sequence { index }) => *index += 1,
+        None => {}
+    }
+}
+
+fn format_path(ancestors: &[Node]) -> String {
+    let mut path = String::new();
+    for node in ancestors {
+        match node {
+            Node::Mapping { key: Some(key) } => {
+                let is_plain = !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+                if !is_plain {
+                    let _ = write!(path, "[{:?}]", key);
+                } else if path.is_empty() {
+                    path.push_str(key);
+                } else {
+                    let _ = write!(path, ".{key}");
+                }
+            }
+            Node::Sequence { index } => {
+                let _ = write!(path, "[{index}]");
+            }
+            Node::Mapping { key: None } => {}
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_YAML: &[u8] = include_bytes!("../../../test_files/yaml/yaml_test.yaml");
+
+    #[test]
+    fn scrape_yaml_test() {
+        let links = scrape(TEST_YAML).unwrap();
+        println!("{:?}", links);
+        let found: Vec<(&str, &str, usize)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.path.as_str(), it.document))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://homepage.test.com", "project.homepage", 0),
+                ("https://registry.test.com/app", "project.jobs[0].image", 0),
+                ("https://mirror.test.com", "project.jobs[0].mirrors[1]", 0),
+                ("https://docs.test.com/a", "project.jobs[1].notes", 0),
+                ("https://docs.test.com/b", "project.jobs[1].notes", 0),
+                ("https://anchored.test.com", "project.defaults.service", 0),
+                (
+                    "https://annotation.test.com",
+                    r#"project.annotations["example.com/link"]"#,
+                    0
+                ),
+                ("https://second.test.com", "", 1),
+            ]
+        );
+        assert_eq!(links[0].location, 3);
+    }
+
+    #[test]
+    fn scrape_alias_once_test() {
+        let yaml = "base: &base\n  url: https://a.test.com\nfirst: *base\nsecond:\n  <<: *base\n  other: https://b.test.com\n";
+        let links = scrape(yaml.as_bytes()).unwrap();
+        let found: Vec<(&str, &str)> = links
+            .iter()
+            .map(|it| (it.url.as_str(), it.path.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://a.test.com", "base.url"),
+                ("https://b.test.com", "second.other"),
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_complex_keys_test() {
+        let yaml = "? [https://key.test.com]\n: https://value.test.com\n";
+        let links = scrape(yaml.as_bytes()).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://value.test.com");
+        assert_eq!(links[0].path, r#"["?"]"#);
+    }
+
+    #[test]
+    fn fail_on_invalid_yaml_test() {
+        let result = scrape("a: [https://a.test.com".as_bytes());
+        assert!(matches!(result, Err(YamlScrapingError::YamlError(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let links = scrape(TEST_YAML).unwrap();
+        let json = serde_json::to_string(&links).unwrap();
+        let deserialized: Vec<YamlLink> = serde_json::from_str(&json).unwrap();
+        assert_eq!(links, deserialized);
+    }
+}
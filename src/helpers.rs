@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+use std::net::Ipv6Addr;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
 use linkify::LinkFinder;
 use linkify::LinkKind::Url;
 
@@ -11,11 +16,853 @@ pub use crate::any_format_scraper::scrape;
 /// let urls = find_urls("dfjaoijewfj oijoiwfjoiwjoi j´21214https://www.google.com .äwä.f.f.wä ");
 /// assert_eq!(urls.first().unwrap().as_str(), "https://www.google.com")
 /// ```
-pub fn find_urls(content: &str) -> Vec<linkify::Link> {
-    LinkFinder::new()
+///
+/// Trailing [`TRAILING_PUNCTUATION`] is not considered part of a url, unless it closes a bracket opened inside the url.
+/// Percent-encoded and non-ASCII characters (like in `https://例え.テスト/パス`) are part of the url,
+/// use [`UrlFinder::ascii`] to get the urls in their ASCII-form instead.
+/// Uses a shared [`UrlFinder`].
+pub fn find_urls(content: &str) -> Vec<UrlMatch> {
+    URL_FINDER.find_all(content)
+}
+
+/// Characters that are removed from the end of found urls by default, see [`trim_trailing_punctuation`].
+pub const TRAILING_PUNCTUATION: [char; 12] =
+    ['.', ',', ';', ':', '!', '?', ')', ']', '}', '>', '\'', '"'];
+
+/// Like [`find_urls`], but lets you choose which trailing characters are not considered part of a url.
+///
+/// Note that the underlying url-detection already stops at some of the [`TRAILING_PUNCTUATION`] on its own.
+pub fn find_urls_with_trailing_punctuation(content: &str, trailing: &[char]) -> Vec<UrlMatch> {
+    URL_FINDER.find_all_with_trailing_punctuation(content, trailing)
+}
+
+/// The [`UrlFinder`] used by [`find_urls`]
+static URL_FINDER: LazyLock<UrlFinder> = LazyLock::new(UrlFinder::new);
+
+/// A configured url-detection, that can be reused for many strings.
+///
+/// [`find_urls`] uses a shared instance, so you only need your own to change the trailing punctuation once
+/// instead of passing it with every call.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::UrlFinder;
+/// let finder = UrlFinder::with_trailing_punctuation(&['/']);
+/// let urls = finder.find_all("https://example.com/a/ and https://example.com/b");
+/// assert_eq!(urls[0].as_str(), "https://example.com/a");
+/// assert_eq!(urls[1].as_str(), "https://example.com/b");
+/// ```
+#[derive(Debug)]
+pub struct UrlFinder {
+    finder: LinkFinder,
+    trailing: Vec<char>,
+    #[cfg(feature = "url")]
+    ascii: bool,
+}
+
+impl UrlFinder {
+    /// A finder that trims the [`TRAILING_PUNCTUATION`], like [`find_urls`]
+    pub fn new() -> Self {
+        Self::with_trailing_punctuation(&TRAILING_PUNCTUATION)
+    }
+
+    /// A finder that trims the given characters, like [`find_urls_with_trailing_punctuation`]
+    pub fn with_trailing_punctuation(trailing: &[char]) -> Self {
+        let mut finder = LinkFinder::new();
+        finder.kinds(&[Url]);
+        Self {
+            finder,
+            trailing: trailing.to_vec(),
+            #[cfg(feature = "url")]
+            ascii: false,
+        }
+    }
+
+    /// Converts the found urls to ASCII, with a punycode-encoded host and percent-encoded path, query and fragment,
+    /// like `https://xn--r8jz45g.xn--zckzah/%E3%83%91%E3%82%B9` for `https://例え.テスト/パス`.
+    ///
+    /// The urls are converted like by [`crate::processing::normalize_url`], urls it cannot parse are kept as they are.
+    /// [`UrlMatch::start`] and [`UrlMatch::end`] still point to the url as it is written in the searched string.
+    /// # Example
+    /// ```
+    /// use crate::link_scraper::helpers::UrlFinder;
+    /// let urls = UrlFinder::new().ascii().find_all("Siehe https://bücher.example/straße");
+    /// assert_eq!(urls[0].as_str(), "https://xn--bcher-kva.example/stra%C3%9Fe");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn ascii(mut self) -> Self {
+        self.ascii = true;
+        self
+    }
+
+    /// Finds all urls in `content`, ordered by their position
+    pub fn find_all(&self, content: &str) -> Vec<UrlMatch> {
+        self.find_all_with_trailing_punctuation(content, &self.trailing)
+    }
+
+    fn find_all_with_trailing_punctuation(
+        &self,
+        content: &str,
+        trailing: &[char],
+    ) -> Vec<UrlMatch> {
+        let mut matches: Vec<UrlMatch> = self
+            .finder
+            .links(content)
+            .map(|link| {
+                let text = trim_trailing_punctuation(link.as_str(), trailing);
+                UrlMatch {
+                    text: text.to_string(),
+                    start: link.start(),
+                    end: link.start() + text.len(),
+                }
+            })
+            .collect();
+        matches.extend(find_ipv6_urls(content, trailing));
+        add_unicode_tld_urls(&mut matches, content, trailing);
+        matches.sort_by_key(|m| m.start);
+        #[cfg(feature = "url")]
+        if self.ascii {
+            for url in &mut matches {
+                if let Some(ascii) = crate::processing::normalize_url(&url.text) {
+                    url.text = ascii;
+                }
+            }
+        }
+        matches
+    }
+}
+
+impl Default for UrlFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Finds urls with a bracketed IPv6-host like `http://[2001:db8::1]:8080/`, which are not detected by [`LinkFinder`]
+fn find_ipv6_urls(content: &str, trailing: &[char]) -> Vec<UrlMatch> {
+    let mut matches = vec![];
+    for (separator, _) in content.match_indices("://[") {
+        let Some(start) = scheme_start(content, separator) else {
+            continue;
+        };
+
+        let host_start = separator + "://[".len();
+        let Some(host_length) = content[host_start..].find(']') else {
+            continue;
+        };
+        let host = &content[host_start..host_start + host_length];
+        let address = host
+            .split_once('%')
+            .map_or(host, |(address, _zone)| address);
+        if address.parse::<Ipv6Addr>().is_err() {
+            continue;
+        }
+
+        let rest = &content[host_start + host_length + 1..];
+        let length = rest
+            .find(|c: char| c.is_whitespace() || IPV6_URL_DELIMITERS.contains(&c))
+            .unwrap_or(rest.len());
+        let end = host_start + host_length + 1 + length;
+        // The host itself ends with a `]`, so it is never trimmed as an unbalanced bracket
+        let text = trim_trailing_punctuation(&content[start..end], trailing);
+        matches.push(UrlMatch {
+            text: text.to_string(),
+            start,
+            end: start + text.len(),
+        });
+    }
+    matches
+}
+
+/// Adds the urls of [`find_unicode_tld_urls`] to the `matches`, that do not start inside of one of them
+fn add_unicode_tld_urls(matches: &mut Vec<UrlMatch>, content: &str, trailing: &[char]) {
+    let unicode_tld_urls: Vec<UrlMatch> = find_unicode_tld_urls(content, trailing)
+        .into_iter()
+        .filter(|url| {
+            !matches
+                .iter()
+                .any(|it| it.start <= url.start && url.start < it.end)
+        })
+        .collect();
+    matches.extend(unicode_tld_urls);
+}
+
+/// Finds urls with a host whose top-level domain is not written in ASCII, like `https://例え.テスト/パス`,
+/// which are not detected by [`LinkFinder`]
+fn find_unicode_tld_urls(content: &str, trailing: &[char]) -> Vec<UrlMatch> {
+    let mut matches = vec![];
+    for (separator, _) in content.match_indices("://") {
+        let Some(start) = scheme_start(content, separator) else {
+            continue;
+        };
+
+        let host_start = separator + "://".len();
+        let host_length = content[host_start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '.'))
+            .unwrap_or(content.len() - host_start);
+        let host = &content[host_start..host_start + host_length];
+        let Some((_, tld)) = host.rsplit_once('.') else {
+            continue;
+        };
+        if tld.is_empty() || tld.is_ascii() || !tld.chars().all(char::is_alphabetic) {
+            continue;
+        }
+
+        let rest = &content[host_start + host_length..];
+        let length = if rest.starts_with(['/', '?', '#', ':']) {
+            rest.find(|c: char| c.is_whitespace() || IPV6_URL_DELIMITERS.contains(&c))
+                .unwrap_or(rest.len())
+        } else {
+            0
+        };
+        let end = host_start + host_length + length;
+        let text = trim_trailing_punctuation(&content[start..end], trailing);
+        matches.push(UrlMatch {
+            text: text.to_string(),
+            start,
+            end: start + text.len(),
+        });
+    }
+    matches
+}
+
+/// The start of the scheme ending at the `://` at `separator`, if there is a valid one
+fn scheme_start(content: &str, separator: usize) -> Option<usize> {
+    let scheme_length = content[..separator]
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '+' || *c == '-' || *c == '.')
+        .count();
+    let start = separator - scheme_length;
+    content[start..]
+        .starts_with(|c: char| c.is_ascii_alphabetic())
+        .then_some(start)
+}
+
+/// Characters that end a url found by [`find_ipv6_urls`], [`find_unicode_tld_urls`] or [`find_protocol_relative_urls`]
+const IPV6_URL_DELIMITERS: [char; 8] = ['<', '>', '"', '\'', '`', '{', '}', '|'];
+
+/// Like [`find_urls`], but also finds urls without a scheme, like `www.example.com` or `example.co.uk/a`.
+///
+/// To avoid false positives on file names (`config.json`) or version numbers (`1.2.3`), a schemeless url
+/// is only accepted if its host starts with `www.` or ends with one of the [`SCHEMELESS_TLDS`].
+/// The matches are returned as they are written, use [`add_default_scheme`] to turn them into absolute urls.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::{add_default_scheme, find_urls_allow_schemeless};
+/// let urls = find_urls_allow_schemeless("See example.co.uk/a, not config.json");
+/// assert_eq!(urls.len(), 1);
+/// assert_eq!(add_default_scheme(urls[0].as_str()), "https://example.co.uk/a");
+/// ```
+pub fn find_urls_allow_schemeless(content: &str) -> Vec<UrlMatch> {
+    let mut matches = LinkFinder::new()
+        .url_must_have_scheme(false)
         .links(content)
         .filter(|link| link.kind().eq(&Url))
-        .collect()
+        .filter_map(|link| {
+            let text = trim_trailing_punctuation(link.as_str(), &TRAILING_PUNCTUATION);
+            if !has_scheme(text) && !is_plausible_host(text) {
+                return None;
+            }
+            Some(UrlMatch {
+                text: text.to_string(),
+                start: link.start(),
+                end: link.start() + text.len(),
+            })
+        })
+        .chain(find_ipv6_urls(content, &TRAILING_PUNCTUATION))
+        .collect::<Vec<_>>();
+    add_unicode_tld_urls(&mut matches, content, &TRAILING_PUNCTUATION);
+    matches.sort_by_key(|m| m.start);
+    matches
+}
+
+/// Like [`find_urls`], but also finds protocol-relative urls like `//cdn.example.com/lib.js`,
+/// which take the scheme of the document they are embedded in.
+///
+/// Like for [`find_urls_allow_schemeless`], the host has to start with `www.` or end with one of the
+/// [`SCHEMELESS_TLDS`], so comments like `//TODO` or paths like `a//b` are not matched.
+/// Use [`resolve_protocol_relative`] to turn the matches into absolute urls.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::{find_urls_allow_protocol_relative, resolve_protocol_relative};
+/// let urls = find_urls_allow_protocol_relative(r#"<script src="//cdn.example.com/lib.js"></script>"#);
+/// assert_eq!(urls[0].as_str(), "//cdn.example.com/lib.js");
+/// assert_eq!(resolve_protocol_relative(urls[0].as_str(), "https"), "https://cdn.example.com/lib.js");
+/// ```
+pub fn find_urls_allow_protocol_relative(content: &str) -> Vec<UrlMatch> {
+    let mut matches = find_urls(content);
+    matches.extend(find_protocol_relative_urls(content, &TRAILING_PUNCTUATION));
+    matches.sort_by_key(|m| m.start);
+    matches
+}
+
+/// Prepends `default_scheme` to a protocol-relative `url` (e.g. found by [`find_urls_allow_protocol_relative`]),
+/// all other urls are returned as they are.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::resolve_protocol_relative;
+/// assert_eq!(resolve_protocol_relative("//cdn.example.com/lib.js", "https"), "https://cdn.example.com/lib.js");
+/// assert_eq!(resolve_protocol_relative("http://cdn.example.com/lib.js", "https"), "http://cdn.example.com/lib.js");
+/// ```
+pub fn resolve_protocol_relative<'a>(url: &'a str, default_scheme: &str) -> Cow<'a, str> {
+    if url.starts_with("//") {
+        Cow::Owned(format!("{default_scheme}:{url}"))
+    } else {
+        Cow::Borrowed(url)
+    }
+}
+
+/// Finds urls starting with `//` and a plausible host, see [`find_urls_allow_protocol_relative`]
+fn find_protocol_relative_urls(content: &str, trailing: &[char]) -> Vec<UrlMatch> {
+    let mut matches = vec![];
+    for (start, _) in content.match_indices("//") {
+        // `://` belongs to an url with a scheme, `a//b` and `///` are parts of paths
+        let is_part_of_url = content[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c == ':' || c == '/' || c.is_alphanumeric());
+        let rest = &content[start + 2..];
+        if is_part_of_url || !rest.starts_with(|c: char| c.is_alphanumeric()) {
+            continue;
+        }
+
+        let length = rest
+            .find(|c: char| c.is_whitespace() || IPV6_URL_DELIMITERS.contains(&c))
+            .unwrap_or(rest.len());
+        let text = trim_trailing_punctuation(&content[start..start + 2 + length], trailing);
+        if !is_plausible_host(&text[2..]) {
+            continue;
+        }
+        matches.push(UrlMatch {
+            text: text.to_string(),
+            start,
+            end: start + text.len(),
+        });
+    }
+    matches
+}
+
+/// Like [`find_urls`], but also finds local file paths: relative paths starting with `./` or `../`,
+/// Windows paths with a drive letter (`C:\docs\x`) and UNC paths (`\\server\share`).
+///
+/// `file://` uris are already found by [`find_urls`]. A path ends at the first whitespace,
+/// so paths containing spaces are cut off. Use [`LinkScheme::of`] to tell the local references apart from web links
+/// and [`resolve_file_path`] to turn them into absolute paths.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::{find_urls_allow_file_paths, LinkScheme};
+/// let urls = find_urls_allow_file_paths(r"See ./docs/readme, C:\docs\x.txt and https://example.com");
+/// let found: Vec<(&str, LinkScheme)> = urls.iter().map(|it| (it.as_str(), LinkScheme::of(it.as_str()))).collect();
+/// assert_eq!(found, vec![
+///     ("./docs/readme", LinkScheme::File),
+///     (r"C:\docs\x.txt", LinkScheme::File),
+///     ("https://example.com", LinkScheme::Web),
+/// ]);
+/// ```
+pub fn find_urls_allow_file_paths(content: &str) -> Vec<UrlMatch> {
+    let mut matches = find_urls(content);
+    matches.extend(find_file_paths(content, &TRAILING_PUNCTUATION));
+    matches.sort_by_key(|m| m.start);
+    matches
+}
+
+/// Finds the paths described in [`find_urls_allow_file_paths`]
+fn find_file_paths(content: &str, trailing: &[char]) -> Vec<UrlMatch> {
+    let mut matches = vec![];
+    let mut rest_start = 0;
+    while let Some(offset) = content[rest_start..].find(['.', '\\', ':']) {
+        let found = rest_start + offset;
+        rest_start = found + 1;
+        // The path starts at the drive letter before the `:`
+        let start = if content[found..].starts_with(':') {
+            match found.checked_sub(1) {
+                Some(drive) if content.as_bytes()[drive].is_ascii_alphabetic() => drive,
+                _ => continue,
+            }
+        } else {
+            found
+        };
+        let is_delimited = content[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || PATH_PREFIXES.contains(&c));
+        if !is_delimited || !is_local_path(&content[start..]) {
+            continue;
+        }
+
+        let rest = &content[start..];
+        let length = rest
+            .find(|c: char| c.is_whitespace() || IPV6_URL_DELIMITERS.contains(&c))
+            .unwrap_or(rest.len());
+        let text = trim_trailing_punctuation(&rest[..length], trailing);
+        matches.push(UrlMatch {
+            text: text.to_string(),
+            start,
+            end: start + text.len(),
+        });
+        rest_start = start + length.max(1);
+    }
+    matches
+}
+
+/// Characters that may come directly before a path found by [`find_urls_allow_file_paths`]
+const PATH_PREFIXES: [char; 6] = ['"', '\'', '(', '<', '[', '='];
+
+/// Checks if `text` starts with a relative path (`./`, `../`), a Windows path with a drive letter or an UNC path
+fn is_local_path(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let is_separator = |index: usize| matches!(bytes.get(index), Some(b'/' | b'\\'));
+    let is_relative =
+        (text.starts_with('.') && is_separator(1)) || (text.starts_with("..") && is_separator(2));
+    let has_drive = bytes.first().is_some_and(u8::is_ascii_alphabetic)
+        && bytes.get(1) == Some(&b':')
+        && is_separator(2);
+    let is_unc = text.starts_with(r"\\") && bytes.get(2).is_some_and(u8::is_ascii_alphanumeric);
+    is_relative || has_drive || is_unc
+}
+
+/// What kind of resource a link references
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkScheme {
+    /// An `http`- or `https`-url
+    Web,
+    /// A `file`-uri or a local path as found by [`find_urls_allow_file_paths`]
+    File,
+    /// An url with any other scheme (like `mailto:`), or a link without a scheme, that is no explicit local path
+    Other,
+}
+
+impl LinkScheme {
+    /// Classifies `url` by its scheme, schemes are compared case-insensitive
+    /// # Example
+    /// ```
+    /// use crate::link_scraper::helpers::LinkScheme;
+    /// assert_eq!(LinkScheme::of("HTTPS://example.com"), LinkScheme::Web);
+    /// assert_eq!(LinkScheme::of("file:///home/user/notes.txt"), LinkScheme::File);
+    /// assert_eq!(LinkScheme::of("../notes.txt"), LinkScheme::File);
+    /// assert_eq!(LinkScheme::of("mailto:mail@example.com"), LinkScheme::Other);
+    /// ```
+    pub fn of(url: &str) -> Self {
+        if is_local_path(url.trim()) {
+            return Self::File;
+        }
+        match scheme_of(url).as_deref() {
+            Some("http" | "https") => Self::Web,
+            Some("file") => Self::File,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// The scheme of `url` in lower case (without the `:`), if it has one.
+///
+/// Schemes are case-insensitive, so [`find_urls`] also finds urls like `HTTPS://EXAMPLE.COM` and returns them
+/// in their original casing. Use this to classify them.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::scheme_of;
+/// assert_eq!(scheme_of("HtTp://Example.com").as_deref(), Some("http"));
+/// assert_eq!(scheme_of("mailto:mail@example.com").as_deref(), Some("mailto"));
+/// assert_eq!(scheme_of("../relative.html"), None);
+/// ```
+pub fn scheme_of(url: &str) -> Option<Cow<'_, str>> {
+    let (scheme, _) = url.trim_start().split_once(':')?;
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.');
+    if !valid {
+        return None;
+    }
+    Some(if scheme.bytes().any(|c| c.is_ascii_uppercase()) {
+        Cow::Owned(scheme.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(scheme)
+    })
+}
+
+/// Turns a `file`-uri or a local path found by [`find_urls_allow_file_paths`] into a path,
+/// resolving relative paths against `base_dir`.
+///
+/// `.`- and `..`-segments are resolved without accessing the file system. Percent-encoded characters of `file`-uris
+/// are decoded. Windows paths are returned as they are. Returns `None` for all other links,
+/// and for `file`-uris with a host other than `localhost`.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::resolve_file_path;
+/// use std::path::{Path, PathBuf};
+/// let base = Path::new("/home/user/project");
+/// assert_eq!(resolve_file_path("./docs/../README.md", base), Some(PathBuf::from("/home/user/project/README.md")));
+/// assert_eq!(resolve_file_path("file:///tmp/my%20notes.txt", base), Some(PathBuf::from("/tmp/my notes.txt")));
+/// assert_eq!(resolve_file_path("https://example.com", base), None);
+/// ```
+pub fn resolve_file_path(url: &str, base_dir: &Path) -> Option<PathBuf> {
+    let url = url.trim();
+    if let Some(uri) = url
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("file://"))
+        .map(|_| &url[7..])
+    {
+        let path = uri.strip_prefix("localhost").unwrap_or(uri);
+        if !path.starts_with('/') {
+            return None;
+        }
+        let path = percent_decode(path);
+        // `file:///C:/docs` references the Windows path `C:/docs`
+        return Some(match path.strip_prefix('/') {
+            Some(windows_path) if is_local_path(windows_path) => PathBuf::from(windows_path),
+            _ => PathBuf::from(path),
+        });
+    }
+    if !is_local_path(url) {
+        return None;
+    }
+    if !url.starts_with('.') {
+        return Some(PathBuf::from(url));
+    }
+    let mut resolved = base_dir.to_path_buf();
+    for segment in url.split(['/', '\\']) {
+        match segment {
+            ".." => {
+                resolved.pop();
+            }
+            "." | "" => {}
+            segment => resolved.push(segment),
+        }
+    }
+    Some(resolved)
+}
+
+/// Decodes all `%XX`-sequences, invalid sequences are kept as they are
+pub(crate) fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = match bytes.get(i + 1..i + 3) {
+            Some(hex) if bytes[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit) => {
+                std::str::from_utf8(hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            }
+            _ => None,
+        };
+        match byte {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Finds the value of the attribute with the given local name
+#[cfg(any(
+    feature = "feed",
+    feature = "geo",
+    feature = "opml",
+    feature = "ooxml",
+    feature = "epub"
+))]
+pub(crate) fn find_attribute<'a>(
+    attributes: &'a [xml::attribute::OwnedAttribute],
+    local_name: &str,
+) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|it| it.name.local_name == local_name)
+        .map(|it| it.value.as_str())
+}
+
+/// Top-level domains accepted by [`find_urls_allow_schemeless`] for hosts not starting with `www.`.
+///
+/// Domains that are also common file extensions (like `.md`, `.rs` or `.sh`) are left out on purpose.
+pub const SCHEMELESS_TLDS: [&str; 48] = [
+    "com", "org", "net", "edu", "gov", "mil", "int", "info", "biz", "name", "pro", "io", "dev",
+    "app", "ai", "co", "me", "tv", "us", "uk", "de", "fr", "es", "it", "nl", "be", "ch", "at",
+    "se", "no", "dk", "fi", "ie", "eu", "ca", "au", "nz", "jp", "cn", "in", "br", "ru", "mx", "ar",
+    "za", "kr", "tw", "gr",
+];
+
+/// Prepends `https://` to `url`, if it has no scheme (e.g. when found by [`find_urls_allow_schemeless`]).
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::add_default_scheme;
+/// assert_eq!(add_default_scheme("www.example.com"), "https://www.example.com");
+/// assert_eq!(add_default_scheme("http://www.example.com"), "http://www.example.com");
+/// ```
+pub fn add_default_scheme(url: &str) -> Cow<'_, str> {
+    if has_scheme(url) {
+        Cow::Borrowed(url)
+    } else {
+        Cow::Owned(format!("https://{url}"))
+    }
+}
+
+/// Checks if `url` starts with a scheme followed by `://`
+fn has_scheme(url: &str) -> bool {
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+    })
+}
+
+/// Checks if the host of a schemeless `url` looks like a real domain, see [`find_urls_allow_schemeless`]
+fn is_plausible_host(url: &str) -> bool {
+    let host = url
+        .split(['/', '?', '#', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let Some((rest, tld)) = host.rsplit_once('.') else {
+        return false;
+    };
+    if rest.is_empty() {
+        return false;
+    }
+    (host.starts_with("www.") && rest != "www") || SCHEMELESS_TLDS.contains(&tld)
+}
+
+/// Removes `trailing` characters from the end of `url`.
+///
+/// Closing brackets are only removed, if they are not balanced by an opening bracket inside the url,
+/// so links like `https://en.wikipedia.org/wiki/Rust_(disambiguation)` stay intact.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::{trim_trailing_punctuation, TRAILING_PUNCTUATION};
+/// assert_eq!(trim_trailing_punctuation("https://a.com/b).", &TRAILING_PUNCTUATION), "https://a.com/b");
+/// assert_eq!(trim_trailing_punctuation("https://a.com/(b).", &TRAILING_PUNCTUATION), "https://a.com/(b)");
+/// ```
+pub fn trim_trailing_punctuation<'a>(url: &'a str, trailing: &[char]) -> &'a str {
+    let mut trimmed = url;
+    while let Some(last) = trimmed.chars().next_back() {
+        if !trailing.contains(&last) {
+            break;
+        }
+        let opening = match last {
+            ')' => Some('('),
+            ']' => Some('['),
+            '}' => Some('{'),
+            '>' => Some('<'),
+            _ => None,
+        };
+        if let Some(opening) = opening {
+            let opened = trimmed.matches(opening).count();
+            let closed = trimmed.matches(last).count();
+            if closed <= opened {
+                break;
+            }
+        }
+        trimmed = &trimmed[..trimmed.len() - last.len_utf8()];
+    }
+    trimmed
+}
+
+/// A url found inside a string
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlMatch {
+    text: String,
+    start: usize,
+    end: usize,
+}
+
+impl UrlMatch {
+    /// The matched url
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The byte-offset of the start of the url inside the searched string
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte-offset of the end of the url inside the searched string (exclusive)
+    pub fn end(&self) -> usize {
+        self.end
+    }
+}
+
+/// Like [`find_urls`], but also finds urls with one of the given `schemes` (e.g. `mailto`, `tel` or `sms`),
+/// which do not need a `//`-authority.
+///
+/// The schemes are matched case-insensitive and the results are ordered by their position in `content`.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::find_urls_with_schemes;
+/// let urls = find_urls_with_schemes("Contact mailto:support@example.com or https://example.com", &["mailto"]);
+/// assert_eq!(urls[0].as_str(), "mailto:support@example.com");
+/// assert_eq!(urls[1].as_str(), "https://example.com");
+/// ```
+pub fn find_urls_with_schemes(content: &str, schemes: &[&str]) -> Vec<UrlMatch> {
+    let mut matches = find_urls(content);
+
+    let lowercase_content = content.to_ascii_lowercase();
+    for scheme in schemes {
+        let prefix = format!("{}:", scheme.to_ascii_lowercase());
+        for (start, _) in lowercase_content.match_indices(&prefix) {
+            let preceded_by_word = content[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '+' || c == '-' || c == '.');
+            if preceded_by_word || matches.iter().any(|m| m.start <= start && start < m.end) {
+                continue;
+            }
+
+            let rest = &content[start + prefix.len()..];
+            let length = rest
+                .find(|c: char| c.is_whitespace() || SCHEME_URL_DELIMITERS.contains(&c))
+                .unwrap_or(rest.len());
+            let payload = trim_trailing_punctuation(&rest[..length], &TRAILING_PUNCTUATION);
+            if payload.is_empty() {
+                continue;
+            }
+
+            let end = start + prefix.len() + payload.len();
+            matches.push(UrlMatch {
+                text: content[start..end].to_string(),
+                start,
+                end,
+            });
+        }
+    }
+
+    matches.sort_by_key(|m| m.start);
+    matches
+}
+
+/// Finds the urls of all image candidates in a `srcset`-attribute like `small.png 480w, big.png 2x`.
+///
+/// The descriptors (like `480w` or `2x`) are not part of the urls. The urls are returned as they are written,
+/// so they might be relative links or contain commas (only a comma at their end separates the candidates).
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::find_srcset_urls;
+/// let urls = find_srcset_urls("https://example.com/small.png 1x,https://example.com/big.png 2x");
+/// assert_eq!(urls[0].as_str(), "https://example.com/small.png");
+/// assert_eq!(urls[1].as_str(), "https://example.com/big.png");
+/// ```
+pub fn find_srcset_urls(srcset: &str) -> Vec<UrlMatch> {
+    let mut matches = vec![];
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+
+        let start = srcset.len() - rest.len();
+        let url_length = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (url, after_url) = rest.split_at(url_length);
+        rest = after_url;
+        let url = if url.ends_with(',') {
+            url.trim_end_matches(',')
+        } else {
+            // Skip the descriptors of the candidate
+            rest = rest.find(',').map_or("", |comma| &rest[comma + 1..]);
+            url
+        };
+        matches.push(UrlMatch {
+            text: url.to_string(),
+            start,
+            end: start + url.len(),
+        });
+    }
+    matches
+}
+
+/// Characters that end a url found by [`find_urls_with_schemes`]
+const SCHEME_URL_DELIMITERS: [char; 14] = [
+    '<', '>', '"', '\'', '`', '(', ')', '[', ']', '{', '}', '|', '\\', '^',
+];
+
+/// Finds the targets of all css `url()`-functions and `@import`-rules with a string in `css`,
+/// e.g. in a `style`-attribute or a stylesheet.
+///
+/// The targets are returned as they are written (without quotes), so they might be relative links.
+/// # Example
+/// ```
+/// use crate::link_scraper::helpers::find_css_urls;
+/// let urls = find_css_urls("background: url('https://example.com/bg.png') no-repeat");
+/// assert_eq!(urls[0].as_str(), "https://example.com/bg.png");
+/// ```
+pub fn find_css_urls(css: &str) -> Vec<UrlMatch> {
+    let mut matches = vec![];
+    let lowercase_css = css.to_ascii_lowercase();
+    for (function_start, function) in lowercase_css.match_indices("url(") {
+        let preceded_by_word = css[..function_start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if preceded_by_word {
+            continue;
+        }
+
+        let argument_start = function_start + function.len();
+        let rest = &css[argument_start..];
+        let argument = rest.trim_start();
+        let (url, quote_length) = match argument.chars().next() {
+            // A quoted url may contain parentheses, so the closing quote has to be found first
+            Some(quote @ ('"' | '\'')) => {
+                let Some(length) = argument[1..].find(quote) else {
+                    continue;
+                };
+                if !argument[length + 2..].trim_start().starts_with(')') {
+                    continue;
+                }
+                (&argument[1..=length], 1)
+            }
+            _ => {
+                let Some(length) = argument.find(')') else {
+                    continue;
+                };
+                let url = argument[..length].trim_end();
+                if url.contains(['"', '\'']) || url.contains(char::is_whitespace) {
+                    continue;
+                }
+                (url, 0)
+            }
+        };
+        if url.is_empty() {
+            continue;
+        }
+
+        let start = argument_start + (rest.len() - argument.len()) + quote_length;
+        matches.push(UrlMatch {
+            text: url.to_string(),
+            start,
+            end: start + url.len(),
+        });
+    }
+
+    // `@import url(...)` is already covered above, but `@import "..."` is not a function
+    for (rule_start, rule) in lowercase_css.match_indices("@import") {
+        let rest = &css[rule_start + rule.len()..];
+        let argument = rest.trim_start();
+        let Some(quote) = argument.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        let Some(length) = argument[1..].find(quote) else {
+            continue;
+        };
+
+        let start = rule_start + rule.len() + (rest.len() - argument.len()) + 1;
+        matches.push(UrlMatch {
+            text: argument[1..=length].to_string(),
+            start,
+            end: start + length,
+        });
+    }
+
+    matches.sort_by_key(|m| m.start);
+    matches
 }
 
 #[macro_export]
@@ -31,11 +878,27 @@ macro_rules! gen_scrape_from_slice {
     };
 }
 
+#[macro_export]
+macro_rules! gen_scrape_with_options {
+    ($function_name:ident($bound:ident $(+ $bounds:ident)*) -> Result<Vec<$link:ty>, $error:ty>) => {
+        /// Like [`scrape`], but filters, normalizes, deduplicates and limits the links as set in `options`.
+        pub fn scrape_with_options<R>(
+            reader: R,
+            options: &$crate::options::ScrapeOptions,
+        ) -> Result<Vec<$link>, $error>
+        where
+            R: std::io::$bound $(+ std::io::$bounds)*,
+        {
+            Ok(options.apply($function_name(reader)?))
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! gen_scrape_from_file {
-    ($function_name:ident(AsRef<[u8]>) -> $output_type:ty) => {
+    ($function_name:ident(AsRef<[u8]>) -> Result<Vec<$link:ty>, $error:ty>) => {
         /// Convenience function, that reads a file and uses [`scrape`] to scrape links from its content.
-        pub fn scrape_from_file<P>(path: P) -> $output_type
+        pub fn scrape_from_file<P>(path: P) -> Result<Vec<$link>, $error>
         where
             P: AsRef<std::path::Path>,
         {
@@ -48,15 +911,400 @@ macro_rules! gen_scrape_from_file {
             };
             $function_name(bytes)
         }
+
+        #[cfg(feature = "async")]
+        /// Async version of [`scrape_from_file`], that reads the file with [`tokio::fs`] and scrapes its
+        /// content on a blocking thread (see [`tokio::task::spawn_blocking`]).
+        ///
+        /// Requires a running tokio runtime.
+        pub async fn scrape_from_file_async<P>(path: P) -> Result<Vec<$link>, $error>
+        where
+            P: AsRef<std::path::Path>,
+        {
+            let bytes = tokio::fs::read(path).await?;
+            tokio::task::spawn_blocking(move || $function_name(bytes))
+                .await
+                .map_err(std::io::Error::from)?
+        }
+
+        /// Like [`scrape_from_file`], but every link also carries the path of the file, see [`crate::link::Located`].
+        pub fn scrape_from_file_located<P>(
+            path: P,
+        ) -> Result<Vec<$crate::link::Located<$link>>, $error>
+        where
+            P: AsRef<std::path::Path>,
+        {
+            let path = path.as_ref();
+            scrape_from_file(path).map(|links| $crate::link::Located::all(path, links))
+        }
     };
 
-    ($function_name:ident(Read) -> $output_type:ty) => {
+    ($function_name:ident(Read) -> Result<Vec<$link:ty>, $error:ty>) => {
         /// Convenience function, that reads a file and uses [`scrape`] to scrape links from its content.
-        pub fn scrape_from_file<P>(path: P) -> $output_type
+        pub fn scrape_from_file<P>(path: P) -> Result<Vec<$link>, $error>
         where
             P: AsRef<std::path::Path>,
         {
             $function_name(std::io::BufReader::new(std::fs::File::open(path)?))
         }
+
+        #[cfg(feature = "async")]
+        /// Async version of [`scrape_from_file`], that reads the file with [`tokio::fs`] and scrapes its
+        /// content on a blocking thread (see [`tokio::task::spawn_blocking`]).
+        ///
+        /// Requires a running tokio runtime.
+        pub async fn scrape_from_file_async<P>(path: P) -> Result<Vec<$link>, $error>
+        where
+            P: AsRef<std::path::Path>,
+        {
+            let bytes = tokio::fs::read(path).await?;
+            tokio::task::spawn_blocking(move || $function_name(std::io::Cursor::new(bytes)))
+                .await
+                .map_err(std::io::Error::from)?
+        }
+
+        /// Like [`scrape_from_file`], but every link also carries the path of the file, see [`crate::link::Located`].
+        pub fn scrape_from_file_located<P>(
+            path: P,
+        ) -> Result<Vec<$crate::link::Located<$link>>, $error>
+        where
+            P: AsRef<std::path::Path>,
+        {
+            let path = path.as_ref();
+            scrape_from_file(path).map(|links| $crate::link::Located::all(path, links))
+        }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trim_sentence_terminal_period_test() {
+        let urls = find_urls("For more information see https://example.com/info.");
+        assert_eq!(urls[0].as_str(), "https://example.com/info");
+        assert_eq!(urls[0].end(), urls[0].start() + urls[0].as_str().len());
+    }
+
+    #[test]
+    fn keep_balanced_parenthesis_test() {
+        let urls = find_urls("(see https://en.wikipedia.org/wiki/Rust_(disambiguation))");
+        assert_eq!(
+            urls[0].as_str(),
+            "https://en.wikipedia.org/wiki/Rust_(disambiguation)"
+        );
+    }
+
+    #[test]
+    fn trim_unbalanced_parenthesis_test() {
+        let urls = find_urls("(https://example.com/a)");
+        assert_eq!(urls[0].as_str(), "https://example.com/a");
+
+        assert_eq!(
+            trim_trailing_punctuation("https://example.com/a)!", &TRAILING_PUNCTUATION),
+            "https://example.com/a"
+        );
+        assert_eq!(
+            trim_trailing_punctuation("https://example.com/a)!", &['!']),
+            "https://example.com/a)"
+        );
+    }
+
+    #[test]
+    fn custom_trailing_punctuation_test() {
+        let urls = find_urls_with_trailing_punctuation("https://example.com/a/*", &['*', '/']);
+        assert_eq!(urls[0].as_str(), "https://example.com/a");
+    }
+
+    #[test]
+    fn find_urls_with_schemes_test() {
+        let text = "Mail mailto:support+name@example.com, call TEL:+15551234 or text sms:+15551234?body=hi. \
+            Also see https://example.com/mailto:ignored";
+        let urls = find_urls_with_schemes(text, &["mailto", "tel", "sms"]);
+        let urls: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "mailto:support+name@example.com",
+                "TEL:+15551234",
+                "sms:+15551234?body=hi",
+                "https://example.com/mailto:ignored",
+            ]
+        );
+    }
+
+    #[test]
+    fn find_urls_with_schemes_positions_test() {
+        let text = "<a href=\"mailto:a@b.com\">";
+        let urls = find_urls_with_schemes(text, &["mailto"]);
+        assert_eq!(urls.len(), 1);
+        assert_eq!(urls[0].start(), 9);
+        assert_eq!(urls[0].end(), 9 + "mailto:a@b.com".len());
+        assert_eq!(&text[urls[0].start()..urls[0].end()], urls[0].as_str());
+    }
+
+    #[test]
+    fn find_urls_ignores_schemes_test() {
+        assert!(find_urls("mailto:support@example.com tel:+15551234").is_empty());
+    }
+
+    #[test]
+    fn find_ip_literal_urls_test() {
+        let text = "see http://192.168.0.1:8080/admin.";
+        let urls = find_urls(text);
+        assert_eq!(urls[0].as_str(), "http://192.168.0.1:8080/admin");
+
+        let text = "[http://[2001:db8::1]/index.html] and <https://[::1]:8443>, (http://[fe80::1%25eth0]/a_(b))";
+        let urls = find_urls(text);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(
+            found,
+            vec![
+                "http://[2001:db8::1]/index.html",
+                "https://[::1]:8443",
+                "http://[fe80::1%25eth0]/a_(b)"
+            ]
+        );
+        assert_eq!(&text[urls[0].start()..urls[0].end()], urls[0].as_str());
+        assert!(find_urls("http://[not::an:ip:address:at:all]/").is_empty());
+    }
+
+    #[test]
+    fn find_percent_encoded_urls_test() {
+        let text = "see https://x.test.com/%E2%9C%93/a%20b?q=%C3%A4#%2F, then";
+        let urls = find_urls(text);
+        assert_eq!(
+            urls[0].as_str(),
+            "https://x.test.com/%E2%9C%93/a%20b?q=%C3%A4#%2F"
+        );
+    }
+
+    #[test]
+    fn find_unicode_urls_test() {
+        let text =
+            "(https://例え.テスト/パス?q=値) and https://例え.jp/パス. or https://例え.テスト";
+        let urls = find_urls(text);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(
+            found,
+            vec![
+                "https://例え.テスト/パス?q=値",
+                "https://例え.jp/パス",
+                "https://例え.テスト"
+            ]
+        );
+        assert_eq!(&text[urls[0].start()..urls[0].end()], urls[0].as_str());
+        assert!(find_urls("https://例え.1/パス").is_empty());
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn find_ascii_urls_test() {
+        let text = "https://例え.テスト/パス?q=値 https://x.test.com/%E2%9C%93";
+        let urls = UrlFinder::new().ascii().find_all(text);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(
+            found,
+            vec![
+                "https://xn--r8jz45g.xn--zckzah/%E3%83%91%E3%82%B9?q=%E5%80%A4",
+                "https://x.test.com/%E2%9C%93"
+            ]
+        );
+        assert_eq!(urls[0].end(), "https://例え.テスト/パス?q=値".len());
+    }
+
+    #[test]
+    fn find_urls_allow_schemeless_test() {
+        let text = "Visit www.x.com or example.co.uk/a, and https://a.com/b.";
+        let urls = find_urls_allow_schemeless(text);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(
+            found,
+            vec!["www.x.com", "example.co.uk/a", "https://a.com/b"]
+        );
+        assert_eq!(&text[urls[1].start()..urls[1].end()], "example.co.uk/a");
+
+        let normalized: Vec<_> = urls
+            .iter()
+            .map(|it| add_default_scheme(it.as_str()))
+            .collect();
+        assert_eq!(
+            normalized,
+            vec![
+                "https://www.x.com",
+                "https://example.co.uk/a",
+                "https://a.com/b"
+            ]
+        );
+    }
+
+    #[test]
+    fn find_urls_allow_schemeless_false_positives_test() {
+        let text =
+            "Edit config.json, see README.md, run v1.2.3 or 192.168.0.1 and mail me@example.com";
+        assert!(find_urls_allow_schemeless(text).is_empty());
+        assert!(find_urls("www.x.com").is_empty());
+    }
+
+    #[test]
+    fn find_urls_allow_protocol_relative_test() {
+        let text = r#"<script src="//cdn.example.com/lib.js"></script> (see //www.x.org/a), https://a.com//b.com and http://c.com"#;
+        let urls = find_urls_allow_protocol_relative(text);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(
+            found,
+            vec![
+                "//cdn.example.com/lib.js",
+                "//www.x.org/a",
+                "https://a.com//b.com",
+                "http://c.com"
+            ]
+        );
+        assert_eq!(&text[urls[0].start()..urls[0].end()], urls[0].as_str());
+        assert!(find_urls_allow_protocol_relative("// TODO, //config.json or a//b.com").is_empty());
+        assert!(find_urls("//cdn.example.com/lib.js").is_empty());
+    }
+
+    #[test]
+    fn resolve_protocol_relative_test() {
+        let urls = find_urls_allow_protocol_relative("<img src='//cdn.example.com/lib.js'>");
+        assert_eq!(
+            resolve_protocol_relative(urls[0].as_str(), "https"),
+            "https://cdn.example.com/lib.js"
+        );
+        assert_eq!(
+            resolve_protocol_relative("https://a.com", "http"),
+            "https://a.com"
+        );
+    }
+
+    #[test]
+    fn find_srcset_urls_test() {
+        let srcset = " a.test.com/1.png 320w,\n  https://b.test.com/2,3.png 640w , https://c.test.com/4.png,";
+        let found = find_srcset_urls(srcset);
+        let urls: Vec<&str> = found.iter().map(|it| it.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "a.test.com/1.png",
+                "https://b.test.com/2,3.png",
+                "https://c.test.com/4.png"
+            ]
+        );
+        assert!(found
+            .iter()
+            .all(|it| &srcset[it.start()..it.end()] == it.as_str()));
+        assert!(find_srcset_urls(" , ").is_empty());
+    }
+
+    #[test]
+    fn find_urls_case_insensitive_scheme_test() {
+        let urls = find_urls("LEGACY: HTTPS://EXAMPLE.COM/INDEX.HTM and HtTp://Example.com/a");
+        let found: Vec<(&str, Option<Cow<str>>)> = urls
+            .iter()
+            .map(|it| (it.as_str(), scheme_of(it.as_str())))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "HTTPS://EXAMPLE.COM/INDEX.HTM",
+                    Some(Cow::Borrowed("https"))
+                ),
+                ("HtTp://Example.com/a", Some(Cow::Borrowed("http"))),
+            ]
+        );
+        assert_eq!(LinkScheme::of(urls[0].as_str()), LinkScheme::Web);
+        assert!(matches!(
+            scheme_of("https://a.test.com"),
+            Some(Cow::Borrowed("https"))
+        ));
+        assert_eq!(scheme_of("1http://a.test.com"), None);
+        assert_eq!(scheme_of("no scheme: here"), None);
+    }
+
+    #[test]
+    fn find_urls_allow_file_paths_test() {
+        let content = r#"Open file:///home/user/notes.txt or "./docs/readme", see (..\shared\x.md).
+            Then C:\docs\x and \\server\share\y. Not https://a.test.com/./z, v1.2 or ratio 1:2."#;
+        let urls = find_urls_allow_file_paths(content);
+        let found: Vec<(&str, LinkScheme)> = urls
+            .iter()
+            .map(|it| (it.as_str(), LinkScheme::of(it.as_str())))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("file:///home/user/notes.txt", LinkScheme::File),
+                ("./docs/readme", LinkScheme::File),
+                (r"..\shared\x.md", LinkScheme::File),
+                (r"C:\docs\x", LinkScheme::File),
+                (r"\\server\share\y", LinkScheme::File),
+                ("https://a.test.com/./z", LinkScheme::Web),
+            ]
+        );
+        // Without the opt-in only the uri is found
+        assert_eq!(find_urls(content).len(), 2);
+    }
+
+    #[test]
+    fn resolve_file_path_test() {
+        let base = Path::new("/home/user/project");
+        assert_eq!(
+            resolve_file_path("./docs/readme", base),
+            Some(PathBuf::from("/home/user/project/docs/readme"))
+        );
+        assert_eq!(
+            resolve_file_path(r"..\shared\.\x.md", base),
+            Some(PathBuf::from("/home/user/shared/x.md"))
+        );
+        assert_eq!(
+            resolve_file_path("file://localhost/etc/hosts", base),
+            Some(PathBuf::from("/etc/hosts"))
+        );
+        assert_eq!(
+            resolve_file_path("FILE:///C:/docs/a%20b.txt", base),
+            Some(PathBuf::from("C:/docs/a b.txt"))
+        );
+        assert_eq!(resolve_file_path("file://server/share", base), None);
+        assert_eq!(resolve_file_path("docs/readme", base), None);
+    }
+
+    #[test]
+    fn find_css_urls_test() {
+        let css = "a { background: URL( \"https://example.com/a.png\" ) } b { mask: url(img/b.svg#m); } c { background: myurl(no) }";
+        let urls = find_css_urls(css);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(found, vec!["https://example.com/a.png", "img/b.svg#m"]);
+        assert_eq!(&css[urls[1].start()..urls[1].end()], "img/b.svg#m");
+
+        let css = "a { background: url(\"a(b).png\") } b { background: url(\"x') } c { background: url('c.png') }";
+        let urls = find_css_urls(css);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(found, vec!["a(b).png", "c.png"]);
+        assert_eq!(&css[urls[0].start()..urls[0].end()], "a(b).png");
+
+        let css = "@import 'theme.css'; @import url(print.css) print;";
+        let urls = find_css_urls(css);
+        let found: Vec<&str> = urls.iter().map(|it| it.as_str()).collect();
+        assert_eq!(found, vec!["theme.css", "print.css"]);
+        assert_eq!(&css[urls[0].start()..urls[0].end()], "theme.css");
+    }
+
+    #[test]
+    fn url_finder_matches_find_urls_test() {
+        let content =
+            "Visit https://a.test.com/(b). or mail a@b.test.com, ftp://c.test.com/x?y=1; \
+            http://[2001:db8::1]:8080/z] www.d.test.com \"https://e.test.com\"";
+        let finder = UrlFinder::new();
+        assert_eq!(finder.find_all(content), find_urls(content));
+        assert_eq!(finder.find_all(content).len(), 4);
+
+        let finder = UrlFinder::with_trailing_punctuation(&['/', ']']);
+        assert_eq!(
+            finder.find_all(content),
+            find_urls_with_trailing_punctuation(content, &['/', ']'])
+        );
+    }
+}
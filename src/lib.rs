@@ -7,7 +7,22 @@
 #[cfg(feature = "any_format")]
 /// Use only if you're not sure what format your file will be.
 pub mod any_format_scraper;
+/// Chooses the format-specific scrape-function by the file-content and -extension.
+pub mod dispatch;
 /// Contains format-specific scrape-functions. Prefer over [`any_format_scraper`].
 pub mod formats;
 /// Helper functions
 pub mod helpers;
+/// Common abstraction over the links of all formats
+pub mod link;
+/// A location of links in the same shape for all formats
+pub mod location;
+/// Options shared by the `scrape_with_options`-functions of all formats
+pub mod options;
+/// Post-processing of scraped links
+pub mod processing;
+#[cfg(all(
+    feature = "serde",
+    any(feature = "xml", feature = "xlink", feature = "ooxml", feature = "odf")
+))]
+mod serde_helpers;
@@ -0,0 +1,198 @@
+use crate::location::SourceLocation;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+#[cfg(any(feature = "xml", feature = "xlink", feature = "ooxml", feature = "odf"))]
+use xml::common::TextPosition;
+
+/// Implemented by the links of all formats, so they can be handled regardless of their source format.
+///
+/// Links are [`Send`], so the links of several files can be scraped in parallel.
+pub trait Link: Send {
+    /// The scraped url
+    fn url(&self) -> &str;
+
+    /// Replaces the scraped url, e.g. after resolving it
+    fn set_url(&mut self, url: String);
+
+    /// Where the link was found inside its source
+    fn location(&self) -> Location;
+
+    /// Where the link was found inside its source, in the same shape for all formats.
+    ///
+    /// By default this converts [`Link::location`], formats with a path to the value containing
+    /// the link (like the json-pointer of json) return it as [`SourceLocation::Pointer`] instead.
+    fn source_location(&self) -> SourceLocation {
+        self.location().into()
+    }
+
+    /// The base-urls declared inside the source (e.g. by `xml:base`), that apply to this link.
+    /// Ordered from the outermost to the innermost declaration, each one relative to the previous one.
+    fn bases(&self) -> &[String] {
+        &[]
+    }
+}
+
+impl<L: Link + ?Sized> Link for Box<L> {
+    fn url(&self) -> &str {
+        (**self).url()
+    }
+
+    fn set_url(&mut self, url: String) {
+        (**self).set_url(url)
+    }
+
+    fn location(&self) -> Location {
+        (**self).location()
+    }
+
+    fn source_location(&self) -> SourceLocation {
+        (**self).source_location()
+    }
+
+    fn bases(&self) -> &[String] {
+        (**self).bases()
+    }
+}
+
+/// The location of a link inside its source.
+///
+/// Which variant is used depends on the format the link was scraped from.
+/// Some variants only exist with the features of their formats, so matches on it always need a wildcard arm.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Location {
+    /// A position inside a xml-based document
+    #[cfg(any(feature = "xml", feature = "xlink", feature = "ooxml", feature = "odf"))]
+    TextPosition(TextPosition),
+
+    /// A position inside one of the files of an archive (ooxml, odf, epub)
+    #[cfg(any(feature = "ooxml", feature = "odf", feature = "epub"))]
+    ArchiveFile {
+        file: String,
+        position: TextPosition,
+    },
+
+    /// A position inside a text-based document
+    LineAndPos {
+        /// The line, starting at 1
+        line: usize,
+        /// The byte-offset of the link inside its line
+        pos: usize,
+        /// The byte-offset of the end of the link relative to the start of its line (exclusive)
+        end: usize,
+        /// The byte-offset of the link from the start of the document
+        byte_offset: usize,
+        /// The column of the link inside its line, counted in unicode scalar values and starting at 0
+        column: usize,
+    },
+
+    /// A cell of a table
+    Cell {
+        /// The row, starting at 0
+        row: usize,
+        /// The column, starting at 0
+        column: usize,
+    },
+
+    /// A line, starting at 1
+    Line(usize),
+
+    /// A page of a document, starting at 1
+    Page(usize),
+
+    /// The exif-field of an image
+    ExifField(String),
+
+    /// The format does not provide any location-information
+    Unknown,
+}
+
+/// A link together with the path of the file it was scraped from.
+///
+/// Returned by the `scrape_from_file_located`-functions of all formats,
+/// so links from different files can be merged without losing track of their source.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Located<L> {
+    /// The file the link was scraped from
+    pub path: PathBuf,
+    pub link: L,
+}
+
+impl<L> Located<L> {
+    /// Attaches `path` to all `links`
+    pub fn all(path: &Path, links: Vec<L>) -> Vec<Located<L>> {
+        links
+            .into_iter()
+            .map(|link| Located {
+                path: path.to_path_buf(),
+                link,
+            })
+            .collect()
+    }
+}
+
+impl<L: Link> Link for Located<L> {
+    fn url(&self) -> &str {
+        self.link.url()
+    }
+
+    fn set_url(&mut self, url: String) {
+        self.link.set_url(url)
+    }
+
+    fn location(&self) -> Location {
+        self.link.location()
+    }
+
+    fn source_location(&self) -> SourceLocation {
+        self.link.source_location()
+    }
+
+    fn bases(&self) -> &[String] {
+        self.link.bases()
+    }
+}
+
+impl<L: Display> Display for Located<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.link)
+    }
+}
+
+/// The links of a document scraped with an upper bound on their number, see the `scrape_limited`-functions.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limited<L> {
+    /// At most as many links as the limit allows, in the order they were found
+    pub links: Vec<L>,
+    /// Whether the document contains more links, that were not collected
+    pub truncated: bool,
+}
+
+#[cfg(all(test, feature = "plaintext"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic_link_test() {
+        fn urls(links: &[impl Link]) -> Vec<&str> {
+            links.iter().map(|link| link.url()).collect()
+        }
+
+        let links =
+            crate::formats::plaintext::scrape_from_slice("foo\nbar https://test.com".as_bytes())
+                .unwrap();
+        assert_eq!(urls(&links), vec!["https://test.com"]);
+        assert_eq!(
+            links[0].location(),
+            Location::LineAndPos {
+                line: 2,
+                pos: 4,
+                end: 20,
+                byte_offset: 8,
+                column: 4
+            }
+        );
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(feature = "plaintext")]
+use crate::formats::plaintext::TextFileLinkLocation;
+use crate::link::Location;
+#[cfg(any(feature = "xml", feature = "xlink", feature = "ooxml", feature = "odf"))]
+use xml::common::TextPosition;
+
+/// The location of a link in the same shape for all formats, see [`crate::link::Link::source_location`].
+///
+/// Unlike [`Location`] the variants do not depend on the enabled features, but details that only
+/// some formats provide (like the file inside an archive) are dropped.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SourceLocation {
+    /// A position inside a text-based document
+    LineColumn {
+        /// The line, starting at 1
+        line: usize,
+        /// The column inside the line, counted in unicode scalar values and starting at 0.
+        /// [None] if the format only provides the line.
+        column: Option<usize>,
+        /// The byte-offset from the start of the document, if the format provides it
+        byte_offset: Option<usize>,
+    },
+
+    /// The path to the value containing the link inside a structured document, in the syntax of its format.
+    /// Like the json-pointer of json, the key path of yaml, toml and property lists,
+    /// the name of an exif-field or `/row/column` for a cell of a table.
+    Pointer(String),
+
+    /// A page of a document
+    Page {
+        /// The page, starting at 1
+        number: usize,
+    },
+
+    /// The format does not provide any location-information
+    Unknown,
+}
+
+#[cfg(any(feature = "xml", feature = "xlink", feature = "ooxml", feature = "odf"))]
+impl From<TextPosition> for SourceLocation {
+    /// The row of a [`TextPosition`] starts at 0, so it is shifted by one
+    fn from(position: TextPosition) -> Self {
+        SourceLocation::LineColumn {
+            line: position.row as usize + 1,
+            column: Some(position.column as usize),
+            byte_offset: None,
+        }
+    }
+}
+
+#[cfg(feature = "plaintext")]
+impl From<&TextFileLinkLocation> for SourceLocation {
+    fn from(location: &TextFileLinkLocation) -> Self {
+        SourceLocation::LineColumn {
+            line: location.line,
+            column: Some(location.column),
+            byte_offset: Some(location.byte_offset),
+        }
+    }
+}
+
+#[cfg(feature = "plaintext")]
+impl From<TextFileLinkLocation> for SourceLocation {
+    fn from(location: TextFileLinkLocation) -> Self {
+        (&location).into()
+    }
+}
+
+impl From<Location> for SourceLocation {
+    fn from(location: Location) -> Self {
+        match location {
+            #[cfg(any(feature = "xml", feature = "xlink", feature = "ooxml", feature = "odf"))]
+            Location::TextPosition(position) => position.into(),
+            #[cfg(any(feature = "ooxml", feature = "odf", feature = "epub"))]
+            Location::ArchiveFile { position, .. } => position.into(),
+            Location::LineAndPos {
+                line,
+                byte_offset,
+                column,
+                ..
+            } => SourceLocation::LineColumn {
+                line,
+                column: Some(column),
+                byte_offset: Some(byte_offset),
+            },
+            Location::Cell { row, column } => SourceLocation::Pointer(format!("/{row}/{column}")),
+            Location::Line(line) => SourceLocation::LineColumn {
+                line,
+                column: None,
+                byte_offset: None,
+            },
+            Location::Page(number) => SourceLocation::Page { number },
+            Location::ExifField(field) => SourceLocation::Pointer(field),
+            Location::Unknown => SourceLocation::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "xml", feature = "xlink", feature = "ooxml", feature = "odf"))]
+    #[test]
+    fn from_text_position_test() {
+        let position = TextPosition { row: 2, column: 5 };
+        let expected = SourceLocation::LineColumn {
+            line: 3,
+            column: Some(5),
+            byte_offset: None,
+        };
+        assert_eq!(SourceLocation::from(position), expected);
+        assert_eq!(
+            SourceLocation::from(Location::TextPosition(position)),
+            expected
+        );
+    }
+
+    #[cfg(any(feature = "ooxml", feature = "odf", feature = "epub"))]
+    #[test]
+    fn from_archive_file_test() {
+        let location = Location::ArchiveFile {
+            file: "word/document.xml".to_string(),
+            position: TextPosition { row: 0, column: 0 },
+        };
+        assert_eq!(
+            SourceLocation::from(location),
+            SourceLocation::LineColumn {
+                line: 1,
+                column: Some(0),
+                byte_offset: None,
+            }
+        );
+    }
+
+    #[cfg(feature = "plaintext")]
+    #[test]
+    fn from_text_file_link_location_test() {
+        let links =
+            crate::formats::plaintext::scrape_from_slice("foo\nbär https://test.com".as_bytes())
+                .unwrap();
+        let expected = SourceLocation::LineColumn {
+            line: 2,
+            column: Some(4),
+            byte_offset: Some(9),
+        };
+        assert_eq!(SourceLocation::from(&links[0].location), expected);
+        assert_eq!(SourceLocation::from(links[0].location.clone()), expected);
+    }
+
+    #[test]
+    fn from_location_test() {
+        assert_eq!(
+            SourceLocation::from(Location::LineAndPos {
+                line: 2,
+                pos: 5,
+                end: 21,
+                byte_offset: 9,
+                column: 4,
+            }),
+            SourceLocation::LineColumn {
+                line: 2,
+                column: Some(4),
+                byte_offset: Some(9),
+            }
+        );
+        assert_eq!(
+            SourceLocation::from(Location::Line(7)),
+            SourceLocation::LineColumn {
+                line: 7,
+                column: None,
+                byte_offset: None,
+            }
+        );
+        assert_eq!(
+            SourceLocation::from(Location::Cell { row: 3, column: 1 }),
+            SourceLocation::Pointer("/3/1".to_string())
+        );
+        assert_eq!(
+            SourceLocation::from(Location::Page(4)),
+            SourceLocation::Page { number: 4 }
+        );
+        assert_eq!(
+            SourceLocation::from(Location::ExifField("ImageDescription".to_string())),
+            SourceLocation::Pointer("ImageDescription".to_string())
+        );
+        assert_eq!(
+            SourceLocation::from(Location::Unknown),
+            SourceLocation::Unknown
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_source_location_test() {
+        use crate::link::Link;
+
+        let links =
+            crate::formats::json::scrape_from_slice(br#"{"a": [{"b": "https://test.com"}]}"#)
+                .unwrap();
+        assert_eq!(
+            links[0].source_location(),
+            SourceLocation::Pointer("/a/0/b".to_string())
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use crate::link::Link;
+use crate::processing::{dedup_key, normalize_host, SchemeFilter, UrlValidation};
+#[cfg(feature = "url")]
+use crate::processing::{normalize_links, NormalizeOptions};
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// The post-processing of the `scrape_with_options`-functions of all formats.
+///
+/// The default options keep all links unchanged, so `scrape_with_options(reader, &ScrapeOptions::default())`
+/// returns the same links as `scrape(reader)`. The steps are applied in the order of [`ScrapeOptions::apply`].
+///
+/// The `scrape_with_options`-functions of [`crate::formats::csv`] and [`crate::formats::json`] take the options
+/// of their format instead, pass their links to [`ScrapeOptions::apply`] to post-process them as well.
+/// # Example
+/// ```
+/// use link_scraper::options::ScrapeOptions;
+/// let options = ScrapeOptions::new()
+///     .allowed_schemes(["https"])
+///     .dedup()
+///     .max_links(10);
+/// # #[cfg(feature = "xml")] {
+/// let xml = r#"<a href="https://a.example.com">http://b.example.com https://a.example.com</a>"#;
+/// let links = link_scraper::formats::xml::scrape_with_options(xml.as_bytes(), &options).unwrap();
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(links[0].url, "https://a.example.com");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScrapeOptions {
+    url_transform: Option<UrlTransform>,
+    scheme_filter: Option<SchemeFilter>,
+    #[cfg(feature = "url")]
+    normalize: Option<NormalizeOptions>,
+    normalize_hosts: bool,
+    validation: Option<UrlValidation>,
+    dedup: bool,
+    max_links: Option<usize>,
+}
+
+impl ScrapeOptions {
+    /// The default options, that keep all links unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only keeps up to `max_links` links, counted after all other steps.
+    ///
+    /// The document is still scraped completely, use the `scrape_limited`-functions of the formats
+    /// that have one to stop parsing early.
+    pub fn max_links(mut self, max_links: usize) -> Self {
+        self.max_links = Some(max_links);
+        self
+    }
+
+    /// Only keeps the links with one of the given `schemes` and the links without a scheme,
+    /// see [`SchemeFilter::allow`]
+    pub fn allowed_schemes<I, S>(self, schemes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.scheme_filter(SchemeFilter::allow(schemes))
+    }
+
+    /// Only keeps the links permitted by `filter`, replacing the schemes set by [`ScrapeOptions::allowed_schemes`]
+    pub fn scheme_filter(mut self, filter: SchemeFilter) -> Self {
+        self.scheme_filter = Some(filter);
+        self
+    }
+
+    /// Only keeps the first link of every url, compared like in [`crate::processing::dedup_links`]
+    pub fn dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
+
+    /// Replaces the url of every link with its normalized form, see [`crate::processing::normalize_links`]
+    #[cfg(feature = "url")]
+    pub fn normalize(mut self, options: NormalizeOptions) -> Self {
+        self.normalize = Some(options);
+        self
+    }
+
+    /// Replaces the url of every link with the result of `transform`, before all other steps.
+    /// Only the url is changed, the location of the link and all its other fields are kept.
+    /// # Example
+    /// ```
+    /// use link_scraper::options::ScrapeOptions;
+    /// let options = ScrapeOptions::new()
+    ///     .transform_urls(|url| url.replace("https://cdn.example.com/", "https://proxy.example.com/cdn/"));
+    /// # #[cfg(feature = "plaintext")] {
+    /// let text = "https://cdn.example.com/logo.png";
+    /// let links = link_scraper::formats::plaintext::scrape_with_options(text.as_bytes(), &options).unwrap();
+    /// assert_eq!(links[0].url, "https://proxy.example.com/cdn/logo.png");
+    /// # }
+    /// ```
+    pub fn transform_urls<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.url_transform = Some(UrlTransform(Arc::new(transform)));
+        self
+    }
+
+    /// Lowercases only the scheme and host of every url, see [`crate::processing::normalize_host`].
+    /// Unlike `ScrapeOptions::normalize` of the `url`-feature the path, query and fragment keep their case.
+    pub fn normalize_hosts(mut self) -> Self {
+        self.normalize_hosts = true;
+        self
+    }
+
+    /// Removes the links whose url fails the `validation`, like urls with an unknown top-level domain.
+    /// Use [`crate::processing::validate_links`] to flag these links instead.
+    pub fn validate_urls(mut self, validation: UrlValidation) -> Self {
+        self.validation = Some(validation);
+        self
+    }
+
+    /// Transforms the urls, filters the links by their scheme, normalizes the urls and then their hosts,
+    /// removes the invalid urls, deduplicates and finally limits them (in this order)
+    pub fn apply<L: Link>(&self, mut links: Vec<L>) -> Vec<L> {
+        if let Some(UrlTransform(transform)) = &self.url_transform {
+            for link in &mut links {
+                let url = transform(link.url());
+                link.set_url(url);
+            }
+        }
+        let mut links = match &self.scheme_filter {
+            Some(filter) => crate::processing::filter_schemes(links, filter),
+            None => links,
+        };
+        #[cfg(feature = "url")]
+        if let Some(options) = &self.normalize {
+            normalize_links(&mut links, options);
+        }
+        if self.normalize_hosts {
+            for link in &mut links {
+                let url = normalize_host(link.url());
+                link.set_url(url);
+            }
+        }
+        if let Some(validation) = &self.validation {
+            links.retain(|link| validation.is_valid(link.url()));
+        }
+        if self.dedup {
+            let mut seen = HashSet::new();
+            links.retain(|link| seen.insert(dedup_key(link.url())));
+        }
+        if let Some(max_links) = self.max_links {
+            links.truncate(max_links);
+        }
+        links
+    }
+}
+
+/// The function set by [`ScrapeOptions::transform_urls`].
+/// Two transforms are only equal if they are the same function.
+#[derive(Clone)]
+struct UrlTransform(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl Debug for UrlTransform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("UrlTransform")
+    }
+}
+
+impl PartialEq for UrlTransform {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(all(test, feature = "plaintext"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_test() {
+        let text = "https://a.test.com ftp://b.test.com HTTPS://A.test.com/ https://c.test.com https://d.test.com";
+        let scrape = |options: &ScrapeOptions| -> Vec<String> {
+            crate::formats::plaintext::scrape_with_options(text.as_bytes(), options)
+                .unwrap()
+                .into_iter()
+                .map(|it| it.url)
+                .collect()
+        };
+        assert_eq!(scrape(&ScrapeOptions::default()).len(), 5);
+        assert_eq!(
+            scrape(
+                &ScrapeOptions::new()
+                    .allowed_schemes(["https"])
+                    .dedup()
+                    .max_links(2)
+            ),
+            vec!["https://a.test.com", "https://c.test.com"]
+        );
+    }
+
+    #[test]
+    fn normalize_hosts_test() {
+        let text = "HTTPS://Case.Test.COM/Path/File.PDF?Id=AbC";
+        let links = crate::formats::plaintext::scrape_with_options(
+            text.as_bytes(),
+            &ScrapeOptions::new().normalize_hosts(),
+        )
+        .unwrap();
+        assert_eq!(links[0].url, "https://case.test.com/Path/File.PDF?Id=AbC");
+    }
+
+    #[test]
+    fn validate_urls_test() {
+        let text = "http://a.co https://www.test.com https://www.test.xyzzy";
+        let validation = UrlValidation::new().min_length(12);
+        #[cfg(feature = "publicsuffix")]
+        let validation = validation.known_tld();
+        let links = crate::formats::plaintext::scrape_with_options(
+            text.as_bytes(),
+            &ScrapeOptions::new().validate_urls(validation),
+        )
+        .unwrap();
+        let found: Vec<&str> = links.iter().map(|it| it.url.as_str()).collect();
+        if cfg!(feature = "publicsuffix") {
+            assert_eq!(found, vec!["https://www.test.com"]);
+        } else {
+            assert_eq!(
+                found,
+                vec!["https://www.test.com", "https://www.test.xyzzy"]
+            );
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! The xml-crate does not implement serde for its types, so they are mirrored here
+//! and can be used with `#[serde(with = "...")]`.
+
+use serde::{Deserialize, Serialize};
+use xml::common::TextPosition;
+#[cfg(any(feature = "xml", feature = "xlink"))]
+use {
+    serde::{Deserializer, Serializer},
+    xml::attribute::OwnedAttribute,
+    xml::name::OwnedName,
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "TextPosition")]
+pub(crate) struct TextPositionDef {
+    pub row: u64,
+    pub column: u64,
+}
+
+#[cfg(any(feature = "xml", feature = "xlink"))]
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "OwnedName")]
+pub(crate) struct OwnedNameDef {
+    pub local_name: String,
+    pub namespace: Option<String>,
+    pub prefix: Option<String>,
+}
+
+#[cfg(any(feature = "xml", feature = "xlink"))]
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "OwnedAttribute")]
+pub(crate) struct OwnedAttributeDef {
+    #[serde(with = "OwnedNameDef")]
+    pub name: OwnedName,
+    pub value: String,
+}
+
+#[cfg(any(feature = "xml", feature = "xlink"))]
+#[derive(Serialize, Deserialize)]
+struct OwnedNameWrapper(#[serde(with = "OwnedNameDef")] OwnedName);
+
+#[cfg(any(feature = "xml", feature = "xlink"))]
+pub(crate) mod vec_owned_name {
+    use super::*;
+
+    pub fn serialize<S>(value: &[OwnedName], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value
+            .iter()
+            .cloned()
+            .map(OwnedNameWrapper)
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<OwnedName>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<OwnedNameWrapper>::deserialize(deserializer)?
+            .into_iter()
+            .map(|wrapper| wrapper.0)
+            .collect())
+    }
+}